@@ -0,0 +1,88 @@
+//! End-to-end latency of audio chunks sent through default and low latency streaming
+//! recognizer (see Recognizer::create_streaming_recognizer_low_latency), measured from
+//! the moment chunk is sent into audio sink until it is received by mock speech server.
+//! cargo bench --features testing --bench low_latency
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::StreamingRecognitionConfig;
+use google_cognitive_apis::speechtotext::recognizer_v2::Recognizer;
+use google_cognitive_apis::testing::MockSpeech;
+use std::time::{Duration, Instant};
+
+const RECOGNIZER: &str = "projects/p/locations/global/recognizers/_";
+
+/// 20ms of 16kHz LINEAR16 mono audio
+const CHUNK_BYTES: usize = 640;
+const CHUNK_DURATION: Duration = Duration::from_millis(20);
+
+/// Sends chunks (at real-time pace if paced) and returns latency of every chunk.
+async fn chunk_latencies(low_latency: bool, chunks: usize, paced: bool) -> Vec<Duration> {
+    let mock = MockSpeech::new();
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer = Recognizer::create_streaming_recognizer_low_latency_with_channel(
+        channel,
+        "Bearer bench".to_string(),
+        StreamingRecognitionConfig::default(),
+        low_latency,
+        RECOGNIZER.to_string(),
+    )
+    .await
+    .unwrap();
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    let producer = tokio::spawn(async move {
+        let mut sent_at = Vec::with_capacity(chunks);
+        for _ in 0..chunks {
+            sent_at.push(Instant::now());
+            audio_sink
+                .send(Recognizer::streaming_request_from_bytes(
+                    vec![0u8; CHUNK_BYTES],
+                    String::new(),
+                ))
+                .await
+                .unwrap();
+            if paced {
+                tokio::time::sleep(CHUNK_DURATION).await;
+            }
+        }
+        sent_at
+    });
+    recognizer.streaming_recognize().await.unwrap();
+    let sent_at = producer.await.unwrap();
+
+    // first request of the stream is streaming config
+    let received_at = mock.streaming_request_times().remove(0);
+    sent_at
+        .iter()
+        .zip(&received_at[1..])
+        .map(|(sent, received)| received.saturating_duration_since(*sent))
+        .collect()
+}
+
+fn report(scenario: &str, low_latency: bool, mut latencies: Vec<Duration>) {
+    latencies.sort();
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    println!(
+        "{:<7} low_latency={:<5} chunks={:<5} mean={:>10.3?} p50={:>10.3?} p99={:>10.3?} max={:>10.3?}",
+        scenario,
+        low_latency,
+        latencies.len(),
+        mean,
+        percentile(50),
+        percentile(99),
+        latencies[latencies.len() - 1]
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    // live audio (e.g. microphone), chunks are produced at real-time pace
+    for low_latency in [false, true] {
+        let latencies = chunk_latencies(low_latency, 150, true).await;
+        report("paced", low_latency, latencies);
+    }
+    // backlog of audio (e.g. after stalled capture), chunks are produced at once
+    for low_latency in [false, true] {
+        let latencies = chunk_latencies(low_latency, 2000, false).await;
+        report("burst", low_latency, latencies);
+    }
+}
@@ -0,0 +1,83 @@
+//! Throughput of recognize calls over single connection and over pool of connections
+//! (common::PooledChannel). Mock speech server delays every response and limits number
+//! of concurrent calls per connection, similarly to Google frontends.
+//! cargo bench --features testing --bench pooled_channel
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::{
+    recognize_request::AudioSource, RecognizeRequest,
+};
+use google_cognitive_apis::common::PooledChannel;
+use google_cognitive_apis::speechtotext::recognizer_v2::Recognizer;
+use google_cognitive_apis::testing::MockSpeech;
+use std::time::{Duration, Instant};
+use tonic::transport::{Channel, Endpoint};
+
+const RECOGNIZER: &str = "projects/p/locations/global/recognizers/_";
+
+const RECOGNIZE_DELAY: Duration = Duration::from_millis(5);
+const MAX_CONCURRENT_STREAMS: u32 = 16;
+const CALLERS: usize = 128;
+const CALLS_PER_CALLER: usize = 40;
+
+/// Runs callers_count concurrent tasks, each issuing given number of recognize calls.
+async fn run_callers(channel: &Channel, callers_count: usize, calls: usize) {
+    let mut callers = vec![];
+    for _ in 0..callers_count {
+        let channel = channel.clone();
+        callers.push(tokio::spawn(async move {
+            let mut recognizer = Recognizer::create_synchronous_recognizer_with_channel(
+                channel,
+                "Bearer bench".into(),
+            )
+            .await
+            .unwrap();
+            for _ in 0..calls {
+                recognizer
+                    .recognize(RecognizeRequest {
+                        recognizer: RECOGNIZER.to_string(),
+                        audio_source: Some(AudioSource::Content(vec![0u8; 3200])),
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+            }
+        }));
+    }
+    for caller in callers {
+        caller.await.unwrap();
+    }
+}
+
+/// Returns recognize calls/sec of CALLERS concurrent tasks over channel.
+async fn throughput(channel: Channel) -> f64 {
+    // connections are established and server settings (max concurrent streams) received
+    // before measurement, otherwise server refuses streams over the limit
+    run_callers(&channel, MAX_CONCURRENT_STREAMS as usize, 1).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let started = Instant::now();
+    run_callers(&channel, CALLERS, CALLS_PER_CALLER).await;
+    (CALLERS * CALLS_PER_CALLER) as f64 / started.elapsed().as_secs_f64()
+}
+
+#[tokio::main]
+async fn main() {
+    let mock = MockSpeech::new();
+    mock.set_recognize_delay(RECOGNIZE_DELAY);
+    mock.set_max_concurrent_streams(MAX_CONCURRENT_STREAMS);
+    let addr = mock.serve_addr().await.unwrap();
+    let endpoint = Endpoint::from_shared(format!("http://{}", addr)).unwrap();
+
+    let single = endpoint.connect().await.unwrap();
+    println!(
+        "single connection  calls/sec={:>8.0}",
+        throughput(single).await
+    );
+    for pool_size in [2, 4, 8] {
+        let pool = PooledChannel::from_endpoint(endpoint.clone(), pool_size);
+        println!(
+            "pool_size={:<8} calls/sec={:>8.0}",
+            pool_size,
+            throughput(pool.channel()).await
+        );
+    }
+}
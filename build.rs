@@ -20,7 +20,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tonic_build::configure()
         .build_client(true)
-        .build_server(false)
+        // server stubs are used only by mock servers in tests
+        .build_server(true)
+        .server_mod_attribute(".", r#"#[cfg(feature = "testing")]"#)
         .out_dir("src/grpc_stubs")
         .compile(
             &[
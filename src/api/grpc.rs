@@ -1,4 +1,8 @@
 //! This module wraps underlying GRPC stubs and organizes them into nested module structure.
+// generated stubs are not clippy clean, we do not want to touch them
+#![allow(clippy::doc_overindented_list_items)]
+#![allow(clippy::doc_lazy_continuation)]
+#![allow(clippy::large_enum_variant)]
 macro_rules! include_proto {
     ($package: tt) => {
         include!(concat!("../grpc_stubs/", concat!($package, ".rs")));
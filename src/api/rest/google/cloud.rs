@@ -1,3 +1,6 @@
+#[cfg(feature = "dialogflow")]
 pub mod dialogflow;
+#[cfg(feature = "speech-to-text")]
 pub mod speechtotext;
+#[cfg(feature = "text-to-speech")]
 pub mod texttospeech;
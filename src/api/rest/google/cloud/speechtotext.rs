@@ -1,4 +1,6 @@
 //! Contains speech-to-text recognizers (v1 and v1p1beta1).
 pub mod v1;
 pub mod v1p1beta1;
-pub mod v2;
\ No newline at end of file
+// hand formatted, excluded from cargo fmt
+#[rustfmt::skip]
+pub mod v2;
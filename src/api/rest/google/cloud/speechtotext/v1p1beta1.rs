@@ -1,5 +1,6 @@
 #![allow(clippy::from_over_into)]
 #![allow(clippy::manual_unwrap_or)]
+#![allow(clippy::manual_unwrap_or_default)]
 #![allow(clippy::manual_map)]
 use crate::api::grpc::google::cloud::speechtotext::v1p1beta1::SpeechAdaptation as GrpcSpeechAdaptation;
 /// This module DOES NOT address all the differences between GRPC v1 and v1p1beta1 proto definitions
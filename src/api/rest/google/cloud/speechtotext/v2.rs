@@ -367,7 +367,8 @@ mod tests {
                 ],
                 "diarizationConfig": {
                     "enableSpeakerDiarization": false,
-                    "minSpeakerCount": 2
+                    "minSpeakerCount": 2,
+                    "maxSpeakerCount": 6
                 },
                 "adaptation": {
                     "phraseSets": [
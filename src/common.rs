@@ -1,35 +1,122 @@
 //! Contains common utility & convenience functions.
 //! All functions here are intended for internal use only.
-use crate::errors::Result;
+use crate::credentials::{token_from_json, CredentialProvider};
+use crate::errors::{Error, Result};
 use crate::CERTIFICATES;
-use gouth::Builder;
-use std::sync::Arc;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tonic::{
-    metadata::{Ascii, MetadataValue},
+    metadata::{Ascii, MetadataMap, MetadataValue},
     service::Interceptor,
-    transport::{Certificate, Channel, ClientTlsConfig},
-    Status,
+    transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Uri},
+    Code, Status,
 };
+use tower::discover::Change;
+use tower::Service;
+
+/// conversions of protobuf well-known types
+pub mod convert;
+
+/// Number of attempts to get token from credential provider before request fails.
+const TOKEN_REFRESH_ATTEMPTS: usize = 3;
+
+/// Delay before second attempt to get token, doubled before every further attempt.
+const TOKEN_REFRESH_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Metadata key marking UNAUTHENTICATED statuses caused by failed token refresh
+/// (as opposed to statuses returned by Google). See errors::Error code AUTH_REFRESH.
+pub(crate) const AUTH_REFRESH_FAILED_KEY: &str = "x-auth-refresh-failed";
 
 #[derive(Clone)]
-pub struct TokenInterceptor(Arc<String>);
+enum TokenSource {
+    /// token obtained when client was created
+    Static(Arc<String>),
+    /// token obtained from provider before every request (see TokenInterceptor::refresh),
+    /// cached so that synchronous interceptor does not call provider
+    Provider(Arc<dyn CredentialProvider>, Arc<Mutex<Option<Arc<String>>>>),
+}
+
+#[derive(Clone)]
+pub struct TokenInterceptor(TokenSource);
 impl TokenInterceptor {
     fn new(token_header_val: Arc<String>) -> TokenInterceptor {
-        TokenInterceptor(token_header_val)
+        TokenInterceptor(TokenSource::Static(token_header_val))
+    }
+
+    /// Gets token from credential provider (see token_with_retry) and caches it for the
+    /// interceptor, to be called before every request. Provider (which may refresh token
+    /// with blocking HTTP) is called on blocking thread pool and backoff does not block,
+    /// i.e. tokio workers are never stalled. Static tokens have nothing to refresh.
+    /// Returns error with code AUTH_REFRESH if provider keeps failing.
+    pub(crate) async fn refresh(&self) -> Result<()> {
+        if let TokenSource::Provider(provider, cached) = &self.0 {
+            let token = token_with_retry(provider.clone())
+                .await
+                .map_err(auth_refresh_error)?;
+            if let Ok(mut cached) = cached.lock() {
+                *cached = Some(Arc::new(token));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn auth_refresh_error(error: Error) -> Error {
+    Error::new_with_code(
+        format!("Error when getting token from provider: {}", error.message),
+        "AUTH_REFRESH".to_string(),
+    )
+}
+/// Token itself is never printed.
+impl std::fmt::Debug for TokenInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            TokenSource::Static(_) => f.write_str("TokenInterceptor(static)"),
+            TokenSource::Provider(..) => f.write_str("TokenInterceptor(provider)"),
+        }
     }
 }
 pub fn new_interceptor(token_header_val: Arc<String>) -> TokenInterceptor {
     TokenInterceptor::new(token_header_val)
 }
+/// Creates interceptor which uses token obtained from credential provider. Library clients
+/// refresh the token (asynchronously, with retries) before every request. If no token was
+/// refreshed yet (e.g. interceptor used with custom client) provider is called directly
+/// from the interceptor, i.e. synchronously and without retries.
+pub fn new_provider_interceptor(provider: Arc<dyn CredentialProvider>) -> TokenInterceptor {
+    TokenInterceptor(TokenSource::Provider(provider, Arc::new(Mutex::new(None))))
+}
 impl Interceptor for TokenInterceptor {
     fn call(
         &mut self,
         request: tonic::Request<()>,
     ) -> core::result::Result<tonic::Request<()>, Status> {
         let mut req = request;
+        let token_header_val = match &self.0 {
+            TokenSource::Static(token_header_val) => token_header_val.clone(),
+            TokenSource::Provider(provider, cached) => match cached_or_provided(provider, cached) {
+                Ok(token) => token,
+                Err(some_error) => {
+                    // marked so that failure can be reported with AUTH_REFRESH error code
+                    let mut metadata = MetadataMap::new();
+                    metadata.insert(AUTH_REFRESH_FAILED_KEY, MetadataValue::from_static("true"));
+                    return Err(Status::with_metadata(
+                        Code::Unauthenticated,
+                        format!(
+                            "new_provider_interceptor: Error when getting token from provider {:?}",
+                            some_error
+                        ),
+                        metadata,
+                    ));
+                }
+            },
+        };
         #[allow(deprecated)]
-        let meta_result = MetadataValue::<Ascii>::from_str(&self.0);
-        return match meta_result {
+        let meta_result = MetadataValue::<Ascii>::from_str(&token_header_val);
+        match meta_result {
             Ok(meta) => {
                 req.metadata_mut().insert("authorization", meta);
 
@@ -45,7 +132,75 @@ impl Interceptor for TokenInterceptor {
                 "new_interceptor: Error when getting MetadataValue from token {:?}",
                 some_error
             ))),
-        };
+        }
+    }
+}
+
+/// Returns token refreshed ahead of request (see TokenInterceptor::refresh). Falls back
+/// to single (blocking) provider call if token was not refreshed yet.
+fn cached_or_provided(
+    provider: &Arc<dyn CredentialProvider>,
+    cached: &Mutex<Option<Arc<String>>>,
+) -> Result<Arc<String>> {
+    let cached = cached.lock().ok().and_then(|cached| cached.clone());
+    match cached {
+        Some(token) => Ok(token),
+        None => Ok(Arc::new(provider.token()?)),
+    }
+}
+
+/// Gets token from provider on blocking thread pool. Transient failures (e.g. metadata
+/// server errors) are retried up to TOKEN_REFRESH_ATTEMPTS times with exponential backoff
+/// (TOKEN_REFRESH_BACKOFF, 2 * TOKEN_REFRESH_BACKOFF, ...), last error is returned otherwise.
+async fn token_with_retry(provider: Arc<dyn CredentialProvider>) -> Result<String> {
+    let mut backoff = TOKEN_REFRESH_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        let attempt_provider = provider.clone();
+        let result = tokio::task::spawn_blocking(move || attempt_provider.token())
+            .await
+            .map_err(|error| Error::new(format!("Token provider task failed: {}", error)))
+            .and_then(|result| result);
+        if result.is_ok() || attempt == TOKEN_REFRESH_ATTEMPTS {
+            return result;
+        }
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+/// Best-effort state of GRPC channel used by API client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Channel was successfully connected when client was created.
+    Connected,
+    /// Channel state cannot be determined.
+    Unknown,
+}
+
+/// Describes GRPC endpoint client is connected to. Tonic channels do not expose
+/// much information about underlying connection, hence this struct contains
+/// mostly what was configured when client was created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// channel URL, e.g. https://speech.googleapis.com
+    pub endpoint_url: String,
+
+    /// TLS domain name, e.g. speech.googleapis.com
+    pub domain_name: String,
+
+    /// best-effort readiness state of the channel
+    pub state: ConnectionState,
+}
+
+impl ConnectionInfo {
+    pub(crate) fn new(domain_name: &str, endpoint_url: &str, state: ConnectionState) -> Self {
+        ConnectionInfo {
+            endpoint_url: endpoint_url.to_string(),
+            domain_name: domain_name.to_string(),
+            state,
+        }
     }
 }
 
@@ -75,11 +230,260 @@ pub(crate) async fn new_grpc_channel(
     }
 }
 
+/// Pool of connections to the same endpoint. Single HTTP/2 connection limits number of
+/// concurrent streams (typically 100), pooled channel spreads calls over pool_size
+/// connections (connections are opened lazily).
+///
+/// Calls are balanced with power of two choices (tower p2c, see Channel::balance_channel),
+/// not round-robin: for every call two connections are picked at random and the one with
+/// fewer pending requests is used. Spread is therefore load aware but not strictly even.
+///
+/// Intended for servers issuing many concurrent synchronous recognize calls. Returned
+/// channel is passed to Recognizer::create_*_recognizer_with_channel. Start with pool size
+/// of expected concurrent calls / 100 (plus headroom, since spread is not even) and
+/// increase while throughput improves.
+#[derive(Debug, Clone)]
+pub struct PooledChannel {
+    channel: Channel,
+    pool_size: usize,
+    stream_limiter: Option<StreamLimiter>,
+}
+
+impl PooledChannel {
+    /// Creates pool of TLS connections to Google API, e.g. speech.googleapis.com
+    /// and https://speech.googleapis.com. Must be called within tokio runtime.
+    pub fn new(
+        domain_name: &'static str,
+        channel_url: &'static str,
+        pool_size: usize,
+    ) -> Result<Self> {
+        let tls_config = ClientTlsConfig::new()
+            .ca_certificate(Certificate::from_pem(CERTIFICATES))
+            .domain_name(domain_name);
+        let endpoint = Channel::from_static(channel_url).tls_config(tls_config)?;
+        Ok(PooledChannel::from_endpoint(endpoint, pool_size))
+    }
+
+    /// Creates pool of pool_size (at least 1) connections to given endpoint.
+    /// Must be called within tokio runtime.
+    pub fn from_endpoint(endpoint: Endpoint, pool_size: usize) -> Self {
+        let pool_size = pool_size.max(1);
+        let (channel, sender) = Channel::balance_channel(pool_size);
+        for index in 0..pool_size {
+            // capacity of change channel equals pool size, i.e. sending never fails
+            let _ = sender.try_send(Change::Insert(index, endpoint.clone()));
+        }
+        PooledChannel {
+            channel,
+            pool_size,
+            stream_limiter: None,
+        }
+    }
+
+    /// Limits number of concurrent streaming calls over this channel (see StreamLimiter),
+    /// e.g. to pool_size * 100 (typical HTTP/2 max concurrent streams per connection).
+    pub fn with_max_concurrent_streams(
+        mut self,
+        max_concurrent_streams: usize,
+        policy: OverflowPolicy,
+    ) -> Self {
+        self.stream_limiter = Some(StreamLimiter::new(max_concurrent_streams, policy));
+        self
+    }
+
+    /// Returns limiter of concurrent streams (if configured) to be passed
+    /// to Recognizer::set_stream_limiter of all recognizers sharing this channel.
+    pub fn stream_limiter(&self) -> Option<StreamLimiter> {
+        self.stream_limiter.clone()
+    }
+
+    /// Returns channel balancing calls (p2c) over pooled connections.
+    pub fn channel(&self) -> Channel {
+        self.channel.clone()
+    }
+
+    /// Returns number of pooled connections.
+    pub fn pool_size(&self) -> usize {
+        self.pool_size
+    }
+}
+
+/// What happens with streaming call exceeding StreamLimiter limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// call waits until some other stream finishes
+    Queue,
+    /// call fails immediately with error code STREAM_LIMIT_EXCEEDED
+    Reject,
+}
+
+/// Limits number of concurrent streaming calls of recognizers sharing the same channel.
+/// Exceeding HTTP/2 max concurrent streams otherwise silently queues streams inside
+/// the connection (head-of-line blocking). Clones share the same limit.
+#[derive(Debug, Clone)]
+pub struct StreamLimiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrent_streams: usize,
+    policy: OverflowPolicy,
+}
+
+impl StreamLimiter {
+    /// Creates limiter allowing max_concurrent_streams (at least 1) concurrent streams.
+    pub fn new(max_concurrent_streams: usize, policy: OverflowPolicy) -> Self {
+        let max_concurrent_streams = max_concurrent_streams.max(1);
+        StreamLimiter {
+            semaphore: Arc::new(Semaphore::new(max_concurrent_streams)),
+            max_concurrent_streams,
+            policy,
+        }
+    }
+
+    /// Returns max number of concurrent streams.
+    pub fn max_concurrent_streams(&self) -> usize {
+        self.max_concurrent_streams
+    }
+
+    /// Returns number of streams currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent_streams - self.semaphore.available_permits()
+    }
+
+    /// Acquires permit for one stream (released when dropped) according to policy.
+    pub(crate) async fn acquire(&self) -> Result<OwnedSemaphorePermit> {
+        let permit = match self.policy {
+            OverflowPolicy::Queue => self.semaphore.clone().acquire_owned().await.ok(),
+            OverflowPolicy::Reject => self.semaphore.clone().try_acquire_owned().ok(),
+        };
+        permit.ok_or_else(|| {
+            Error::new_with_code(
+                format!(
+                    "Max concurrent streams ({}) exceeded",
+                    self.max_concurrent_streams
+                ),
+                "STREAM_LIMIT_EXCEEDED".to_string(),
+            )
+        })
+    }
+}
+
+/// Creates channel using custom connector (tower service returning connection for URI),
+/// e.g. to connect via unix socket or in-memory transport (sidecars, tests). Uri is still
+/// required by GRPC (e.g. http://localhost), connector may ignore it. Returned channel is
+/// passed to Recognizer::create_*_recognizer_with_channel.
+pub async fn new_channel_with_connector<C>(uri: &str, connector: C) -> Result<Channel>
+where
+    C: Service<Uri> + Send + 'static,
+    C::Response: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    C::Future: Send + 'static,
+    C::Error: std::error::Error + Send + Sync + 'static,
+{
+    let endpoint = Endpoint::from_shared(uri.to_string())
+        .map_err(|error| Error::new(format!("Invalid channel uri {}: {}", uri, error)))?;
+    Ok(endpoint.connect_with_connector(connector).await?)
+}
+
 /// Returns google token (String value) from
 /// Google Cloud Platform project JSON credentials (provided as String).
 #[allow(clippy::rc_buffer)]
 pub(crate) fn get_token(google_credentials: impl AsRef<str>) -> Result<Arc<String>> {
-    let token = Builder::new().json(google_credentials).build()?;
+    let token = token_from_json(google_credentials.as_ref())?;
     let token_header_val: Arc<String> = token.header_value()?;
     Ok(token_header_val)
 }
+
+/// Tolerated difference (seconds) between local clock and token issuer clock.
+const CLOCK_SKEW_LEEWAY_SECS: u64 = 60;
+
+/// Checks validity window (iat, nbf, exp claims) of JWT token against local clock.
+/// Signature is NOT verified. Token can be passed with or without "Bearer " prefix.
+/// Returns error with code CLOCK_SKEW if local time is outside of validity window
+/// (with CLOCK_SKEW_LEEWAY_SECS tolerance), i.e. if local clock is not synchronized
+/// or token expired. Opaque (non-JWT) tokens, e.g. OAuth2 access tokens ya29...,
+/// cannot be checked and are accepted.
+pub fn validate_token(token: &str) -> Result<()> {
+    let token = token.strip_prefix("Bearer ").unwrap_or(token);
+    if token.split('.').count() != 3 {
+        return Ok(());
+    }
+    let claims = match jsonwebtoken::dangerous_insecure_decode::<Value>(token) {
+        Ok(token_data) => token_data.claims,
+        Err(_) => return Ok(()),
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let claim = |name: &str| claims.get(name).and_then(Value::as_u64);
+    let clock_skew = |message: String| {
+        Err(Error::new_with_code(
+            format!(
+                "{}. Make sure local clock is synchronized (e.g. via NTP).",
+                message
+            ),
+            "CLOCK_SKEW".to_string(),
+        ))
+    };
+
+    for name in ["iat", "nbf"] {
+        if let Some(not_before) = claim(name) {
+            if now + CLOCK_SKEW_LEEWAY_SECS < not_before {
+                return clock_skew(format!(
+                    "Token is not valid yet ({} is {}s in the future)",
+                    name,
+                    not_before - now
+                ));
+            }
+        }
+    }
+    if let Some(expires_at) = claim("exp") {
+        if expires_at + CLOCK_SKEW_LEEWAY_SECS < now {
+            return clock_skew(format!(
+                "Token expired {}s ago (or local clock is ahead)",
+                now - expires_at
+            ));
+        }
+    }
+    Ok(())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn jwt(claims: Value) -> String {
+        jsonwebtoken::encode(
+            &jsonwebtoken::Header::default(),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap()
+    }
+
+    // cargo test -- --show-output test_validate_token
+    #[test]
+    fn test_validate_token() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let valid = jwt(serde_json::json!({"iat": now, "exp": now + 3600}));
+        assert!(validate_token(&valid).is_ok());
+        assert!(validate_token(&format!("Bearer {}", valid)).is_ok());
+
+        let future = jwt(serde_json::json!({"iat": now + 600, "exp": now + 4200}));
+        let error = validate_token(&future).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("CLOCK_SKEW"));
+
+        let expired = jwt(serde_json::json!({"iat": now - 7200, "exp": now - 3600}));
+        let error = validate_token(&expired).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("CLOCK_SKEW"));
+
+        // small skew is tolerated
+        let skewed = jwt(serde_json::json!({"nbf": now + 30, "exp": now + 3600}));
+        assert!(validate_token(&skewed).is_ok());
+
+        // opaque tokens cannot be checked
+        assert!(validate_token("Bearer ya29.a0AfH6SM").is_ok());
+    }
+}
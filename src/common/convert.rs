@@ -0,0 +1,153 @@
+//! Conversions between protobuf well-known types (prost_types::Duration,
+//! prost_types::Timestamp) and std::time types.
+//!
+//! Protobuf values are normalized first (nanos outside 0..1_000_000_000 are carried
+//! into seconds), values outside of range of the target type are clamped:
+//! * negative durations are converted to zero std durations
+//! * durations and timestamps are clamped to range allowed by protobuf specification
+//!   (+-315,576,000,000 seconds, years 0001 - 9999 respectively)
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Max seconds of protobuf duration (about 10,000 years).
+pub const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+/// Seconds of timestamp 0001-01-01T00:00:00Z (min protobuf timestamp).
+pub const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+
+/// Seconds of timestamp 9999-12-31T23:59:59Z (max protobuf timestamp).
+pub const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Converts protobuf duration into std duration. Negative durations are mapped to zero.
+pub fn to_std_duration(duration: &prost_types::Duration) -> Duration {
+    let nanos = total_nanos(duration.seconds, duration.nanos)
+        .min(MAX_DURATION_SECONDS as i128 * NANOS_PER_SECOND);
+    if nanos <= 0 {
+        Duration::from_secs(0)
+    } else {
+        Duration::new(
+            (nanos / NANOS_PER_SECOND) as u64,
+            (nanos % NANOS_PER_SECOND) as u32,
+        )
+    }
+}
+
+/// Converts std duration into protobuf duration. Durations longer than
+/// MAX_DURATION_SECONDS are clamped.
+pub fn to_prost_duration(duration: Duration) -> prost_types::Duration {
+    if duration.as_secs() > MAX_DURATION_SECONDS as u64 {
+        return prost_types::Duration {
+            seconds: MAX_DURATION_SECONDS,
+            nanos: 0,
+        };
+    }
+    prost_types::Duration {
+        seconds: duration.as_secs() as i64,
+        nanos: duration.subsec_nanos() as i32,
+    }
+}
+
+/// Converts protobuf timestamp into system time. Timestamps before unix epoch are
+/// supported, timestamps outside of protobuf range (or not representable by system
+/// time of the platform) are clamped.
+pub fn to_system_time(timestamp: &prost_types::Timestamp) -> SystemTime {
+    let nanos = total_nanos(timestamp.seconds, timestamp.nanos).clamp(
+        MIN_TIMESTAMP_SECONDS as i128 * NANOS_PER_SECOND,
+        (MAX_TIMESTAMP_SECONDS as i128 + 1) * NANOS_PER_SECOND - 1,
+    );
+    let offset = Duration::new(
+        (nanos.abs() / NANOS_PER_SECOND) as u64,
+        (nanos.abs() % NANOS_PER_SECOND) as u32,
+    );
+    let time = if nanos < 0 {
+        UNIX_EPOCH.checked_sub(offset)
+    } else {
+        UNIX_EPOCH.checked_add(offset)
+    };
+    time.unwrap_or(UNIX_EPOCH)
+}
+
+/// Converts system time into protobuf timestamp (nanos are always non-negative,
+/// i.e. times before unix epoch have negative seconds). Times outside of protobuf
+/// range are clamped.
+pub fn to_prost_timestamp(time: SystemTime) -> prost_types::Timestamp {
+    let nanos = match time.duration_since(UNIX_EPOCH) {
+        Ok(offset) => offset.as_nanos() as i128,
+        Err(error) => -(error.duration().as_nanos() as i128),
+    }
+    .clamp(
+        MIN_TIMESTAMP_SECONDS as i128 * NANOS_PER_SECOND,
+        (MAX_TIMESTAMP_SECONDS as i128 + 1) * NANOS_PER_SECOND - 1,
+    );
+    prost_types::Timestamp {
+        seconds: nanos.div_euclid(NANOS_PER_SECOND) as i64,
+        nanos: nanos.rem_euclid(NANOS_PER_SECOND) as i32,
+    }
+}
+
+fn total_nanos(seconds: i64, nanos: i32) -> i128 {
+    seconds as i128 * NANOS_PER_SECOND + nanos as i128
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_duration_conversions
+    #[test]
+    fn test_duration_conversions() {
+        let duration = |seconds: i64, nanos: i32| prost_types::Duration { seconds, nanos };
+        assert_eq!(
+            to_std_duration(&duration(1, 500_000_000)),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(to_std_duration(&duration(-1, 0)), Duration::from_secs(0));
+        assert_eq!(to_std_duration(&duration(0, -1)), Duration::from_secs(0));
+        // nanos are normalized
+        assert_eq!(
+            to_std_duration(&duration(1, -500_000_000)),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            to_std_duration(&duration(0, 1_500_000_000)),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            to_std_duration(&duration(i64::MAX, 0)),
+            Duration::from_secs(MAX_DURATION_SECONDS as u64)
+        );
+
+        assert_eq!(
+            to_prost_duration(Duration::from_millis(1500)),
+            duration(1, 500_000_000)
+        );
+        assert_eq!(
+            to_prost_duration(Duration::from_secs(u64::MAX)),
+            duration(MAX_DURATION_SECONDS, 0)
+        );
+    }
+
+    // cargo test -- --show-output test_timestamp_conversions
+    #[test]
+    fn test_timestamp_conversions() {
+        let timestamp = |seconds: i64, nanos: i32| prost_types::Timestamp { seconds, nanos };
+        let time = UNIX_EPOCH + Duration::from_millis(1500);
+        assert_eq!(to_system_time(&timestamp(1, 500_000_000)), time);
+        assert_eq!(to_prost_timestamp(time), timestamp(1, 500_000_000));
+
+        // before unix epoch
+        let time = UNIX_EPOCH - Duration::from_millis(1500);
+        assert_eq!(to_system_time(&timestamp(-2, 500_000_000)), time);
+        assert_eq!(to_prost_timestamp(time), timestamp(-2, 500_000_000));
+
+        assert_eq!(
+            to_prost_timestamp(to_system_time(&timestamp(i64::MAX, 0))),
+            timestamp(MAX_TIMESTAMP_SECONDS, 999_999_999)
+        );
+        assert_eq!(
+            to_prost_timestamp(to_system_time(&timestamp(i64::MIN, 0))),
+            timestamp(MIN_TIMESTAMP_SECONDS, 0)
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! Pluggable sources of Google auth tokens.
+//! By default clients are created from Google Cloud Platform JSON credentials.
+//! Implement CredentialProvider to obtain tokens e.g. from Vault or Secret Manager,
+//! or to support rotating credentials. Provider is called by GRPC interceptor
+//! for every request, i.e. it should cache tokens until they expire.
+use crate::errors::{Error, Result};
+use gouth::{Builder, ErrorKind as GAuthErrorKind, Token};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Source of Google auth tokens.
+pub trait CredentialProvider: Send + Sync {
+    /// Returns value of authorization header, e.g. "Bearer ya29...".
+    fn token(&self) -> Result<String>;
+}
+
+/// Provides tokens from Google Cloud Platform JSON credentials (provided as String).
+/// Token is refreshed automatically once expired.
+pub struct JsonCredentialProvider {
+    source: TokenSource,
+}
+
+enum TokenSource {
+    Gouth(Token),
+    Delegated(DelegatedServiceAccount),
+}
+
+impl JsonCredentialProvider {
+    /// Creates new provider from JSON credentials.
+    pub fn new(google_credentials: impl AsRef<str>) -> Result<Self> {
+        Ok(JsonCredentialProvider {
+            source: TokenSource::Gouth(token_from_json(google_credentials.as_ref())?),
+        })
+    }
+
+    /// Creates new provider from JSON credentials. If subject (user email) is provided,
+    /// service account impersonates this user (G Suite domain-wide delegation must be
+    /// enabled for the service account). Returns error with code CREDENTIALS_INVALID if
+    /// subject is provided for credentials other than service account.
+    pub fn new_with_subject(
+        google_credentials: impl AsRef<str>,
+        subject: Option<String>,
+    ) -> Result<Self> {
+        match subject {
+            None => JsonCredentialProvider::new(google_credentials),
+            Some(subject) => Ok(JsonCredentialProvider {
+                source: TokenSource::Delegated(DelegatedServiceAccount::new(
+                    google_credentials.as_ref(),
+                    subject,
+                )?),
+            }),
+        }
+    }
+}
+
+impl CredentialProvider for JsonCredentialProvider {
+    fn token(&self) -> Result<String> {
+        match &self.source {
+            TokenSource::Gouth(token) => Ok(token.header_value()?.to_string()),
+            TokenSource::Delegated(account) => account.token(),
+        }
+    }
+}
+
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+/// lifetime of JWT assertion (max allowed by Google)
+const ASSERTION_LIFETIME_SECS: u64 = 60 * 60;
+/// cached token is refreshed this long before it expires
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Service account impersonating subject. Implements JWT bearer flow directly
+/// since gouth does not support sub claim. Token is cached until it expires.
+struct DelegatedServiceAccount {
+    client_email: String,
+    private_key_id: String,
+    key: EncodingKey,
+    token_uri: String,
+    subject: String,
+    cached: Mutex<Option<(String, Instant)>>,
+}
+
+impl DelegatedServiceAccount {
+    fn new(google_credentials: &str, subject: String) -> Result<Self> {
+        validate_credentials(google_credentials)?;
+        let credentials: Value = serde_json::from_str(google_credentials)?;
+        let attribute = |name: &str| {
+            credentials
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        if attribute("type") != "service_account" {
+            return Err(Error::new_with_code(
+                "Subject (domain-wide delegation) requires service_account credentials".to_string(),
+                CREDENTIALS_INVALID.to_string(),
+            ));
+        }
+
+        let key =
+            EncodingKey::from_rsa_pem(attribute("private_key").as_bytes()).map_err(|error| {
+                Error::new_with_code(
+                    format!("Google credentials are invalid: {}", error),
+                    CREDENTIALS_INVALID.to_string(),
+                )
+            })?;
+
+        let token_uri = match attribute("token_uri") {
+            token_uri if token_uri.is_empty() => DEFAULT_TOKEN_URI.to_string(),
+            token_uri => token_uri,
+        };
+
+        Ok(DelegatedServiceAccount {
+            client_email: attribute("client_email"),
+            private_key_id: attribute("private_key_id"),
+            key,
+            token_uri,
+            subject,
+            cached: Mutex::new(None),
+        })
+    }
+
+    fn token(&self) -> Result<String> {
+        let mut cached = self
+            .cached
+            .lock()
+            .map_err(|_| Error::new("Delegated token cache lock poisoned".to_string()))?;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+        let (token, expires_in) = self.fetch_token()?;
+        *cached = Some((token.clone(), Instant::now() + expires_in));
+        Ok(token)
+    }
+
+    /// Returns JWT claims of assertion issued at issued_at (seconds since unix epoch).
+    fn claims(&self, issued_at: u64) -> Value {
+        json!({
+            "iss": self.client_email,
+            "sub": self.subject,
+            "scope": CLOUD_PLATFORM_SCOPE,
+            "aud": self.token_uri,
+            "iat": issued_at,
+            "exp": issued_at + ASSERTION_LIFETIME_SECS,
+        })
+    }
+
+    /// Exchanges signed assertion for access token. Returns header value and token lifetime.
+    fn fetch_token(&self) -> Result<(String, Duration)> {
+        // tolerate client clock being slightly ahead of Google servers
+        let issued_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .saturating_sub(10);
+        let header = Header {
+            typ: Some("JWT".to_string()),
+            alg: Algorithm::RS256,
+            kid: Some(self.private_key_id.clone()),
+            ..Default::default()
+        };
+        let assertion = jsonwebtoken::encode(&header, &self.claims(issued_at), &self.key)
+            .map_err(|error| Error::new(format!("Signing of JWT assertion failed: {}", error)))?;
+
+        let fetch_error = |error: attohttpc::Error| {
+            Error::new(format!("Fetching of delegated token failed: {}", error))
+        };
+        let response = attohttpc::post(&self.token_uri)
+            .form(&[
+                ("grant_type", JWT_BEARER_GRANT_TYPE),
+                ("assertion", assertion.as_str()),
+            ])
+            .map_err(fetch_error)?
+            .send()
+            .map_err(fetch_error)?;
+        if !response.is_success() {
+            return Err(Error::new(format!(
+                "Fetching of delegated token for {} failed with HTTP status {}",
+                self.subject,
+                response.status()
+            )));
+        }
+
+        let body: Value = serde_json::from_str(&response.text().map_err(fetch_error)?)?;
+        let access_token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::new("Token response is missing access_token".to_string()))?;
+        let token_type = body
+            .get("token_type")
+            .and_then(Value::as_str)
+            .unwrap_or("Bearer");
+        let expires_in = body
+            .get("expires_in")
+            .and_then(Value::as_u64)
+            .unwrap_or(ASSERTION_LIFETIME_SECS);
+        Ok((
+            format!("{} {}", token_type, access_token),
+            Duration::from_secs(expires_in),
+        ))
+    }
+}
+
+/// Existing gouth token can be used as provider directly, e.g. token shared (via Arc)
+/// with other Google API clients. Token is refreshed by gouth once expired.
+impl CredentialProvider for Token {
+    fn token(&self) -> Result<String> {
+        Ok(self.header_value()?.to_string())
+    }
+}
+
+impl CredentialProvider for Arc<Token> {
+    fn token(&self) -> Result<String> {
+        self.as_ref().token()
+    }
+}
+
+pub(crate) const CREDENTIALS_INVALID: &str = "CREDENTIALS_INVALID";
+
+/// Checks that JSON credentials can be parsed and contain attributes
+/// required for respective credentials type. Returns error with code
+/// CREDENTIALS_INVALID describing the problem otherwise.
+pub(crate) fn validate_credentials(google_credentials: &str) -> Result<()> {
+    let invalid = |message: String| {
+        Err(Error::new_with_code(
+            message,
+            CREDENTIALS_INVALID.to_string(),
+        ))
+    };
+
+    let credentials: Value = match serde_json::from_str(google_credentials) {
+        Ok(credentials) => credentials,
+        Err(error) => {
+            return invalid(format!(
+                "Google credentials are not valid JSON ({}). Make sure JSON content is passed, not file path.",
+                error
+            ))
+        }
+    };
+
+    let required_attributes: &[&str] = match credentials.get("type").and_then(Value::as_str) {
+        Some("service_account") => &["client_email", "private_key"],
+        Some("authorized_user") => &["client_id", "client_secret", "refresh_token"],
+        Some(other) => {
+            return invalid(format!(
+            "Unsupported Google credentials type '{}', expected service_account or authorized_user",
+            other
+        ))
+        }
+        None => return invalid("Google credentials are missing attribute 'type'".to_string()),
+    };
+
+    let missing: Vec<&str> = required_attributes
+        .iter()
+        .filter(|attribute| {
+            credentials
+                .get(**attribute)
+                .and_then(Value::as_str)
+                .is_none_or(str::is_empty)
+        })
+        .copied()
+        .collect();
+
+    if !missing.is_empty() {
+        return invalid(format!(
+            "Google credentials are missing attribute(s): {}",
+            missing.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates JSON credentials and builds gouth token. Credentials related gouth errors
+/// (JSON parsing, invalid private key) are reported with code CREDENTIALS_INVALID.
+pub(crate) fn token_from_json(google_credentials: &str) -> Result<Token> {
+    validate_credentials(google_credentials)?;
+    Builder::new()
+        .json(google_credentials)
+        .build()
+        .map_err(|error| {
+            let credentials_error = matches!(
+                error.kind(),
+                GAuthErrorKind::CredentialsJson(_) | GAuthErrorKind::Jwt(_)
+            );
+            if credentials_error {
+                Error::new_with_code(
+                    format!("Google credentials are invalid: {}", error),
+                    CREDENTIALS_INVALID.to_string(),
+                )
+            } else {
+                error.into()
+            }
+        })
+}
+
+/// Provides tokens from Application Default Credentials, i.e. from file referenced
+/// by GOOGLE_APPLICATION_CREDENTIALS environment variable, gcloud configuration or
+/// GCE metadata server. Token is refreshed automatically once expired.
+pub struct AdcCredentialProvider {
+    token: Token,
+}
+
+impl AdcCredentialProvider {
+    /// Creates new provider from Application Default Credentials.
+    pub fn new() -> Result<Self> {
+        Ok(AdcCredentialProvider {
+            token: Token::new()?,
+        })
+    }
+}
+
+impl CredentialProvider for AdcCredentialProvider {
+    fn token(&self) -> Result<String> {
+        Ok(self.token.header_value()?.to_string())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::common::new_provider_interceptor;
+    #[allow(unused_imports)]
+    use tonic::service::Interceptor;
+
+    #[allow(unused_imports)]
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[allow(dead_code)]
+    struct StaticProvider;
+
+    /// fails first `failures` calls
+    #[allow(dead_code)]
+    struct FailingProvider {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    impl CredentialProvider for FailingProvider {
+        fn token(&self) -> Result<String> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(Error::new("metadata server unavailable".to_string()))
+            } else {
+                Ok("Bearer refreshed".to_string())
+            }
+        }
+    }
+
+    /// blocks like provider refreshing token over HTTP
+    #[allow(dead_code)]
+    struct SlowProvider;
+
+    impl CredentialProvider for SlowProvider {
+        fn token(&self) -> Result<String> {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            Ok("Bearer slow".to_string())
+        }
+    }
+
+    impl CredentialProvider for StaticProvider {
+        fn token(&self) -> Result<String> {
+            Ok("Bearer test-token".to_string())
+        }
+    }
+
+    // cargo test -- --show-output test_validate_credentials
+    #[test]
+    fn test_validate_credentials() {
+        let code = |json: &str| validate_credentials(json).unwrap_err().code.unwrap();
+        assert_eq!(code("/tmp/cred.json"), "CREDENTIALS_INVALID");
+        assert_eq!(code(r#"{"private_key": "x"}"#), "CREDENTIALS_INVALID");
+        assert_eq!(
+            code(r#"{"type": "external_account"}"#),
+            "CREDENTIALS_INVALID"
+        );
+
+        let error = validate_credentials(r#"{"type": "service_account", "client_email": "a@b"}"#)
+            .unwrap_err();
+        assert!(error.message.contains("private_key"));
+
+        assert!(validate_credentials(
+            r#"{"type": "service_account", "client_email": "a@b", "private_key": "key"}"#
+        )
+        .is_ok());
+    }
+
+    // cargo test -- --show-output test_subject_requires_service_account
+    #[test]
+    fn test_subject_requires_service_account() {
+        let user = r#"{"type": "authorized_user", "client_id": "id", "client_secret": "secret", "refresh_token": "token"}"#;
+        let error = JsonCredentialProvider::new_with_subject(user, Some("user@example.com".into()))
+            .err()
+            .unwrap();
+        assert_eq!(error.code.as_deref(), Some("CREDENTIALS_INVALID"));
+
+        let service_account = r#"{"type": "service_account", "client_email": "sa@p.iam.gserviceaccount.com", "private_key": "not a key"}"#;
+        let error = JsonCredentialProvider::new_with_subject(
+            service_account,
+            Some("user@example.com".into()),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.code.as_deref(), Some("CREDENTIALS_INVALID"));
+        assert!(error.message.contains("invalid"));
+    }
+
+    // cargo test -- --show-output test_provider_interceptor
+    #[test]
+    fn test_provider_interceptor() {
+        let mut interceptor = new_provider_interceptor(Arc::new(StaticProvider));
+        let request = interceptor.call(tonic::Request::new(())).unwrap();
+        assert_eq!(
+            request.metadata().get("authorization").unwrap(),
+            "Bearer test-token"
+        );
+    }
+
+    // cargo test -- --show-output test_interceptor_warm_up
+    #[tokio::test]
+    async fn test_interceptor_warm_up() {
+        let provider = Arc::new(FailingProvider {
+            failures: 0,
+            calls: AtomicUsize::new(0),
+        });
+        let interceptor = new_provider_interceptor(provider.clone());
+        interceptor.refresh().await.unwrap();
+        interceptor.refresh().await.unwrap();
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+
+        let interceptor = new_provider_interceptor(Arc::new(FailingProvider {
+            failures: usize::MAX,
+            calls: AtomicUsize::new(0),
+        }));
+        let error = interceptor.refresh().await.unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("AUTH_REFRESH"));
+
+        // slow provider does not block (single threaded) executor
+        let interceptor = new_provider_interceptor(Arc::new(SlowProvider));
+        let refresh = interceptor.refresh();
+        tokio::pin!(refresh);
+        tokio::select! {
+            biased;
+            _ = &mut refresh => panic!("provider call finished before timer"),
+            _ = tokio::time::sleep(std::time::Duration::from_millis(20)) => {}
+        }
+        refresh.await.unwrap();
+    }
+
+    // cargo test -- --show-output test_token_refresh_failure
+    #[tokio::test]
+    async fn test_token_refresh_failure() {
+        // transient failures are retried, refreshed token is used by interceptor
+        let provider = Arc::new(FailingProvider {
+            failures: 2,
+            calls: AtomicUsize::new(0),
+        });
+        let mut interceptor = new_provider_interceptor(provider.clone());
+        interceptor.refresh().await.unwrap();
+        let request = interceptor.call(tonic::Request::new(())).unwrap();
+        assert_eq!(
+            request.metadata().get("authorization").unwrap(),
+            "Bearer refreshed"
+        );
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+
+        // persistent failure is reported with AUTH_REFRESH code
+        let mut interceptor = new_provider_interceptor(Arc::new(FailingProvider {
+            failures: usize::MAX,
+            calls: AtomicUsize::new(0),
+        }));
+        let error = interceptor.refresh().await.unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("AUTH_REFRESH"));
+        let status = interceptor.call(tonic::Request::new(())).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        let error: Error = status.into();
+        assert_eq!(error.code.as_deref(), Some("AUTH_REFRESH"));
+
+        // statuses returned by Google are not affected
+        let error: Error = tonic::Status::unauthenticated("invalid token").into();
+        assert_eq!(error.code, None);
+    }
+}
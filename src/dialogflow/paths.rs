@@ -0,0 +1,184 @@
+//! Typed builders of Dialogflow ES resource paths. Components are validated
+//! (must not be empty) and percent-encoded, i.e. characters other than
+//! `A-Z a-z 0-9 - _ . ~` (e.g. `/` or spaces) cannot break the path structure.
+use crate::errors::{Error, Result};
+use std::fmt;
+
+/// Max length of (encoded) session id accepted by Dialogflow.
+pub const SESSION_ID_MAX_LEN: usize = 36;
+
+/// Path of Dialogflow agent, i.e. projects/{project}/locations/{location}/agent
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AgentPath {
+    project: String,
+    location: String,
+}
+
+impl AgentPath {
+    /// Returns (encoded) project id.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// Returns (encoded) location id.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+impl fmt::Display for AgentPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "projects/{}/locations/{}/agent",
+            self.project, self.location
+        )
+    }
+}
+
+/// Path of Dialogflow session, i.e.
+/// projects/{project}/locations/{location}/agent/sessions/{session}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionPath {
+    agent: AgentPath,
+    session: String,
+}
+
+impl SessionPath {
+    /// Returns path of agent this session belongs to.
+    pub fn agent(&self) -> &AgentPath {
+        &self.agent
+    }
+
+    /// Returns (encoded) session id.
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+}
+
+impl fmt::Display for SessionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/sessions/{}", self.agent, self.session)
+    }
+}
+
+/// Path of Dialogflow entity type, i.e.
+/// projects/{project}/locations/{location}/agent/entityTypes/{entity_type}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTypePath {
+    agent: AgentPath,
+    entity_type: String,
+}
+
+impl EntityTypePath {
+    /// Returns path of agent this entity type belongs to.
+    pub fn agent(&self) -> &AgentPath {
+        &self.agent
+    }
+
+    /// Returns (encoded) entity type id.
+    pub fn entity_type(&self) -> &str {
+        &self.entity_type
+    }
+}
+
+impl fmt::Display for EntityTypePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/entityTypes/{}", self.agent, self.entity_type)
+    }
+}
+
+/// Builds agent path. Returns error with code INVALID_PATH if any component is empty.
+pub fn agent_path(project: &str, location: &str) -> Result<AgentPath> {
+    Ok(AgentPath {
+        project: encode_component("project", project)?,
+        location: encode_component("location", location)?,
+    })
+}
+
+/// Builds session path. Returns error with code INVALID_PATH if any component is empty
+/// or encoded session id is longer than SESSION_ID_MAX_LEN.
+pub fn session_path(project: &str, location: &str, session: &str) -> Result<SessionPath> {
+    let session = encode_component("session", session)?;
+    if session.len() > SESSION_ID_MAX_LEN {
+        return Err(Error::new_with_code(
+            format!(
+                "Session id {} is longer than {} characters",
+                session, SESSION_ID_MAX_LEN
+            ),
+            "INVALID_PATH".to_string(),
+        ));
+    }
+    Ok(SessionPath {
+        agent: agent_path(project, location)?,
+        session,
+    })
+}
+
+/// Builds entity type path. Returns error with code INVALID_PATH if any component is empty.
+pub fn entity_type_path(
+    project: &str,
+    location: &str,
+    entity_type: &str,
+) -> Result<EntityTypePath> {
+    Ok(EntityTypePath {
+        agent: agent_path(project, location)?,
+        entity_type: encode_component("entity type", entity_type)?,
+    })
+}
+
+fn encode_component(name: &str, value: &str) -> Result<String> {
+    if value.trim().is_empty() {
+        return Err(Error::new_with_code(
+            format!("Dialogflow path component {} must not be empty", name),
+            "INVALID_PATH".to_string(),
+        ));
+    }
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Ok(encoded)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_session_path
+    #[test]
+    fn test_session_path() {
+        let path = session_path("my-project", "europe-west1", "session-1").unwrap();
+        assert_eq!(
+            path.to_string(),
+            "projects/my-project/locations/europe-west1/agent/sessions/session-1"
+        );
+        assert_eq!(
+            path.agent().to_string(),
+            "projects/my-project/locations/europe-west1/agent"
+        );
+
+        let path = session_path("my-project", "global", "a/b c").unwrap();
+        assert_eq!(path.session(), "a%2Fb%20c");
+
+        let error = session_path("my-project", "", "session-1").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("INVALID_PATH"));
+        let error = session_path("my-project", "global", &"x".repeat(37)).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("INVALID_PATH"));
+    }
+
+    // cargo test -- --show-output test_entity_type_path
+    #[test]
+    fn test_entity_type_path() {
+        let path = entity_type_path("my-project", "global", "fruit").unwrap();
+        assert_eq!(
+            path.to_string(),
+            "projects/my-project/locations/global/agent/entityTypes/fruit"
+        );
+        assert!(entity_type_path(" ", "global", "fruit").is_err());
+    }
+}
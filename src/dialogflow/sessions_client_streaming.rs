@@ -5,7 +5,7 @@
 #![allow(clippy::manual_map)]
 use crate::api::grpc::google::cloud::dialogflow::v2beta1::{
     sessions_client::SessionsClient as GrpcSessionsClient, DetectIntentResponse,
-    StreamingDetectIntentRequest, StreamingDetectIntentResponse,
+    StreamingDetectIntentRequest, StreamingDetectIntentResponse, StreamingRecognitionResult,
 };
 use crate::common::{get_token, new_grpc_channel, new_interceptor, TokenInterceptor};
 use crate::errors::Result;
@@ -37,6 +37,10 @@ pub struct SessionsClient {
     /// where STT results will be sent. Library client is using respective
     /// receiver to get the results.
     result_sender: Option<mpsc::Sender<StreamingDetectIntentResponse>>,
+
+    /// Internal channel sender where intermediate transcripts (recognition_result
+    /// of streaming responses) will be sent (see get_recognition_receiver).
+    recognition_sender: Option<mpsc::Sender<StreamingRecognitionResult>>,
 }
 
 impl SessionsClient {
@@ -73,6 +77,7 @@ impl SessionsClient {
             audio_sender: Some(audio_sender),
             audio_receiver: Some(audio_receiver),
             result_sender: None,
+            recognition_sender: None,
         })
     }
 
@@ -117,6 +122,22 @@ impl SessionsClient {
         result_receiver
     }
 
+    /// Returns receiver of intermediate transcripts (StreamingDetectIntentResponse.recognition_result)
+    /// which arrive before the final intent, e.g. to show live captions while agent processes
+    /// the query. Responses are still sent to receiver returned by get_streaming_result_receiver.
+    /// Used only by streaming_detect_intent. Delivery is best effort: transcripts are dropped
+    /// when receiver is full or was dropped, i.e. captions never stall or end the intent stream.
+    pub fn get_recognition_receiver(
+        &mut self,
+        // buffer size for tokio channel. If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+    ) -> mpsc::Receiver<StreamingRecognitionResult> {
+        let (recognition_sender, recognition_receiver) =
+            mpsc::channel::<StreamingRecognitionResult>(buffer_size.unwrap_or(1000));
+        self.recognition_sender = Some(recognition_sender);
+        recognition_receiver
+    }
+
     /// Convenience function so that client does not have to create full StreamingDetectIntentRequest
     /// and can just pass audio bytes vector instead.
     #[allow(deprecated)]
@@ -197,6 +218,11 @@ impl SessionsClient {
                 streaming_recognize_result?.into_inner();
 
             while let Some(streaming_detect_intent_response) = response_stream.message().await? {
+                if let Some(recognition_result) =
+                    &streaming_detect_intent_response.recognition_result
+                {
+                    self.forward_recognition(recognition_result);
+                }
                 if let Some(result_sender) = &self.result_sender {
                     result_sender.send(streaming_detect_intent_response).await?;
                 }
@@ -205,4 +231,59 @@ impl SessionsClient {
 
         Ok(())
     }
+
+    /// Sends intermediate transcript to recognition receiver (if any) without waiting,
+    /// see get_recognition_receiver.
+    fn forward_recognition(&self, recognition_result: &StreamingRecognitionResult) {
+        if let Some(recognition_sender) = &self.recognition_sender {
+            let _ = recognition_sender.try_send(recognition_result.clone());
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn lazy_client() -> SessionsClient {
+        let channel = Channel::from_static("http://127.0.0.1:1").connect_lazy();
+        SessionsClient {
+            sessions_client: GrpcSessionsClient::with_interceptor(
+                channel,
+                new_interceptor(std::sync::Arc::new("Bearer test".to_string())),
+            ),
+            audio_sender: None,
+            audio_receiver: None,
+            result_sender: None,
+            recognition_sender: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    fn recognition(transcript: &str) -> StreamingRecognitionResult {
+        StreamingRecognitionResult {
+            transcript: transcript.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_recognition_receiver
+    #[tokio::test]
+    async fn test_recognition_receiver() {
+        let mut client = lazy_client();
+        // no receiver requested, transcript is ignored
+        client.forward_recognition(&recognition("ignored"));
+
+        let mut receiver = client.get_recognition_receiver(Some(1));
+        client.forward_recognition(&recognition("hello"));
+        // full receiver does not block, transcript is dropped
+        client.forward_recognition(&recognition("dropped"));
+        assert_eq!(receiver.recv().await.unwrap().transcript, "hello");
+        assert!(receiver.try_recv().is_err());
+
+        // dropped receiver does not fail
+        drop(receiver);
+        client.forward_recognition(&recognition("closed"));
+    }
 }
@@ -1,28 +1,141 @@
 //! Package error contains struct Error used to wrap library errors.
+#[cfg(feature = "dialogflow")]
 use crate::api::grpc::google::cloud::dialogflow::v2beta1::{
     StreamingDetectIntentRequest, StreamingDetectIntentResponse,
+    StreamingRecognitionResult as DialogflowRecognitionResult,
 };
+#[cfg(feature = "speech-to-text")]
 use crate::api::grpc::google::cloud::speechtotext::v1::{
     StreamingRecognizeRequest, StreamingRecognizeResponse,
 };
+#[cfg(feature = "speech-to-text")]
 use crate::api::grpc::google::cloud::speechtotext::v1p1beta1::StreamingRecognizeRequest as StreamingRecognizeRequestBeta;
+#[cfg(feature = "speech-to-text")]
 use crate::api::grpc::google::cloud::speechtotext::v1p1beta1::StreamingRecognizeResponse as StreamingRecognizeResponseBeta;
+#[cfg(feature = "speech-to-text")]
 use crate::api::grpc::google::cloud::speechtotext::v2::{
     StreamingRecognizeRequest as StreamingRecognizeRequestv2,
     StreamingRecognizeResponse as StreamingRecognizeResponsev2,
 };
+use crate::api::grpc::google::rpc::Status as RpcStatus;
+use crate::common::convert::to_std_duration;
+use crate::common::AUTH_REFRESH_FAILED_KEY;
+#[cfg(feature = "speech-to-text")]
+use crate::speechtotext::compare::ResultPair;
+#[cfg(feature = "speech-to-text")]
+use crate::speechtotext::recognizer_v2::ContextualResponse;
 use gouth::Error as GAuthError;
 use prost::DecodeError as ProstDecodeError;
+use prost::Message;
 use std::result;
+use std::time::Duration;
+#[cfg(any(feature = "speech-to-text", feature = "dialogflow"))]
 use tokio::sync::mpsc::error::SendError;
-use tonic::metadata::errors::InvalidMetadataValue;
+use tonic::metadata::errors::{InvalidMetadataKey, InvalidMetadataValue};
 use tonic::transport::Error as TTError;
+use tonic::Code as TCode;
 use tonic::Status as TStatus;
 
+/// Library error. Marked non_exhaustive since it carries private state (kind, original
+/// GRPC status), construct it with Error::new or Error::new_with_code.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Error {
     pub message: String,
     pub code: Option<String>,
+    /// Delay requested by Google before the call is retried (e.g. when rate-limited
+    /// with RESOURCE_EXHAUSTED). Populated from google.rpc.RetryInfo status details.
+    pub retry_after: Option<Duration>,
+    /// classification of the error, see kind
+    kind: ErrorKind,
+    /// original GRPC status (if error was converted from status), see as_tonic_status
+    status: Option<Box<TStatus>>,
+}
+
+/// Typed classification of Error, derived from error source (GRPC status,
+/// IO error, ...) or from library specific error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// authentication/authorization failure (invalid credentials, failed token refresh,
+    /// UNAUTHENTICATED or PERMISSION_DENIED status)
+    Auth,
+    /// connection failure (GRPC transport error, UNAVAILABLE status)
+    Transport,
+    /// call was cancelled or aborted
+    Cancelled,
+    /// quota or rate limit exceeded (RESOURCE_EXHAUSTED status)
+    ResourceExhausted,
+    /// invalid request, configuration or audio
+    InvalidArgument,
+    /// deadline exceeded
+    Timeout,
+    /// local IO error (e.g. when reading audio file)
+    Io,
+    /// response (or JSON) could not be decoded
+    Decode,
+    /// any other error
+    Other,
+}
+
+impl From<TCode> for ErrorKind {
+    fn from(code: TCode) -> Self {
+        match code {
+            TCode::Unauthenticated | TCode::PermissionDenied => ErrorKind::Auth,
+            TCode::Unavailable => ErrorKind::Transport,
+            TCode::Cancelled | TCode::Aborted => ErrorKind::Cancelled,
+            TCode::ResourceExhausted => ErrorKind::ResourceExhausted,
+            TCode::InvalidArgument | TCode::FailedPrecondition | TCode::OutOfRange => {
+                ErrorKind::InvalidArgument
+            }
+            TCode::DeadlineExceeded => ErrorKind::Timeout,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Derives kind from library error code (e.g. AUTH_REFRESH) or numeric
+    /// google.rpc.Code (used by errors of long running operations).
+    fn from_code(code: &str) -> Self {
+        match library_code_kind(code) {
+            Some(kind) => kind,
+            None => match code.parse::<i32>() {
+                Ok(rpc_code) => ErrorKind::from(TCode::from_i32(rpc_code)),
+                Err(_) => ErrorKind::Other,
+            },
+        }
+    }
+}
+
+/// Returns kind of error code defined by this library, None for other codes.
+/// Every code passed to Error::new_with_code must be listed here (see test_library_error_codes).
+fn library_code_kind(code: &str) -> Option<ErrorKind> {
+    let kind = match code {
+        "AUTH_REFRESH" | "CREDENTIALS_INVALID" | "CREDENTIALS_ENV" | "CLOCK_SKEW" => {
+            ErrorKind::Auth
+        }
+        "AUDIO_TOO_LONG"
+        | "AUDIO_SOURCE_CONFLICT"
+        | "FRAME_MISALIGNED"
+        | "CHANNEL_COUNT_CONFLICT"
+        | "STREAMING_CONFIG_SENT"
+        | "STREAMING_CONFIG_MISSING"
+        | "REPLAY_CONFIG_MISSING"
+        | "FALLBACK_UNSUPPORTED"
+        | "INVALID_OGG_OPUS"
+        | "NO_AUDIO_SENT"
+        | "AUDIO_SINK_CLOSED"
+        | "INVALID_PATH"
+        | "UNSUPPORTED_FORMAT"
+        | "WORD_TIMINGS_MISSING"
+        | "OPERATIONS_CLIENT_MISSING" => ErrorKind::InvalidArgument,
+        "STREAM_LIMIT_EXCEEDED" => ErrorKind::ResourceExhausted,
+        // failures of individual cancellations are listed in the message
+        "NO_SPEECH" | "CANCEL_PARTIALLY_FAILED" => ErrorKind::Other,
+        "MP3_DECODE" => ErrorKind::Decode,
+        _ => return None,
+    };
+    Some(kind)
 }
 
 impl Error {
@@ -30,14 +143,60 @@ impl Error {
         Error {
             message,
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
     pub fn new_with_code(message: String, code: String) -> Self {
         Error {
             message,
+            kind: ErrorKind::from_code(&code),
             code: Some(code),
+            retry_after: None,
+            status: None,
         }
     }
+
+    /// Returns typed classification of the error. Allows exhaustive matching
+    /// instead of matching string codes.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns original GRPC status if error was converted from tonic::Status,
+    /// e.g. to inspect status details() or metadata() not reflected in Error.
+    pub fn as_tonic_status(&self) -> Option<&TStatus> {
+        self.status.as_deref()
+    }
+
+    /// Returns request fields rejected by Google as (field path, description) pairs, e.g.
+    /// ("config.language_codes", "Invalid language code"). Decoded from google.rpc.BadRequest
+    /// status details, empty if error was not converted from status carrying them.
+    pub fn field_violations(&self) -> Vec<(String, String)> {
+        self.status
+            .as_deref()
+            .map(|status| status_details::<BadRequest>(status, BAD_REQUEST_TYPE_URL))
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|bad_request| bad_request.field_violations)
+            .map(|violation| (violation.field, violation.description))
+            .collect()
+    }
+
+    /// Returns quota violations as (subject, description) pairs, e.g. ("project:123",
+    /// "Limit exceeded"). Decoded from google.rpc.QuotaFailure status details,
+    /// empty if error was not converted from status carrying them.
+    pub fn quota_violations(&self) -> Vec<(String, String)> {
+        self.status
+            .as_deref()
+            .map(|status| status_details::<QuotaFailure>(status, QUOTA_FAILURE_TYPE_URL))
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|quota_failure| quota_failure.violations)
+            .map(|violation| (violation.subject, violation.description))
+            .collect()
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -47,6 +206,9 @@ impl From<serde_json::error::Error> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Decode,
         }
     }
 }
@@ -56,6 +218,9 @@ impl From<std::io::Error> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Io,
         }
     }
 }
@@ -65,6 +230,9 @@ impl From<TTError> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Transport,
         }
     }
 }
@@ -74,6 +242,9 @@ impl From<GAuthError> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Auth,
         }
     }
 }
@@ -82,79 +253,263 @@ impl From<TStatus> for Error {
     fn from(error: TStatus) -> Error {
         Error {
             message: format!("{}", error),
-            code: None,
+            // failed token refresh is reported with dedicated code so that callers
+            // can distinguish auth problems from errors returned by Google
+            code: if error.metadata().contains_key(AUTH_REFRESH_FAILED_KEY) {
+                Some("AUTH_REFRESH".to_string())
+            } else if is_no_audio_status(&error) {
+                Some("NO_AUDIO_SENT".to_string())
+            } else {
+                None
+            },
+            retry_after: retry_info_delay(&error),
+            kind: if error.metadata().contains_key(AUTH_REFRESH_FAILED_KEY) {
+                ErrorKind::Auth
+            } else {
+                ErrorKind::from(error.code())
+            },
+            status: Some(Box::new(error)),
         }
     }
 }
 
+const RETRY_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+/// google.rpc.RetryInfo (see res/proto/google/rpc/error_details.proto). Defined here
+/// manually since error_details.proto is not compiled into GRPC stubs.
+#[derive(Clone, PartialEq, prost::Message)]
+struct RetryInfo {
+    /// Clients should wait at least this long between retrying the same request.
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+/// Audio fields of recognize requests (StreamingRecognizeRequest.audio,
+/// RecognizeRequest.content) as reported in google.rpc.BadRequest field violations.
+const NO_AUDIO_FIELDS: &[&str] = &["audio", "content"];
+
+/// Returns true if status is Google rejection of stream (or request) without audio, i.e.
+/// INVALID_ARGUMENT carrying BadRequest field violation of audio field. Status message
+/// is not inspected (it is free text and may be localized), rejections without structured
+/// details are not mapped. Client side variant is detected by Recognizer before GRPC call
+/// is opened (see receive_queued_until_audio).
+fn is_no_audio_status(status: &TStatus) -> bool {
+    status.code() == TCode::InvalidArgument
+        && status_details::<BadRequest>(status, BAD_REQUEST_TYPE_URL)
+            .iter()
+            .flat_map(|bad_request| bad_request.field_violations.iter())
+            .any(|violation| {
+                NO_AUDIO_FIELDS.iter().any(|field| {
+                    violation.field == *field || violation.field.ends_with(&format!(".{}", field))
+                })
+            })
+}
+
+/// Extracts retry delay from google.rpc.RetryInfo contained in status details (if any).
+fn retry_info_delay(status: &TStatus) -> Option<Duration> {
+    let rpc_status = RpcStatus::decode(status.details()).ok()?;
+    rpc_status
+        .details
+        .iter()
+        .filter(|detail| detail.type_url == RETRY_INFO_TYPE_URL)
+        .find_map(|detail| RetryInfo::decode(detail.value.as_slice()).ok())
+        .and_then(|retry_info| retry_info.retry_delay)
+        .map(|retry_delay| to_std_duration(&retry_delay))
+}
+
+const BAD_REQUEST_TYPE_URL: &str = "type.googleapis.com/google.rpc.BadRequest";
+const QUOTA_FAILURE_TYPE_URL: &str = "type.googleapis.com/google.rpc.QuotaFailure";
+
+/// google.rpc.BadRequest (see res/proto/google/rpc/error_details.proto).
+#[derive(Clone, PartialEq, prost::Message)]
+struct BadRequest {
+    /// Describes all violations in a client request.
+    #[prost(message, repeated, tag = "1")]
+    field_violations: Vec<FieldViolation>,
+}
+
+/// google.rpc.BadRequest.FieldViolation
+#[derive(Clone, PartialEq, prost::Message)]
+struct FieldViolation {
+    /// A path leading to a field in the request body.
+    #[prost(string, tag = "1")]
+    field: String,
+    /// A description of why the request element is bad.
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+/// google.rpc.QuotaFailure (see res/proto/google/rpc/error_details.proto).
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuotaFailure {
+    /// Describes all quota violations.
+    #[prost(message, repeated, tag = "1")]
+    violations: Vec<QuotaViolation>,
+}
+
+/// google.rpc.QuotaFailure.Violation
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuotaViolation {
+    /// The subject on which the quota check failed.
+    #[prost(string, tag = "1")]
+    subject: String,
+    /// A description of how the quota check failed.
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+/// Decodes all status details of given type (malformed details are skipped).
+fn status_details<T: Message + Default>(status: &TStatus, type_url: &str) -> Vec<T> {
+    match RpcStatus::decode(status.details()) {
+        Ok(rpc_status) => rpc_status
+            .details
+            .iter()
+            .filter(|detail| detail.type_url == type_url)
+            .filter_map(|detail| T::decode(detail.value.as_slice()).ok())
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeRequest>> for Error {
     fn from(error: SendError<StreamingRecognizeRequest>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeRequestBeta>> for Error {
     fn from(error: SendError<StreamingRecognizeRequestBeta>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeRequestv2>> for Error {
     fn from(error: SendError<StreamingRecognizeRequestv2>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeResponse>> for Error {
     fn from(error: SendError<StreamingRecognizeResponse>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeResponseBeta>> for Error {
     fn from(error: SendError<StreamingRecognizeResponseBeta>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
 impl From<SendError<StreamingRecognizeResponsev2>> for Error {
     fn from(error: SendError<StreamingRecognizeResponsev2>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "speech-to-text")]
+impl<C, T> From<SendError<ContextualResponse<C, T>>> for Error {
+    fn from(error: SendError<ContextualResponse<C, T>>) -> Error {
+        Error {
+            message: format!("{}", error),
+            code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "speech-to-text")]
+impl From<SendError<ResultPair>> for Error {
+    fn from(error: SendError<ResultPair>) -> Error {
+        Error {
+            message: format!("{}", error),
+            code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "dialogflow")]
 impl From<SendError<StreamingDetectIntentRequest>> for Error {
     fn from(error: SendError<StreamingDetectIntentRequest>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
 
+#[cfg(feature = "dialogflow")]
 impl From<SendError<StreamingDetectIntentResponse>> for Error {
     fn from(error: SendError<StreamingDetectIntentResponse>) -> Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "dialogflow")]
+impl From<SendError<DialogflowRecognitionResult>> for Error {
+    fn from(error: SendError<DialogflowRecognitionResult>) -> Error {
+        Error {
+            message: format!("{}", error),
+            code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Other,
         }
     }
 }
@@ -164,6 +519,9 @@ impl From<ProstDecodeError> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::Decode,
         }
     }
 }
@@ -173,6 +531,284 @@ impl From<InvalidMetadataValue> for Error {
         Error {
             message: format!("{}", error),
             code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::InvalidArgument,
+        }
+    }
+}
+
+impl From<InvalidMetadataKey> for Error {
+    fn from(error: InvalidMetadataKey) -> Error {
+        Error {
+            message: format!("{}", error),
+            code: None,
+            retry_after: None,
+            status: None,
+            kind: ErrorKind::InvalidArgument,
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_retry_info_from_status
+    #[test]
+    fn test_retry_info_from_status() {
+        let retry_info = RetryInfo {
+            retry_delay: Some(prost_types::Duration {
+                seconds: 2,
+                nanos: 500_000_000,
+            }),
+        };
+        let rpc_status = RpcStatus {
+            code: tonic::Code::ResourceExhausted as i32,
+            message: "quota exceeded".to_string(),
+            details: vec![prost_types::Any {
+                type_url: RETRY_INFO_TYPE_URL.to_string(),
+                value: retry_info.encode_to_vec(),
+            }],
+        };
+        let status = TStatus::with_details(
+            tonic::Code::ResourceExhausted,
+            "quota exceeded",
+            rpc_status.encode_to_vec().into(),
+        );
+        let error: Error = status.into();
+        assert_eq!(error.retry_after, Some(Duration::from_millis(2500)));
+
+        let error: Error = TStatus::unavailable("unavailable").into();
+        assert_eq!(error.retry_after, None);
+    }
+
+    // cargo test -- --show-output test_error_details_from_status
+    #[test]
+    fn test_error_details_from_status() {
+        let bad_request = BadRequest {
+            field_violations: vec![FieldViolation {
+                field: "config.language_codes".to_string(),
+                description: "Invalid language code xx-XX".to_string(),
+            }],
+        };
+        let quota_failure = QuotaFailure {
+            violations: vec![QuotaViolation {
+                subject: "project:123".to_string(),
+                description: "Requests per minute exceeded".to_string(),
+            }],
+        };
+        let rpc_status = RpcStatus {
+            code: tonic::Code::InvalidArgument as i32,
+            message: "invalid config".to_string(),
+            details: vec![
+                prost_types::Any {
+                    type_url: BAD_REQUEST_TYPE_URL.to_string(),
+                    value: bad_request.encode_to_vec(),
+                },
+                prost_types::Any {
+                    type_url: QUOTA_FAILURE_TYPE_URL.to_string(),
+                    value: quota_failure.encode_to_vec(),
+                },
+                // malformed detail is ignored
+                prost_types::Any {
+                    type_url: BAD_REQUEST_TYPE_URL.to_string(),
+                    value: vec![0xff, 0xff],
+                },
+            ],
+        };
+        let status = TStatus::with_details(
+            tonic::Code::InvalidArgument,
+            "invalid config",
+            rpc_status.encode_to_vec().into(),
+        );
+        let error: Error = status.into();
+        assert_eq!(
+            error.field_violations(),
+            vec![(
+                "config.language_codes".to_string(),
+                "Invalid language code xx-XX".to_string()
+            )]
+        );
+        assert_eq!(
+            error.quota_violations(),
+            vec![(
+                "project:123".to_string(),
+                "Requests per minute exceeded".to_string()
+            )]
+        );
+
+        let error: Error = TStatus::invalid_argument("invalid config").into();
+        assert!(error.field_violations().is_empty());
+        assert!(error.quota_violations().is_empty());
+    }
+
+    // cargo test -- --show-output test_as_tonic_status
+    #[test]
+    fn test_as_tonic_status() {
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        metadata.insert("x-debug", "trace-1".parse().unwrap());
+        let status = TStatus::with_details_and_metadata(
+            tonic::Code::InvalidArgument,
+            "bad config",
+            prost::bytes::Bytes::from_static(b"details"),
+            metadata,
+        );
+        let error = Error::from(status);
+        let status = error.as_tonic_status().unwrap();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.details(), b"details");
+        assert_eq!(status.metadata().get("x-debug").unwrap(), "trace-1");
+
+        assert!(Error::new("other".to_string()).as_tonic_status().is_none());
+    }
+
+    // cargo test -- --show-output test_no_audio_status
+    #[test]
+    fn test_no_audio_status() {
+        let status = |code: tonic::Code, field: &str| {
+            let bad_request = BadRequest {
+                field_violations: vec![FieldViolation {
+                    field: field.to_string(),
+                    description: "must not be empty".to_string(),
+                }],
+            };
+            let rpc_status = RpcStatus {
+                code: code as i32,
+                message: "invalid request".to_string(),
+                details: vec![prost_types::Any {
+                    type_url: BAD_REQUEST_TYPE_URL.to_string(),
+                    value: bad_request.encode_to_vec(),
+                }],
+            };
+            TStatus::with_details(code, "invalid request", rpc_status.encode_to_vec().into())
+        };
+        let error = Error::from(status(tonic::Code::InvalidArgument, "audio"));
+        assert_eq!(error.code.as_deref(), Some("NO_AUDIO_SENT"));
+        assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+        let error = Error::from(status(tonic::Code::InvalidArgument, "request.content"));
+        assert_eq!(error.code.as_deref(), Some("NO_AUDIO_SENT"));
+
+        let error = Error::from(status(tonic::Code::InvalidArgument, "config.model"));
+        assert!(error.code.is_none());
+        let error = Error::from(status(tonic::Code::Internal, "audio"));
+        assert!(error.code.is_none());
+        // message is not inspected
+        let error = Error::from(TStatus::invalid_argument("Stream contains no audio"));
+        assert!(error.code.is_none());
+    }
+
+    // cargo test -- --show-output test_error_kind
+    #[test]
+    fn test_error_kind() {
+        let kind = |status: TStatus| Error::from(status).kind();
+        assert_eq!(kind(TStatus::unauthenticated("")), ErrorKind::Auth);
+        assert_eq!(kind(TStatus::permission_denied("")), ErrorKind::Auth);
+        assert_eq!(kind(TStatus::unavailable("")), ErrorKind::Transport);
+        assert_eq!(kind(TStatus::cancelled("")), ErrorKind::Cancelled);
+        assert_eq!(
+            kind(TStatus::resource_exhausted("")),
+            ErrorKind::ResourceExhausted
+        );
+        assert_eq!(
+            kind(TStatus::invalid_argument("")),
+            ErrorKind::InvalidArgument
+        );
+        assert_eq!(kind(TStatus::deadline_exceeded("")), ErrorKind::Timeout);
+        assert_eq!(kind(TStatus::internal("")), ErrorKind::Other);
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+        assert_eq!(Error::from(io_error).kind(), ErrorKind::Io);
+        let decode_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert_eq!(Error::from(decode_error).kind(), ErrorKind::Decode);
+        let decode_error = RetryInfo::decode(&[0xff][..]).unwrap_err();
+        assert_eq!(Error::from(decode_error).kind(), ErrorKind::Decode);
+        let metadata_error = "bad\nvalue"
+            .parse::<tonic::metadata::MetadataValue<tonic::metadata::Ascii>>()
+            .unwrap_err();
+        assert_eq!(
+            Error::from(metadata_error).kind(),
+            ErrorKind::InvalidArgument
+        );
+
+        let code_kind = |code: &str| Error::new_with_code(String::new(), code.to_string()).kind();
+        assert_eq!(code_kind("AUTH_REFRESH"), ErrorKind::Auth);
+        assert_eq!(code_kind("AUDIO_TOO_LONG"), ErrorKind::InvalidArgument);
+        // numeric google.rpc.Code of failed long running operation
+        assert_eq!(code_kind("1"), ErrorKind::Cancelled);
+        assert_eq!(code_kind("SOMETHING_ELSE"), ErrorKind::Other);
+        assert_eq!(Error::new(String::new()).kind(), ErrorKind::Other);
+    }
+
+    /// Collects string literal codes passed to Error::new_with_code in given source code.
+    #[allow(dead_code)]
+    fn error_codes_in_source(source: &str, codes: &mut Vec<String>) {
+        for call in source.split("new_with_code(").skip(1) {
+            // arguments end at closing parenthesis of the call (string literals skipped)
+            let mut depth = 1;
+            let mut in_literal = false;
+            let mut previous = ' ';
+            let mut end = call.len();
+            for (index, c) in call.char_indices() {
+                match c {
+                    '"' if previous != '\\' => in_literal = !in_literal,
+                    '(' if !in_literal => depth += 1,
+                    ')' if !in_literal => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    end = index;
+                    break;
+                }
+                previous = c;
+            }
+            // code is the last argument, codes passed as variables are skipped
+            let code = call[..end]
+                .split('"')
+                .skip(1)
+                .step_by(2)
+                .filter(|literal| {
+                    !literal.is_empty()
+                        && literal
+                            .chars()
+                            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+                })
+                .last();
+            if let Some(code) = code {
+                codes.push(code.to_string());
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    fn collect_error_codes(dir: &std::path::Path, codes: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.ends_with("grpc_stubs") {
+                continue;
+            }
+            if path.is_dir() {
+                collect_error_codes(&path, codes);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                error_codes_in_source(&std::fs::read_to_string(&path).unwrap(), codes);
+            }
+        }
+    }
+
+    // cargo test -- --show-output test_library_error_codes
+    #[test]
+    fn test_library_error_codes() {
+        let mut codes = vec![crate::credentials::CREDENTIALS_INVALID.to_string()];
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        collect_error_codes(&src, &mut codes);
+        assert!(codes.iter().any(|code| code == "WORD_TIMINGS_MISSING"));
+        for code in codes {
+            assert!(
+                library_code_kind(&code).is_some(),
+                "error code {} is not mapped in library_code_kind",
+                code
+            );
         }
+        assert_eq!(library_code_kind("SOMETHING_ELSE"), None);
     }
 }
@@ -743,6 +743,384 @@ pub mod contexts_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod contexts_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with ContextsServer.
+    #[async_trait]
+    pub trait Contexts: Send + Sync + 'static {
+        /// Returns the list of all contexts in the specified session.
+        async fn list_contexts(
+            &self,
+            request: tonic::Request<super::ListContextsRequest>,
+        ) -> Result<tonic::Response<super::ListContextsResponse>, tonic::Status>;
+        /// Retrieves the specified context.
+        async fn get_context(
+            &self,
+            request: tonic::Request<super::GetContextRequest>,
+        ) -> Result<tonic::Response<super::Context>, tonic::Status>;
+        /// Creates a context.
+        ///
+        /// If the specified context already exists, overrides the context.
+        async fn create_context(
+            &self,
+            request: tonic::Request<super::CreateContextRequest>,
+        ) -> Result<tonic::Response<super::Context>, tonic::Status>;
+        /// Updates the specified context.
+        async fn update_context(
+            &self,
+            request: tonic::Request<super::UpdateContextRequest>,
+        ) -> Result<tonic::Response<super::Context>, tonic::Status>;
+        /// Deletes the specified context.
+        async fn delete_context(
+            &self,
+            request: tonic::Request<super::DeleteContextRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        /// Deletes all active contexts in the specified session.
+        async fn delete_all_contexts(
+            &self,
+            request: tonic::Request<super::DeleteAllContextsRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+    }
+    /// Service for managing [Contexts][google.cloud.dialogflow.v2.Context].
+    #[derive(Debug)]
+    pub struct ContextsServer<T: Contexts> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Contexts> ContextsServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ContextsServer<T>
+    where
+        T: Contexts,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.dialogflow.v2.Contexts/ListContexts" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListContextsSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::ListContextsRequest>
+                    for ListContextsSvc<T> {
+                        type Response = super::ListContextsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListContextsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_contexts(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListContextsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Contexts/GetContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetContextSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::GetContextRequest>
+                    for GetContextSvc<T> {
+                        type Response = super::Context;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetContextRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_context(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetContextSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Contexts/CreateContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateContextSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::CreateContextRequest>
+                    for CreateContextSvc<T> {
+                        type Response = super::Context;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateContextRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).create_context(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateContextSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Contexts/UpdateContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateContextSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::UpdateContextRequest>
+                    for UpdateContextSvc<T> {
+                        type Response = super::Context;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateContextRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_context(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateContextSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Contexts/DeleteContext" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteContextSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::DeleteContextRequest>
+                    for DeleteContextSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteContextRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_context(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteContextSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Contexts/DeleteAllContexts" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteAllContextsSvc<T: Contexts>(pub Arc<T>);
+                    impl<
+                        T: Contexts,
+                    > tonic::server::UnaryService<super::DeleteAllContextsRequest>
+                    for DeleteAllContextsSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteAllContextsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_all_contexts(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteAllContextsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Contexts> Clone for ContextsServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Contexts> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Contexts> tonic::server::NamedService for ContextsServer<T> {
+        const NAME: &'static str = "google.cloud.dialogflow.v2.Contexts";
+    }
+}
 /// An intent categorizes an end-user's intention for one conversation turn. For
 /// each agent, you define many intents, where your combined intents can handle a
 /// complete conversation. When an end-user writes or says something, referred to
@@ -2043,6 +2421,435 @@ pub mod intents_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod intents_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with IntentsServer.
+    #[async_trait]
+    pub trait Intents: Send + Sync + 'static {
+        /// Returns the list of all intents in the specified agent.
+        async fn list_intents(
+            &self,
+            request: tonic::Request<super::ListIntentsRequest>,
+        ) -> Result<tonic::Response<super::ListIntentsResponse>, tonic::Status>;
+        /// Retrieves the specified intent.
+        async fn get_intent(
+            &self,
+            request: tonic::Request<super::GetIntentRequest>,
+        ) -> Result<tonic::Response<super::Intent>, tonic::Status>;
+        /// Creates an intent in the specified agent.
+        async fn create_intent(
+            &self,
+            request: tonic::Request<super::CreateIntentRequest>,
+        ) -> Result<tonic::Response<super::Intent>, tonic::Status>;
+        /// Updates the specified intent.
+        async fn update_intent(
+            &self,
+            request: tonic::Request<super::UpdateIntentRequest>,
+        ) -> Result<tonic::Response<super::Intent>, tonic::Status>;
+        /// Deletes the specified intent and its direct or indirect followup intents.
+        async fn delete_intent(
+            &self,
+            request: tonic::Request<super::DeleteIntentRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        /// Updates/Creates multiple intents in the specified agent.
+        ///
+        /// Operation <response: [BatchUpdateIntentsResponse][google.cloud.dialogflow.v2.BatchUpdateIntentsResponse]>
+        async fn batch_update_intents(
+            &self,
+            request: tonic::Request<super::BatchUpdateIntentsRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        /// Deletes intents in the specified agent.
+        ///
+        /// Operation <response: [google.protobuf.Empty][google.protobuf.Empty]>
+        async fn batch_delete_intents(
+            &self,
+            request: tonic::Request<super::BatchDeleteIntentsRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+    }
+    /// Service for managing [Intents][google.cloud.dialogflow.v2.Intent].
+    #[derive(Debug)]
+    pub struct IntentsServer<T: Intents> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Intents> IntentsServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for IntentsServer<T>
+    where
+        T: Intents,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.dialogflow.v2.Intents/ListIntents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListIntentsSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::ListIntentsRequest>
+                    for ListIntentsSvc<T> {
+                        type Response = super::ListIntentsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListIntentsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_intents(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListIntentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/GetIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetIntentSvc<T: Intents>(pub Arc<T>);
+                    impl<T: Intents> tonic::server::UnaryService<super::GetIntentRequest>
+                    for GetIntentSvc<T> {
+                        type Response = super::Intent;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetIntentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).get_intent(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/CreateIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateIntentSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::CreateIntentRequest>
+                    for CreateIntentSvc<T> {
+                        type Response = super::Intent;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateIntentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).create_intent(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/UpdateIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateIntentSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::UpdateIntentRequest>
+                    for UpdateIntentSvc<T> {
+                        type Response = super::Intent;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateIntentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_intent(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/DeleteIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteIntentSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::DeleteIntentRequest>
+                    for DeleteIntentSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteIntentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_intent(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/BatchUpdateIntents" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchUpdateIntentsSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::BatchUpdateIntentsRequest>
+                    for BatchUpdateIntentsSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchUpdateIntentsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_update_intents(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchUpdateIntentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Intents/BatchDeleteIntents" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchDeleteIntentsSvc<T: Intents>(pub Arc<T>);
+                    impl<
+                        T: Intents,
+                    > tonic::server::UnaryService<super::BatchDeleteIntentsRequest>
+                    for BatchDeleteIntentsSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchDeleteIntentsRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_delete_intents(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchDeleteIntentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Intents> Clone for IntentsServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Intents> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Intents> tonic::server::NamedService for IntentsServer<T> {
+        const NAME: &'static str = "google.cloud.dialogflow.v2.Intents";
+    }
+}
 /// Each intent parameter has a type, called the entity type, which dictates
 /// exactly how data from an end-user expression is extracted.
 ///
@@ -2690,6 +3497,593 @@ pub mod entity_types_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod entity_types_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with EntityTypesServer.
+    #[async_trait]
+    pub trait EntityTypes: Send + Sync + 'static {
+        /// Returns the list of all entity types in the specified agent.
+        async fn list_entity_types(
+            &self,
+            request: tonic::Request<super::ListEntityTypesRequest>,
+        ) -> Result<tonic::Response<super::ListEntityTypesResponse>, tonic::Status>;
+        /// Retrieves the specified entity type.
+        async fn get_entity_type(
+            &self,
+            request: tonic::Request<super::GetEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::EntityType>, tonic::Status>;
+        /// Creates an entity type in the specified agent.
+        async fn create_entity_type(
+            &self,
+            request: tonic::Request<super::CreateEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::EntityType>, tonic::Status>;
+        /// Updates the specified entity type.
+        async fn update_entity_type(
+            &self,
+            request: tonic::Request<super::UpdateEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::EntityType>, tonic::Status>;
+        /// Deletes the specified entity type.
+        async fn delete_entity_type(
+            &self,
+            request: tonic::Request<super::DeleteEntityTypeRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+        /// Updates/Creates multiple entity types in the specified agent.
+        ///
+        /// Operation <response: [BatchUpdateEntityTypesResponse][google.cloud.dialogflow.v2.BatchUpdateEntityTypesResponse]>
+        async fn batch_update_entity_types(
+            &self,
+            request: tonic::Request<super::BatchUpdateEntityTypesRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        /// Deletes entity types in the specified agent.
+        ///
+        /// Operation <response: [google.protobuf.Empty][google.protobuf.Empty]>
+        async fn batch_delete_entity_types(
+            &self,
+            request: tonic::Request<super::BatchDeleteEntityTypesRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        /// Creates multiple new entities in the specified entity type.
+        ///
+        /// Operation <response: [google.protobuf.Empty][google.protobuf.Empty]>
+        async fn batch_create_entities(
+            &self,
+            request: tonic::Request<super::BatchCreateEntitiesRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        /// Updates or creates multiple entities in the specified entity type. This
+        /// method does not affect entities in the entity type that aren't explicitly
+        /// specified in the request.
+        ///
+        ///
+        /// Operation <response: [google.protobuf.Empty][google.protobuf.Empty]>
+        async fn batch_update_entities(
+            &self,
+            request: tonic::Request<super::BatchUpdateEntitiesRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        /// Deletes entities in the specified entity type.
+        ///
+        ///
+        /// Operation <response: [google.protobuf.Empty][google.protobuf.Empty]>
+        async fn batch_delete_entities(
+            &self,
+            request: tonic::Request<super::BatchDeleteEntitiesRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+    }
+    /// Service for managing [EntityTypes][google.cloud.dialogflow.v2.EntityType].
+    #[derive(Debug)]
+    pub struct EntityTypesServer<T: EntityTypes> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: EntityTypes> EntityTypesServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for EntityTypesServer<T>
+    where
+        T: EntityTypes,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.dialogflow.v2.EntityTypes/ListEntityTypes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEntityTypesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::ListEntityTypesRequest>
+                    for ListEntityTypesSvc<T> {
+                        type Response = super::ListEntityTypesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEntityTypesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_entity_types(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListEntityTypesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/GetEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEntityTypeSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::GetEntityTypeRequest>
+                    for GetEntityTypeSvc<T> {
+                        type Response = super::EntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetEntityTypeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).get_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/CreateEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateEntityTypeSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::CreateEntityTypeRequest>
+                    for CreateEntityTypeSvc<T> {
+                        type Response = super::EntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateEntityTypeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).create_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/UpdateEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateEntityTypeSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::UpdateEntityTypeRequest>
+                    for UpdateEntityTypeSvc<T> {
+                        type Response = super::EntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateEntityTypeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/DeleteEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteEntityTypeSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::DeleteEntityTypeRequest>
+                    for DeleteEntityTypeSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteEntityTypeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/BatchUpdateEntityTypes" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchUpdateEntityTypesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::BatchUpdateEntityTypesRequest>
+                    for BatchUpdateEntityTypesSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchUpdateEntityTypesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_update_entity_types(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchUpdateEntityTypesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/BatchDeleteEntityTypes" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchDeleteEntityTypesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::BatchDeleteEntityTypesRequest>
+                    for BatchDeleteEntityTypesSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchDeleteEntityTypesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_delete_entity_types(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchDeleteEntityTypesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/BatchCreateEntities" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchCreateEntitiesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::BatchCreateEntitiesRequest>
+                    for BatchCreateEntitiesSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchCreateEntitiesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_create_entities(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchCreateEntitiesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/BatchUpdateEntities" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchUpdateEntitiesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::BatchUpdateEntitiesRequest>
+                    for BatchUpdateEntitiesSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchUpdateEntitiesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_update_entities(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchUpdateEntitiesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.EntityTypes/BatchDeleteEntities" => {
+                    #[allow(non_camel_case_types)]
+                    struct BatchDeleteEntitiesSvc<T: EntityTypes>(pub Arc<T>);
+                    impl<
+                        T: EntityTypes,
+                    > tonic::server::UnaryService<super::BatchDeleteEntitiesRequest>
+                    for BatchDeleteEntitiesSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BatchDeleteEntitiesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).batch_delete_entities(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = BatchDeleteEntitiesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: EntityTypes> Clone for EntityTypesServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: EntityTypes> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: EntityTypes> tonic::server::NamedService for EntityTypesServer<T> {
+        const NAME: &'static str = "google.cloud.dialogflow.v2.EntityTypes";
+    }
+}
 /// A session represents a conversation between a Dialogflow agent and an
 /// end-user. You can create special entities, called session entities, during a
 /// session. Session entities can extend or replace custom entity types and only
@@ -3037,6 +4431,372 @@ pub mod session_entity_types_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod session_entity_types_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with SessionEntityTypesServer.
+    #[async_trait]
+    pub trait SessionEntityTypes: Send + Sync + 'static {
+        /// Returns the list of all session entity types in the specified session.
+        ///
+        /// This method doesn't work with Google Assistant integration.
+        /// Contact Dialogflow support if you need to use session entities
+        /// with Google Assistant integration.
+        async fn list_session_entity_types(
+            &self,
+            request: tonic::Request<super::ListSessionEntityTypesRequest>,
+        ) -> Result<
+            tonic::Response<super::ListSessionEntityTypesResponse>,
+            tonic::Status,
+        >;
+        /// Retrieves the specified session entity type.
+        ///
+        /// This method doesn't work with Google Assistant integration.
+        /// Contact Dialogflow support if you need to use session entities
+        /// with Google Assistant integration.
+        async fn get_session_entity_type(
+            &self,
+            request: tonic::Request<super::GetSessionEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::SessionEntityType>, tonic::Status>;
+        /// Creates a session entity type.
+        ///
+        /// If the specified session entity type already exists, overrides the session
+        /// entity type.
+        ///
+        /// This method doesn't work with Google Assistant integration.
+        /// Contact Dialogflow support if you need to use session entities
+        /// with Google Assistant integration.
+        async fn create_session_entity_type(
+            &self,
+            request: tonic::Request<super::CreateSessionEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::SessionEntityType>, tonic::Status>;
+        /// Updates the specified session entity type.
+        ///
+        /// This method doesn't work with Google Assistant integration.
+        /// Contact Dialogflow support if you need to use session entities
+        /// with Google Assistant integration.
+        async fn update_session_entity_type(
+            &self,
+            request: tonic::Request<super::UpdateSessionEntityTypeRequest>,
+        ) -> Result<tonic::Response<super::SessionEntityType>, tonic::Status>;
+        /// Deletes the specified session entity type.
+        ///
+        /// This method doesn't work with Google Assistant integration.
+        /// Contact Dialogflow support if you need to use session entities
+        /// with Google Assistant integration.
+        async fn delete_session_entity_type(
+            &self,
+            request: tonic::Request<super::DeleteSessionEntityTypeRequest>,
+        ) -> Result<tonic::Response<()>, tonic::Status>;
+    }
+    /// Service for managing [SessionEntityTypes][google.cloud.dialogflow.v2.SessionEntityType].
+    #[derive(Debug)]
+    pub struct SessionEntityTypesServer<T: SessionEntityTypes> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: SessionEntityTypes> SessionEntityTypesServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SessionEntityTypesServer<T>
+    where
+        T: SessionEntityTypes,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.dialogflow.v2.SessionEntityTypes/ListSessionEntityTypes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListSessionEntityTypesSvc<T: SessionEntityTypes>(pub Arc<T>);
+                    impl<
+                        T: SessionEntityTypes,
+                    > tonic::server::UnaryService<super::ListSessionEntityTypesRequest>
+                    for ListSessionEntityTypesSvc<T> {
+                        type Response = super::ListSessionEntityTypesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListSessionEntityTypesRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).list_session_entity_types(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListSessionEntityTypesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.SessionEntityTypes/GetSessionEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSessionEntityTypeSvc<T: SessionEntityTypes>(pub Arc<T>);
+                    impl<
+                        T: SessionEntityTypes,
+                    > tonic::server::UnaryService<super::GetSessionEntityTypeRequest>
+                    for GetSessionEntityTypeSvc<T> {
+                        type Response = super::SessionEntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetSessionEntityTypeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).get_session_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSessionEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.SessionEntityTypes/CreateSessionEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateSessionEntityTypeSvc<T: SessionEntityTypes>(pub Arc<T>);
+                    impl<
+                        T: SessionEntityTypes,
+                    > tonic::server::UnaryService<super::CreateSessionEntityTypeRequest>
+                    for CreateSessionEntityTypeSvc<T> {
+                        type Response = super::SessionEntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::CreateSessionEntityTypeRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).create_session_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CreateSessionEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.SessionEntityTypes/UpdateSessionEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateSessionEntityTypeSvc<T: SessionEntityTypes>(pub Arc<T>);
+                    impl<
+                        T: SessionEntityTypes,
+                    > tonic::server::UnaryService<super::UpdateSessionEntityTypeRequest>
+                    for UpdateSessionEntityTypeSvc<T> {
+                        type Response = super::SessionEntityType;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::UpdateSessionEntityTypeRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).update_session_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateSessionEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.SessionEntityTypes/DeleteSessionEntityType" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteSessionEntityTypeSvc<T: SessionEntityTypes>(pub Arc<T>);
+                    impl<
+                        T: SessionEntityTypes,
+                    > tonic::server::UnaryService<super::DeleteSessionEntityTypeRequest>
+                    for DeleteSessionEntityTypeSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::DeleteSessionEntityTypeRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).delete_session_entity_type(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteSessionEntityTypeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: SessionEntityTypes> Clone for SessionEntityTypesServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: SessionEntityTypes> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: SessionEntityTypes> tonic::server::NamedService
+    for SessionEntityTypesServer<T> {
+        const NAME: &'static str = "google.cloud.dialogflow.v2.SessionEntityTypes";
+    }
+}
 /// The request to detect user's intent.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DetectIntentRequest {
@@ -3768,3 +5528,229 @@ pub mod sessions_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod sessions_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with SessionsServer.
+    #[async_trait]
+    pub trait Sessions: Send + Sync + 'static {
+        /// Processes a natural language query and returns structured, actionable data
+        /// as a result. This method is not idempotent, because it may cause contexts
+        /// and session entity types to be updated, which in turn might affect
+        /// results of future queries.
+        ///
+        /// Note: Always use agent versions for production traffic.
+        /// See [Versions and
+        /// environments](https://cloud.google.com/dialogflow/es/docs/agents-versions).
+        async fn detect_intent(
+            &self,
+            request: tonic::Request<super::DetectIntentRequest>,
+        ) -> Result<tonic::Response<super::DetectIntentResponse>, tonic::Status>;
+        ///Server streaming response type for the StreamingDetectIntent method.
+        type StreamingDetectIntentStream: futures_core::Stream<
+                Item = Result<super::StreamingDetectIntentResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Processes a natural language query in audio format in a streaming fashion
+        /// and returns structured, actionable data as a result. This method is only
+        /// available via the gRPC API (not REST).
+        ///
+        /// Note: Always use agent versions for production traffic.
+        /// See [Versions and
+        /// environments](https://cloud.google.com/dialogflow/es/docs/agents-versions).
+        async fn streaming_detect_intent(
+            &self,
+            request: tonic::Request<
+                tonic::Streaming<super::StreamingDetectIntentRequest>,
+            >,
+        ) -> Result<tonic::Response<Self::StreamingDetectIntentStream>, tonic::Status>;
+    }
+    /// A service used for session interactions.
+    ///
+    /// For more information, see the [API interactions
+    /// guide](https://cloud.google.com/dialogflow/docs/api-overview).
+    #[derive(Debug)]
+    pub struct SessionsServer<T: Sessions> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Sessions> SessionsServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SessionsServer<T>
+    where
+        T: Sessions,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.dialogflow.v2.Sessions/DetectIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct DetectIntentSvc<T: Sessions>(pub Arc<T>);
+                    impl<
+                        T: Sessions,
+                    > tonic::server::UnaryService<super::DetectIntentRequest>
+                    for DetectIntentSvc<T> {
+                        type Response = super::DetectIntentResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DetectIntentRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).detect_intent(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DetectIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.dialogflow.v2.Sessions/StreamingDetectIntent" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamingDetectIntentSvc<T: Sessions>(pub Arc<T>);
+                    impl<
+                        T: Sessions,
+                    > tonic::server::StreamingService<
+                        super::StreamingDetectIntentRequest,
+                    > for StreamingDetectIntentSvc<T> {
+                        type Response = super::StreamingDetectIntentResponse;
+                        type ResponseStream = T::StreamingDetectIntentStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::StreamingDetectIntentRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).streaming_detect_intent(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamingDetectIntentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Sessions> Clone for SessionsServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Sessions> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Sessions> tonic::server::NamedService for SessionsServer<T> {
+        const NAME: &'static str = "google.cloud.dialogflow.v2.Sessions";
+    }
+}
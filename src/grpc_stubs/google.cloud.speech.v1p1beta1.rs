@@ -1178,3 +1178,261 @@ pub mod speech_client {
         }
     }
 }
+/// Generated server implementations.
+#[cfg(feature = "testing")]
+pub mod speech_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    ///Generated trait containing gRPC methods that should be implemented for use with SpeechServer.
+    #[async_trait]
+    pub trait Speech: Send + Sync + 'static {
+        /// Performs synchronous speech recognition: receive results after all audio
+        /// has been sent and processed.
+        async fn recognize(
+            &self,
+            request: tonic::Request<super::RecognizeRequest>,
+        ) -> Result<tonic::Response<super::RecognizeResponse>, tonic::Status>;
+        /// Performs asynchronous speech recognition: receive results via the
+        /// google.longrunning.Operations interface. Returns either an
+        /// `Operation.error` or an `Operation.response` which contains
+        /// a `LongRunningRecognizeResponse` message.
+        /// For more information on asynchronous speech recognition, see the
+        /// [how-to](https://cloud.google.com/speech-to-text/docs/async-recognize).
+        async fn long_running_recognize(
+            &self,
+            request: tonic::Request<super::LongRunningRecognizeRequest>,
+        ) -> Result<
+            tonic::Response<super::super::super::super::longrunning::Operation>,
+            tonic::Status,
+        >;
+        ///Server streaming response type for the StreamingRecognize method.
+        type StreamingRecognizeStream: futures_core::Stream<
+                Item = Result<super::StreamingRecognizeResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Performs bidirectional streaming speech recognition: receive results while
+        /// sending audio. This method is only available via the gRPC API (not REST).
+        async fn streaming_recognize(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::StreamingRecognizeRequest>>,
+        ) -> Result<tonic::Response<Self::StreamingRecognizeStream>, tonic::Status>;
+    }
+    /// Service that implements Google Cloud Speech API.
+    #[derive(Debug)]
+    pub struct SpeechServer<T: Speech> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: Speech> SpeechServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SpeechServer<T>
+    where
+        T: Speech,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/google.cloud.speech.v1p1beta1.Speech/Recognize" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecognizeSvc<T: Speech>(pub Arc<T>);
+                    impl<T: Speech> tonic::server::UnaryService<super::RecognizeRequest>
+                    for RecognizeSvc<T> {
+                        type Response = super::RecognizeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecognizeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).recognize(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RecognizeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.speech.v1p1beta1.Speech/LongRunningRecognize" => {
+                    #[allow(non_camel_case_types)]
+                    struct LongRunningRecognizeSvc<T: Speech>(pub Arc<T>);
+                    impl<
+                        T: Speech,
+                    > tonic::server::UnaryService<super::LongRunningRecognizeRequest>
+                    for LongRunningRecognizeSvc<T> {
+                        type Response = super::super::super::super::longrunning::Operation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LongRunningRecognizeRequest>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).long_running_recognize(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = LongRunningRecognizeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/google.cloud.speech.v1p1beta1.Speech/StreamingRecognize" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamingRecognizeSvc<T: Speech>(pub Arc<T>);
+                    impl<
+                        T: Speech,
+                    > tonic::server::StreamingService<super::StreamingRecognizeRequest>
+                    for StreamingRecognizeSvc<T> {
+                        type Response = super::StreamingRecognizeResponse;
+                        type ResponseStream = T::StreamingRecognizeStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::StreamingRecognizeRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move {
+                                (*inner).streaming_recognize(request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamingRecognizeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: Speech> Clone for SpeechServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+            }
+        }
+    }
+    impl<T: Speech> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: Speech> tonic::server::NamedService for SpeechServer<T> {
+        const NAME: &'static str = "google.cloud.speech.v1p1beta1.Speech";
+    }
+}
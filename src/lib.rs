@@ -7,8 +7,19 @@
 pub const CERTIFICATES: &[u8] = include_bytes!("../res/certs/roots.pem");
 
 pub mod api;
+// parts of common used only by speech-to-text recognizers
+#[cfg_attr(not(feature = "speech-to-text"), allow(dead_code))]
 pub mod common;
+pub mod credentials;
+#[cfg(feature = "dialogflow")]
 pub mod dialogflow;
 pub mod errors;
+pub mod retry;
+#[cfg(feature = "speech-to-text")]
 pub mod speechtotext;
+#[cfg(feature = "text-to-speech")]
 pub mod texttospeech;
+
+/// in-process mock servers for tests
+#[cfg(feature = "testing")]
+pub mod testing;
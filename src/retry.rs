@@ -0,0 +1,341 @@
+//! Retrying of failed API calls with exponential backoff.
+//! When Google requests specific delay (google.rpc.RetryInfo, typically sent
+//! together with RESOURCE_EXHAUSTED status) this delay is used instead of backoff.
+//! Retries (e.g. stream reconnects) can be observed via ReconnectEvents, see retry_with_events.
+//! Events are emitted only for calls retried by retry_with_events, i.e. by reconnect
+//! loops implemented by the caller. Recognizer streaming methods never retry (reconnect)
+//! by themselves and do not emit reconnect events.
+use crate::errors::{Error, ErrorKind, Result};
+use log::*;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::sleep;
+
+/// Parameters of exponential backoff used when retrying failed calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// max number of calls (including the first one)
+    pub max_attempts: u32,
+
+    /// delay after first failed call
+    pub initial_delay: Duration,
+
+    /// upper bound of computed delay
+    pub max_delay: Duration,
+
+    /// factor by which delay grows after every failed call
+    pub multiplier: f64,
+
+    /// randomization of delay (0.0 - 1.0), delay is reduced by random fraction
+    /// up to jitter to avoid synchronized retries of many clients. 0.0 disables jitter.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    /// 5 attempts, delays starting at 100ms, doubled up to 10s, 20% jitter.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns delay before next retry. Delay requested by Google (Error::retry_after)
+    /// takes precedence, otherwise initial_delay * multiplier^attempt clamped at max_delay
+    /// (reduced by jitter) is used. attempt is zero based, i.e. 0 for delay after first failed call.
+    pub fn delay(&self, error: &Error, attempt: u32) -> Duration {
+        if let Some(retry_after) = error.retry_after {
+            return retry_after;
+        }
+        let backoff = self.initial_delay.as_secs_f64()
+            * self
+                .multiplier
+                .max(1.0)
+                .powi(attempt.min(i32::MAX as u32) as i32);
+        let delay = if backoff.is_finite() && backoff < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(backoff)
+        } else {
+            self.max_delay
+        };
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter > 0.0 {
+            delay.mul_f64(1.0 - jitter * random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+/// Returns pseudo random number in range 0.0 - 1.0 (sufficient for jitter).
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Returns delay before next retry. Delay requested by Google (Error::retry_after)
+/// takes precedence, otherwise exponential backoff (base_delay * 2^attempt) is used.
+/// attempt is zero based, i.e. 0 for delay after first failed call.
+pub fn backoff_delay(error: &Error, attempt: u32, base_delay: Duration) -> Duration {
+    match error.retry_after {
+        Some(retry_after) => retry_after,
+        None => base_delay.saturating_mul(2u32.saturating_pow(attempt)),
+    }
+}
+
+/// Calls provided async function until it succeeds or max_attempts calls are made.
+/// Returns result of the last call. Delay between calls is computed by backoff_delay.
+pub async fn retry<T, F, Fut>(max_attempts: u32, base_delay: Duration, call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let policy = RetryPolicy {
+        max_attempts,
+        initial_delay: base_delay,
+        max_delay: Duration::MAX,
+        multiplier: 2.0,
+        jitter: 0.0,
+    };
+    retry_with_policy(&policy, call).await
+}
+
+/// Single retry (reconnect) of failed call, see ReconnectEvents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectEvent {
+    /// number of the failed attempt (1 for the first call)
+    pub attempt: u32,
+
+    /// message of the error which triggered the retry
+    pub error: String,
+
+    /// kind of the error which triggered the retry
+    pub error_kind: ErrorKind,
+
+    /// delay before the retry
+    pub delay: Duration,
+
+    /// number of retries of all calls observed by the same ReconnectEvents (this one included)
+    pub total_reconnects: u64,
+}
+
+/// Publisher of reconnect events shared by retried calls (see retry_with_events).
+/// Clones share subscribers and reconnect counter. Events are broadcast without waiting,
+/// i.e. slow subscribers never delay retried calls, subscribers lagging more than
+/// 100 events behind lose oldest events.
+#[derive(Debug, Clone)]
+pub struct ReconnectEvents {
+    sender: broadcast::Sender<ReconnectEvent>,
+    total_reconnects: Arc<AtomicU64>,
+}
+
+impl Default for ReconnectEvents {
+    fn default() -> Self {
+        ReconnectEvents {
+            sender: broadcast::channel(100).0,
+            total_reconnects: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl ReconnectEvents {
+    /// Creates publisher without subscribers.
+    pub fn new() -> Self {
+        ReconnectEvents::default()
+    }
+
+    /// Subscribes to reconnect events emitted after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<ReconnectEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Returns number of reconnects so far.
+    pub fn total_reconnects(&self) -> u64 {
+        self.total_reconnects.load(Ordering::Relaxed)
+    }
+
+    fn emit(&self, attempt: u32, error: &Error, delay: Duration) {
+        let total_reconnects = self.total_reconnects.fetch_add(1, Ordering::Relaxed) + 1;
+        // error means there are no subscribers
+        let _ = self.sender.send(ReconnectEvent {
+            attempt,
+            error: error.message.clone(),
+            error_kind: error.kind(),
+            delay,
+            total_reconnects,
+        });
+    }
+}
+
+/// Calls provided async function until it succeeds or policy.max_attempts calls are made.
+/// Returns result of the last call. Delay between calls is computed by RetryPolicy::delay.
+pub async fn retry_with_policy<T, F, Fut>(policy: &RetryPolicy, call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry_observed(policy, None, call).await
+}
+
+/// Same as retry_with_policy, every retry is reported to reconnect events
+/// (e.g. for metrics or alerting on unstable streams).
+pub async fn retry_with_events<T, F, Fut>(
+    policy: &RetryPolicy,
+    events: &ReconnectEvents,
+    call: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry_observed(policy, Some(events), call).await
+}
+
+async fn retry_observed<T, F, Fut>(
+    policy: &RetryPolicy,
+    events: Option<&ReconnectEvents>,
+    mut call: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Ok(result) => return Ok(result),
+            Err(error) if attempt + 1 >= policy.max_attempts => return Err(error),
+            Err(error) => {
+                let delay = policy.delay(&error, attempt);
+                debug!(
+                    "retry: attempt {} failed ({}), retrying in {:?}",
+                    attempt + 1,
+                    error.message,
+                    delay
+                );
+                if let Some(events) = events {
+                    events.emit(attempt + 1, &error, delay);
+                }
+                sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_backoff_delay
+    #[test]
+    fn test_backoff_delay() {
+        let base = Duration::from_millis(100);
+        let mut error = Error::new("unavailable".to_string());
+        assert_eq!(backoff_delay(&error, 0, base), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&error, 3, base), Duration::from_millis(800));
+
+        error.retry_after = Some(Duration::from_secs(7));
+        assert_eq!(backoff_delay(&error, 3, base), Duration::from_secs(7));
+    }
+
+    // cargo test -- --show-output test_retry_policy_delay
+    #[test]
+    fn test_retry_policy_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+            multiplier: 3.0,
+            jitter: 0.0,
+        };
+        let mut error = Error::new("unavailable".to_string());
+        assert_eq!(policy.delay(&error, 0), Duration::from_millis(50));
+        assert_eq!(policy.delay(&error, 2), Duration::from_millis(450));
+        assert_eq!(policy.delay(&error, 3), Duration::from_secs(1));
+        assert_eq!(policy.delay(&error, u32::MAX), Duration::from_secs(1));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy.clone()
+        };
+        let delay = jittered.delay(&error, 3);
+        assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+
+        error.retry_after = Some(Duration::from_secs(7));
+        assert_eq!(policy.delay(&error, 3), Duration::from_secs(7));
+    }
+
+    // cargo test -- --show-output test_retry
+    #[tokio::test]
+    async fn test_retry() {
+        let mut calls = 0;
+        let result = retry(3, Duration::from_millis(1), || {
+            calls += 1;
+            let calls = calls;
+            async move {
+                if calls < 3 {
+                    Err(Error::new("unavailable".to_string()))
+                } else {
+                    Ok(calls)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+
+        let result: Result<()> = retry(2, Duration::from_millis(1), || async {
+            Err(Error::new("unavailable".to_string()))
+        })
+        .await;
+        assert!(result.is_err());
+    }
+
+    // cargo test -- --show-output test_retry_with_events
+    #[tokio::test]
+    async fn test_retry_with_events() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            ..Default::default()
+        };
+        let events = ReconnectEvents::new();
+        let mut receiver = events.subscribe();
+
+        let result: Result<()> = retry_with_events(&policy, &events, || async {
+            Err(tonic::Status::unavailable("connection reset").into())
+        })
+        .await;
+        assert!(result.is_err());
+
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.attempt, 1);
+        assert_eq!(first.error_kind, ErrorKind::Transport);
+        assert!(first.error.contains("connection reset"));
+        assert_eq!(first.delay, Duration::from_millis(1));
+        let second = receiver.recv().await.unwrap();
+        assert_eq!(second.attempt, 2);
+        assert_eq!(second.delay, Duration::from_millis(2));
+        assert_eq!(second.total_reconnects, 2);
+        // last failed attempt is not retried
+        assert!(receiver.try_recv().is_err());
+
+        // counter is cumulative across calls
+        let _ = retry_with_events(&policy, &events, || async { Ok(()) }).await;
+        let _: Result<()> = retry_with_events(&policy, &events.clone(), || async {
+            Err(Error::new("unavailable".to_string()))
+        })
+        .await;
+        assert_eq!(events.total_reconnects(), 4);
+    }
+}
@@ -8,3 +8,76 @@ pub mod recognizer_beta;
 
 /// v2 API
 pub mod recognizer_v2;
+
+pub use json::transcribe_to_json;
+pub use recognizer_v2::request_id;
+pub use view::ResponseView;
+
+/// v2 API recognition config helpers
+pub mod config;
+
+/// raw audio helpers (duration computation etc.)
+pub mod audio;
+
+/// audio buffering with checkpoints for reconnect loops driven by caller
+pub mod checkpoint;
+
+/// word level (timing, confidence) extraction helpers
+pub mod words;
+
+/// merging of final streaming results into utterances
+pub mod utterances;
+
+/// automatic selection of streaming or batch recognition
+pub mod auto;
+
+/// pluggable destinations of streaming results
+pub mod sink;
+
+/// speech adaptation helpers (phrase sets, debugging)
+pub mod adaptation;
+
+/// subtitles (SRT, WebVTT) generation
+pub mod subtitles;
+
+/// rebasing of result offsets across stream restarts
+pub mod timeline;
+
+/// audio-time ordering of results of overlapping streams (reconnect loops driven by caller)
+pub mod ordering;
+
+/// curated list of supported language codes
+pub mod locales;
+
+/// streaming of the same audio into two recognizers
+pub mod compare;
+
+/// recording and replay of streaming sessions
+pub mod recording;
+
+/// word and character error rates of transcripts
+pub mod eval;
+
+/// typed access to response metadata
+pub mod meta;
+
+/// v2 to v1 fallback of synchronous recognition
+pub mod fallback;
+
+/// deduplication of overlapping finals after stream restart
+pub mod dedup;
+
+/// borrowed typed view of recognition responses
+pub mod view;
+
+/// word aligned JSON transcripts
+pub mod json;
+
+/// transcription cost estimation
+pub mod billing;
+
+/// caching of synchronous recognition results
+pub mod cache;
+
+/// post-processing of final transcripts
+pub mod processing;
@@ -0,0 +1,232 @@
+//! Debugging helpers for speech adaptation (phrase sets, boost values).
+//! Recognize the same audio with and without adaptation and compare transcripts
+//! to see where biasing had an effect.
+//!
+//! PhraseSetBuilder builds inline phrase sets, including phrases referencing
+//! built-in classes (e.g. $ADDRESSNUM, $OOV_CLASS_DIGIT_SEQUENCE).
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    phrase_set::Phrase,
+    speech_adaptation::{adaptation_phrase_set, AdaptationPhraseSet},
+    PhraseSet, RecognizeResponse, SpeechAdaptation,
+};
+use log::*;
+
+/// Google's documented built-in class tokens which can be referenced in phrases,
+/// e.g. "my address is $ADDRESSNUM". Not all classes are available in all languages.
+pub const BUILTIN_CLASSES: &[&str] = &[
+    "$ADDRESSNUM",
+    "$DAY",
+    "$FULLPHONENUM",
+    "$MONEY",
+    "$MONTH",
+    "$OOV_CLASS_ALPHANUMERIC_SEQUENCE",
+    "$OOV_CLASS_ALPHA_SEQUENCE",
+    "$OOV_CLASS_AM_RADIO_FREQUENCY",
+    "$OOV_CLASS_DIGIT_SEQUENCE",
+    "$OOV_CLASS_FM_RADIO_FREQUENCY",
+    "$OOV_CLASS_FULLPHONENUM",
+    "$OOV_CLASS_PERCENT",
+    "$OOV_CLASS_TEMPERATURE",
+    "$OPERAND",
+    "$PERCENT",
+    "$POSTALCODE",
+    "$TIME",
+    "$YEAR",
+];
+
+/// Returns class token (e.g. $ADDRESSNUM) for built-in class name given with or without
+/// leading $ (case insensitive). Unknown classes are logged with warning and returned
+/// anyway (Google may add new classes, custom classes are referenced the same way).
+pub fn builtin_class(name: &str) -> String {
+    let token = format!("${}", name.trim_start_matches('$').to_uppercase());
+    if !BUILTIN_CLASSES.contains(&token.as_str()) {
+        warn!("Unknown built-in class token {}", token);
+    }
+    token
+}
+
+/// Builder of inline phrase sets.
+#[derive(Debug, Clone, Default)]
+pub struct PhraseSetBuilder {
+    phrase_set: PhraseSet,
+}
+
+impl PhraseSetBuilder {
+    /// Creates builder of empty phrase set.
+    pub fn new() -> Self {
+        PhraseSetBuilder::default()
+    }
+
+    /// Sets boost applied to all phrases without own boost.
+    pub fn boost(mut self, boost: f32) -> Self {
+        self.phrase_set.boost = boost;
+        self
+    }
+
+    /// Adds phrase, boost 0 means boost of phrase set is used.
+    pub fn add_phrase(mut self, value: impl Into<String>, boost: f32) -> Self {
+        self.phrase_set.phrases.push(Phrase {
+            value: value.into(),
+            boost,
+        });
+        self
+    }
+
+    /// Adds phrase consisting of single class token (see builtin_class),
+    /// e.g. add_class_token("ADDRESSNUM", 10.0) biases recognition towards street numbers.
+    pub fn add_class_token(self, name: &str, boost: f32) -> Self {
+        self.add_phrase(builtin_class(name), boost)
+    }
+
+    /// Returns built phrase set.
+    pub fn build(self) -> PhraseSet {
+        self.phrase_set
+    }
+
+    /// Returns speech adaptation (RecognitionConfig.adaptation) with built inline phrase set.
+    pub fn build_adaptation(self) -> SpeechAdaptation {
+        SpeechAdaptation {
+            phrase_sets: vec![AdaptationPhraseSet {
+                value: Some(adaptation_phrase_set::Value::InlinePhraseSet(self.build())),
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns words of top alternatives of all results.
+fn transcript_words(response: &RecognizeResponse) -> Vec<String> {
+    response
+        .results
+        .iter()
+        .filter_map(|result| result.alternatives.first())
+        .flat_map(|alternative| alternative.transcript.split_whitespace())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Aligns transcripts (top alternatives) of two responses word by word (longest common
+/// subsequence) and returns differing passages as (without adaptation, with adaptation)
+/// pairs. Any of the passages is empty if words were only removed or only added.
+/// Words are compared case insensitively.
+pub fn diff_transcripts(
+    without: &RecognizeResponse,
+    with: &RecognizeResponse,
+) -> Vec<(String, String)> {
+    let old = transcript_words(without);
+    let new = transcript_words(with);
+    let same = |i: usize, j: usize| old[i].to_lowercase() == new[j].to_lowercase();
+
+    // lcs[i][j] = length of longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if same(i, j) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut differences = vec![];
+    let (mut removed, mut added): (Vec<&str>, Vec<&str>) = (vec![], vec![]);
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && same(i, j) {
+            if !removed.is_empty() || !added.is_empty() {
+                differences.push((removed.join(" "), added.join(" ")));
+                removed.clear();
+                added.clear();
+            }
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(&new[j]);
+            j += 1;
+        } else {
+            removed.push(&old[i]);
+            i += 1;
+        }
+    }
+    if !removed.is_empty() || !added.is_empty() {
+        differences.push((removed.join(" "), added.join(" ")));
+    }
+
+    differences
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        SpeechRecognitionAlternative, SpeechRecognitionResult,
+    };
+
+    #[allow(dead_code)]
+    fn response(transcripts: &[&str]) -> RecognizeResponse {
+        RecognizeResponse {
+            results: transcripts
+                .iter()
+                .map(|transcript| SpeechRecognitionResult {
+                    alternatives: vec![SpeechRecognitionAlternative {
+                        transcript: transcript.to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+                .collect(),
+            metadata: None,
+        }
+    }
+
+    // cargo test -- --show-output test_phrase_set_builder
+    #[test]
+    fn test_phrase_set_builder() {
+        assert_eq!(builtin_class("addressnum"), "$ADDRESSNUM");
+        assert_eq!(
+            builtin_class("$OOV_CLASS_DIGIT_SEQUENCE"),
+            "$OOV_CLASS_DIGIT_SEQUENCE"
+        );
+        // unknown classes are only logged
+        assert_eq!(builtin_class("$my_class"), "$MY_CLASS");
+
+        let phrase_set = PhraseSetBuilder::new()
+            .boost(5.0)
+            .add_phrase("kubernetes", 0.0)
+            .add_class_token("ADDRESSNUM", 10.0)
+            .build();
+        assert_eq!(phrase_set.boost, 5.0);
+        assert_eq!(phrase_set.phrases.len(), 2);
+        assert_eq!(phrase_set.phrases[1].value, "$ADDRESSNUM");
+        assert_eq!(phrase_set.phrases[1].boost, 10.0);
+
+        let adaptation = PhraseSetBuilder::new()
+            .add_class_token("POSTALCODE", 0.0)
+            .build_adaptation();
+        match &adaptation.phrase_sets[0].value {
+            Some(adaptation_phrase_set::Value::InlinePhraseSet(phrase_set)) => {
+                assert_eq!(phrase_set.phrases[0].value, "$POSTALCODE")
+            }
+            _ => panic!("inline phrase set expected"),
+        }
+    }
+
+    // cargo test -- --show-output test_diff_transcripts
+    #[test]
+    fn test_diff_transcripts() {
+        let without = response(&["send it to cute bernetes", "cluster now"]);
+        let with = response(&["Send it to kubernetes", "cluster right now please"]);
+        assert_eq!(
+            diff_transcripts(&without, &with),
+            vec![
+                ("cute bernetes".to_string(), "kubernetes".to_string()),
+                ("".to_string(), "right".to_string()),
+                ("".to_string(), "please".to_string()),
+            ]
+        );
+
+        assert!(diff_transcripts(&without, &without).is_empty());
+    }
+}
@@ -0,0 +1,537 @@
+//! Helpers for working with raw (PCM) audio sent to speech-to-text APIs.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    explicit_decoding_config::AudioEncoding, ExplicitDecodingConfig,
+};
+use crate::errors::{Error, Result};
+use std::time::Duration;
+
+/// Computes duration of raw PCM audio (e.g. LINEAR16) of given byte length.
+/// If byte_len is not aligned to whole frames (channels * bits_per_sample / 8 bytes)
+/// trailing partial frame is ignored, i.e. duration is rounded down to the last whole frame.
+/// Returns zero duration if any of the audio parameters is zero.
+pub fn pcm_duration(
+    byte_len: usize,
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u16,
+) -> Duration {
+    let bytes_per_sample = (bits_per_sample as u64).div_ceil(8);
+    let frame_size = bytes_per_sample * channels as u64;
+    if frame_size == 0 || sample_rate == 0 {
+        return Duration::from_secs(0);
+    }
+
+    let frames = byte_len as u64 / frame_size;
+    let secs = frames / sample_rate as u64;
+    let remaining_frames = frames % sample_rate as u64;
+    Duration::from_secs(secs)
+        + Duration::from_nanos(remaining_frames * 1_000_000_000 / sample_rate as u64)
+}
+
+/// Inverse of pcm_duration. Computes byte length of raw PCM audio of given duration.
+/// Result is always aligned to whole frames (rounded down).
+pub fn pcm_byte_len(
+    duration: Duration,
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u16,
+) -> usize {
+    let frame_size = (bits_per_sample as u128).div_ceil(8) * channels as u128;
+    let frames = duration.as_nanos() * sample_rate as u128 / 1_000_000_000;
+    (frames * frame_size) as usize
+}
+
+/// Computes duration of headerless audio (LINEAR16, MULAW, ALAW) described by
+/// explicit decoding config. Returns None for other (container/compressed) encodings
+/// where duration cannot be derived from byte length.
+pub fn explicit_audio_duration(
+    decoding_config: &ExplicitDecodingConfig,
+    byte_len: usize,
+) -> Option<Duration> {
+    let bits_per_sample = match AudioEncoding::from_i32(decoding_config.encoding)? {
+        AudioEncoding::Linear16 => 16,
+        AudioEncoding::Mulaw | AudioEncoding::Alaw => 8,
+        _ => return None,
+    };
+    Some(pcm_duration(
+        byte_len,
+        decoding_config.sample_rate_hertz.max(0) as u32,
+        decoding_config.audio_channel_count.max(1) as u16,
+        bits_per_sample,
+    ))
+}
+
+/// Converts 16-bit samples into little-endian LINEAR16 bytes.
+pub fn i16_to_linear16(samples: &[i16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect()
+}
+
+/// Converts normalized f32 samples (-1.0 - 1.0) into little-endian LINEAR16 bytes.
+/// Samples outside of the range are clamped, NaN is converted to silence.
+pub fn f32_to_linear16(samples: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| {
+            // saturating cast, NaN is cast to 0
+            let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+            sample.to_le_bytes()
+        })
+        .collect()
+}
+
+/// Converts little-endian LINEAR16 bytes into 16-bit samples.
+/// Returns error with code FRAME_MISALIGNED if byte length is odd.
+fn linear16_to_i16(bytes: &[u8]) -> Result<Vec<i16>> {
+    Ok(to_little_endian_linear16(bytes, false)?
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+        .collect())
+}
+
+/// Converts LINEAR16 audio to little-endian byte order expected by Google. If source is
+/// big-endian bytes of each 16-bit sample are swapped, otherwise bytes are copied.
+/// Big-endian audio sent as is transcribes as white noise (garbage or empty transcripts).
+/// Returns error with code FRAME_MISALIGNED if byte length is odd.
+pub fn to_little_endian_linear16(bytes: &[u8], source_big_endian: bool) -> Result<Vec<u8>> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(Error::new_with_code(
+            format!(
+                "LINEAR16 audio must have even byte length, got {}",
+                bytes.len()
+            ),
+            "FRAME_MISALIGNED".to_string(),
+        ));
+    }
+    if !source_big_endian {
+        return Ok(bytes.to_vec());
+    }
+    Ok(bytes
+        .chunks_exact(2)
+        .flat_map(|sample| [sample[1], sample[0]])
+        .collect())
+}
+
+/// Scales 16-bit samples so that absolute peak matches target_peak. Result never clips
+/// (no sample exceeds target_peak). Silent audio and non-positive target_peak are left
+/// unchanged. This is a simple peak normalizer, not an automatic gain control (AGC):
+/// single gain is applied to all samples, i.e. single loud click limits amplification
+/// of otherwise quiet audio.
+pub fn normalize_gain(pcm: &mut [i16], target_peak: i16) {
+    let peak = pcm
+        .iter()
+        .map(|sample| (*sample as i32).abs())
+        .max()
+        .unwrap_or(0);
+    if peak == 0 || target_peak <= 0 {
+        return;
+    }
+    let target_peak = target_peak as i32;
+    for sample in pcm.iter_mut() {
+        *sample = (*sample as i32 * target_peak / peak).clamp(-target_peak, target_peak) as i16;
+    }
+}
+
+/// Length of fixed part of Ogg page header (capture pattern up to segment count).
+pub const OGG_PAGE_HEADER_LEN: usize = 27;
+
+/// Checks that audio starts with Ogg page ("OggS" capture pattern) containing
+/// OpusHead header, i.e. that audio is OGG_OPUS stream sent from its beginning.
+/// Returns error with code INVALID_OGG_OPUS otherwise.
+pub fn validate_ogg_opus_header(bytes: &[u8]) -> Result<()> {
+    let invalid = |reason: &str| {
+        Err(Error::new_with_code(
+            format!("Audio is not OGG_OPUS stream: {}", reason),
+            "INVALID_OGG_OPUS".to_string(),
+        ))
+    };
+    if bytes.len() < OGG_PAGE_HEADER_LEN || &bytes[..4] != b"OggS" {
+        return invalid("missing Ogg capture pattern OggS");
+    }
+    let segments_end = OGG_PAGE_HEADER_LEN + bytes[OGG_PAGE_HEADER_LEN - 1] as usize;
+    match bytes.get(segments_end..segments_end + 8) {
+        Some(magic) if magic == b"OpusHead" => Ok(()),
+        _ => invalid("first Ogg page does not contain OpusHead"),
+    }
+}
+
+/// Audio format read from fmt chunk of WAV (RIFF/WAVE) header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavFormat {
+    /// WAVE format tag (1 PCM, 3 IEEE float, 6 A-law, 7 u-law), for WAVE_FORMAT_EXTENSIBLE
+    /// format tag of the sub-format
+    pub format_tag: u16,
+    /// number of channels
+    pub channels: u16,
+    /// sample rate in Hz
+    pub sample_rate: u32,
+    /// bits per sample
+    pub bits_per_sample: u16,
+}
+
+impl WavFormat {
+    /// Returns encoding of samples if it is supported by speech-to-text API
+    /// (16-bit PCM, 8-bit A-law or u-law), None otherwise.
+    pub fn encoding(&self) -> Option<AudioEncoding> {
+        match (self.format_tag, self.bits_per_sample) {
+            (1, 16) => Some(AudioEncoding::Linear16),
+            (6, 8) => Some(AudioEncoding::Alaw),
+            (7, 8) => Some(AudioEncoding::Mulaw),
+            _ => None,
+        }
+    }
+}
+
+/// Parses fmt chunk of WAV header, bytes must start at the beginning of the file
+/// and include fmt chunk. Returns error with code UNSUPPORTED_FORMAT if bytes are
+/// not WAV file or fmt chunk is missing.
+pub fn parse_wav_header(bytes: &[u8]) -> Result<WavFormat> {
+    let invalid = |reason: &str| {
+        Err(Error::new_with_code(
+            format!("Audio is not WAV file: {}", reason),
+            "UNSUPPORTED_FORMAT".to_string(),
+        ))
+    };
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return invalid("missing RIFF/WAVE header");
+    }
+    let u16_at = |offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+    let u32_at = |offset: usize| {
+        u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_len = u32_at(offset + 4) as usize;
+        let data = offset + 8;
+        if &bytes[offset..offset + 4] == b"fmt " {
+            if chunk_len < 16 || data + chunk_len.min(26) > bytes.len() {
+                return invalid("truncated fmt chunk");
+            }
+            let mut format_tag = u16_at(data);
+            // WAVE_FORMAT_EXTENSIBLE, sub-format GUID starts with format tag
+            if format_tag == 0xfffe && chunk_len >= 26 {
+                format_tag = u16_at(data + 24);
+            }
+            return Ok(WavFormat {
+                format_tag,
+                channels: u16_at(data + 2),
+                sample_rate: u32_at(data + 4),
+                bits_per_sample: u16_at(data + 14),
+            });
+        }
+        // chunks are padded to even length
+        offset = data + chunk_len + chunk_len % 2;
+    }
+    invalid("fmt chunk not found")
+}
+
+/// Decodes MP3 audio into little-endian LINEAR16 bytes (channels interleaved) and returns
+/// them together with sample rate and channel count needed for ExplicitDecodingConfig.
+/// Corrupted frames are skipped. Returns error with code MP3_DECODE if audio is not MP3
+/// or contains no decodable frame. Whole audio is decoded into memory, i.e. mind
+/// synchronous recognition limits (about 1 minute of audio) for long podcasts.
+///
+/// Available with feature mp3 which uses pure Rust decoder symphonia
+/// (https://github.com/pdeljanov/Symphonia) licensed under MPL-2.0. MPL is file-level
+/// copyleft, i.e. it is compatible with MIT/Apache-2.0 licensed applications, but
+/// modifications of symphonia sources must be published under MPL.
+#[cfg(feature = "mp3")]
+pub fn decode_mp3_to_linear16(bytes: &[u8]) -> Result<(Vec<u8>, u32, u16)> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let decode_error = |reason: String| {
+        Error::new_with_code(
+            format!("MP3 decoding failed: {}", reason),
+            "MP3_DECODE".to_string(),
+        )
+    };
+
+    let source = MediaSourceStream::new(
+        Box::new(std::io::Cursor::new(bytes.to_vec())),
+        Default::default(),
+    );
+    let mut hint = Hint::new();
+    hint.with_extension("mp3");
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|error| decode_error(error.to_string()))?
+        .format;
+    let track = format
+        .default_track()
+        .ok_or_else(|| decode_error("no audio track".to_string()))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|error| decode_error(error.to_string()))?;
+
+    let mut pcm = vec![];
+    let mut sample_rate = 0;
+    let mut channels = 0;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(error))
+                if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break
+            }
+            Err(error) => return Err(decode_error(error.to_string())),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count() as u16;
+                let mut samples = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+                samples.copy_interleaved_ref(decoded);
+                pcm.extend(i16_to_linear16(samples.samples()));
+            }
+            // corrupted frame
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(error) => return Err(decode_error(error.to_string())),
+        }
+    }
+
+    if pcm.is_empty() {
+        return Err(decode_error("no decodable MP3 frame".to_string()));
+    }
+    Ok((pcm, sample_rate, channels))
+}
+
+/// Transformations of LINEAR16 audio applied by Recognizer::pump_audio_from_reader_with_options
+/// to every chunk before it is sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PumpOptions {
+    /// source audio is big-endian LINEAR16 (see to_little_endian_linear16)
+    pub big_endian: bool,
+
+    /// peak normalization target (see normalize_gain). Gain is computed
+    /// for every chunk separately.
+    pub normalize_gain: Option<i16>,
+}
+
+impl PumpOptions {
+    /// Returns true if options change audio, i.e. chunks must be aligned to 16-bit samples.
+    pub(crate) fn transforms(&self) -> bool {
+        self.big_endian || self.normalize_gain.is_some()
+    }
+
+    /// Applies options to audio chunk.
+    pub(crate) fn apply(&self, chunk: Vec<u8>) -> Result<Vec<u8>> {
+        let chunk = if self.big_endian {
+            to_little_endian_linear16(&chunk, true)?
+        } else {
+            chunk
+        };
+        match self.normalize_gain {
+            Some(target_peak) => {
+                let mut samples = linear16_to_i16(&chunk)?;
+                normalize_gain(&mut samples, target_peak);
+                Ok(i16_to_linear16(&samples))
+            }
+            None => Ok(chunk),
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_pcm_duration
+    #[test]
+    fn test_pcm_duration() {
+        // 1 second of 16kHz mono LINEAR16
+        assert_eq!(pcm_duration(32000, 16000, 1, 16), Duration::from_secs(1));
+        // 250ms of 8kHz stereo LINEAR16
+        assert_eq!(pcm_duration(8000, 8000, 2, 16), Duration::from_millis(250));
+        // trailing partial frame is ignored
+        assert_eq!(pcm_duration(32001, 16000, 1, 16), Duration::from_secs(1));
+        assert_eq!(pcm_duration(1000, 0, 1, 16), Duration::from_secs(0));
+        assert_eq!(pcm_duration(1000, 16000, 0, 16), Duration::from_secs(0));
+    }
+
+    // cargo test -- --show-output test_pcm_byte_len
+    #[test]
+    fn test_pcm_byte_len() {
+        assert_eq!(pcm_byte_len(Duration::from_secs(1), 16000, 1, 16), 32000);
+        assert_eq!(pcm_byte_len(Duration::from_millis(250), 8000, 2, 16), 8000);
+        // rounded down to whole frames
+        assert_eq!(pcm_byte_len(Duration::from_micros(100), 16000, 1, 16), 2);
+    }
+
+    // cargo test -- --show-output test_explicit_audio_duration
+    #[test]
+    fn test_explicit_audio_duration() {
+        let mut decoding_config = ExplicitDecodingConfig {
+            encoding: AudioEncoding::Mulaw as i32,
+            sample_rate_hertz: 8000,
+            audio_channel_count: 1,
+        };
+        assert_eq!(
+            explicit_audio_duration(&decoding_config, 8000),
+            Some(Duration::from_secs(1))
+        );
+
+        decoding_config.encoding = AudioEncoding::Flac as i32;
+        assert_eq!(explicit_audio_duration(&decoding_config, 8000), None);
+    }
+
+    // cargo test -- --show-output test_to_little_endian_linear16
+    #[test]
+    fn test_to_little_endian_linear16() {
+        let big_endian = [0x01, 0x02, 0xff, 0xfe];
+        assert_eq!(
+            to_little_endian_linear16(&big_endian, true).unwrap(),
+            vec![0x02, 0x01, 0xfe, 0xff]
+        );
+        assert_eq!(
+            to_little_endian_linear16(&big_endian, false).unwrap(),
+            big_endian.to_vec()
+        );
+        let error = to_little_endian_linear16(&[1, 2, 3], true).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("FRAME_MISALIGNED"));
+
+        let options = PumpOptions {
+            big_endian: true,
+            ..Default::default()
+        };
+        assert_eq!(options.apply(vec![0, 1]).unwrap(), vec![1, 0]);
+    }
+
+    // cargo test -- --show-output test_normalize_gain
+    #[test]
+    fn test_normalize_gain() {
+        let mut pcm = [100, -200, 50, 0];
+        normalize_gain(&mut pcm, 20000);
+        assert_eq!(pcm, [10000, -20000, 5000, 0]);
+
+        // loud audio is attenuated
+        let mut pcm = [i16::MIN, i16::MAX];
+        normalize_gain(&mut pcm, 16384);
+        assert_eq!(pcm, [-16384, 16383]);
+
+        let mut silence = [0i16; 4];
+        normalize_gain(&mut silence, 20000);
+        assert_eq!(silence, [0; 4]);
+
+        let options = PumpOptions {
+            normalize_gain: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.apply(i16_to_linear16(&[10, -5])).unwrap(),
+            i16_to_linear16(&[1000, -500])
+        );
+    }
+
+    // cargo test -- --show-output test_validate_ogg_opus_header
+    #[test]
+    fn test_validate_ogg_opus_header() {
+        let mut page = b"OggS".to_vec();
+        page.extend([0u8; 22]);
+        // single segment of 19 bytes
+        page.extend([1, 19]);
+        page.extend(b"OpusHead");
+        page.extend([0u8; 11]);
+        assert!(validate_ogg_opus_header(&page).is_ok());
+
+        let error = validate_ogg_opus_header(b"RIFF....WAVEfmt ").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("INVALID_OGG_OPUS"));
+        // Ogg container with other codec
+        let vorbis = [&page[..28], b"\x01vorbis\0"].concat();
+        assert!(validate_ogg_opus_header(&vorbis).is_err());
+    }
+
+    // cargo test -- --show-output test_i16_to_linear16
+    #[test]
+    fn test_i16_to_linear16() {
+        assert_eq!(i16_to_linear16(&[1, -2, 256]), vec![1, 0, 254, 255, 0, 1]);
+    }
+
+    // cargo test -- --show-output test_f32_to_linear16
+    #[test]
+    fn test_f32_to_linear16() {
+        assert_eq!(
+            f32_to_linear16(&[0.0, 1.0, -1.0, 0.5]),
+            i16_to_linear16(&[0, i16::MAX, -i16::MAX, 16384])
+        );
+        // clamped at the extremes
+        assert_eq!(
+            f32_to_linear16(&[1.5, -7.0, f32::INFINITY, f32::NEG_INFINITY]),
+            i16_to_linear16(&[i16::MAX, -i16::MAX, i16::MAX, -i16::MAX])
+        );
+        assert_eq!(f32_to_linear16(&[f32::NAN]), vec![0, 0]);
+    }
+
+    // cargo test -- --show-output test_parse_wav_header
+    #[test]
+    fn test_parse_wav_header() {
+        let wav = |format_tag: u16, bits_per_sample: u16| {
+            let mut header = b"RIFF\0\0\0\0WAVE".to_vec();
+            // chunk preceding fmt chunk (odd length, padded)
+            header.extend(b"LIST\x03\0\0\0abc\0");
+            header.extend(b"fmt \x10\0\0\0");
+            header.extend(format_tag.to_le_bytes());
+            header.extend(2u16.to_le_bytes());
+            header.extend(16000u32.to_le_bytes());
+            header.extend(64000u32.to_le_bytes());
+            header.extend(4u16.to_le_bytes());
+            header.extend(bits_per_sample.to_le_bytes());
+            header
+        };
+        let format = parse_wav_header(&wav(1, 16)).unwrap();
+        assert_eq!(
+            format,
+            WavFormat {
+                format_tag: 1,
+                channels: 2,
+                sample_rate: 16000,
+                bits_per_sample: 16,
+            }
+        );
+        assert_eq!(format.encoding(), Some(AudioEncoding::Linear16));
+        assert_eq!(
+            parse_wav_header(&wav(7, 8)).unwrap().encoding(),
+            Some(AudioEncoding::Mulaw)
+        );
+        // 32-bit float
+        assert_eq!(parse_wav_header(&wav(3, 32)).unwrap().encoding(), None);
+
+        let error = parse_wav_header(b"OggS").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("UNSUPPORTED_FORMAT"));
+        let error = parse_wav_header(b"RIFF\0\0\0\0WAVEdata").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("UNSUPPORTED_FORMAT"));
+    }
+
+    // cargo test --features mp3 -- --show-output test_decode_mp3_to_linear16
+    #[cfg(feature = "mp3")]
+    #[test]
+    fn test_decode_mp3_to_linear16() {
+        let error = decode_mp3_to_linear16(b"RIFF....WAVEfmt ").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("MP3_DECODE"));
+        assert!(decode_mp3_to_linear16(&[]).is_err());
+    }
+}
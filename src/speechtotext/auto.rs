@@ -0,0 +1,346 @@
+//! Transcription which picks the right v2 API automatically. Inline audio is transcribed
+//! by streaming recognition, audio stored in Google Cloud Storage by batch recognition.
+//! Duration threshold does not switch between the two APIs (batch recognition accepts
+//! Cloud Storage URIs only and duration of remote files is not inspected), it rejects
+//! inline audio too long to be streamed. Result of both is returned as unified Transcript.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    batch_recognize_file_metadata, batch_recognize_file_result, recognition_config::DecodingConfig,
+    recognition_output_config, BatchRecognizeFileMetadata, BatchRecognizeRequest,
+    InlineOutputConfig, RecognitionConfig, RecognitionOutputConfig, SpeechRecognitionResult,
+    StreamingRecognitionConfig, StreamingRecognitionResult,
+};
+use crate::common::convert::to_std_duration;
+use crate::errors::{Error, Result};
+use crate::speechtotext::audio::explicit_audio_duration;
+use crate::speechtotext::recognizer_v2::Recognizer;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+/// Default max duration of inline audio transcribed by streaming recognition
+/// (Google limits streaming sessions to about 5 minutes), longer inline audio is rejected.
+pub const STREAMING_MAX_DURATION: Duration = Duration::from_secs(5 * 60);
+
+/// Size of audio chunks sent to streaming recognition.
+const STREAMING_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Audio to be transcribed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioInput {
+    /// inline audio bytes
+    Content(Vec<u8>),
+    /// Google Cloud Storage URI, e.g. gs://bucket/recording.wav
+    Uri(String),
+}
+
+/// API used to transcribe audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptionMode {
+    /// streaming recognition (streaming_recognize)
+    Streaming,
+    /// batch recognition (batch_recognize)
+    Batch,
+}
+
+/// Single final recognition result (top alternative only).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    /// transcript of the top alternative
+    pub text: String,
+
+    /// offset of the end of this result relative to the beginning of the audio
+    pub result_end_offset: Duration,
+
+    /// channel number for multi-channel audio
+    pub channel_tag: i32,
+
+    /// language detected in this result
+    pub language_code: String,
+}
+
+/// Unified result of automatic transcription.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    /// API which was used
+    pub mode: TranscriptionMode,
+
+    /// final results in order returned by the API
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// Returns transcripts of all segments joined by space.
+    pub fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl From<&SpeechRecognitionResult> for TranscriptSegment {
+    fn from(result: &SpeechRecognitionResult) -> Self {
+        TranscriptSegment {
+            text: result
+                .alternatives
+                .first()
+                .map(|alternative| alternative.transcript.clone())
+                .unwrap_or_default(),
+            result_end_offset: result
+                .result_end_offset
+                .as_ref()
+                .map(to_std_duration)
+                .unwrap_or_default(),
+            channel_tag: result.channel_tag,
+            language_code: result.language_code.clone(),
+        }
+    }
+}
+
+impl From<&StreamingRecognitionResult> for TranscriptSegment {
+    fn from(result: &StreamingRecognitionResult) -> Self {
+        TranscriptSegment {
+            text: result
+                .alternatives
+                .first()
+                .map(|alternative| alternative.transcript.clone())
+                .unwrap_or_default(),
+            result_end_offset: result
+                .result_end_offset
+                .as_ref()
+                .map(to_std_duration)
+                .unwrap_or_default(),
+            channel_tag: result.channel_tag,
+            language_code: result.language_code.clone(),
+        }
+    }
+}
+
+/// Same as transcribe_auto_with_threshold with STREAMING_MAX_DURATION threshold.
+pub async fn transcribe_auto(
+    // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+    google_credentials: impl AsRef<str>,
+    source: AudioInput,
+    // Required. The name of the Recognizer to use during recognition.
+    recognizer: String,
+    config: RecognitionConfig,
+) -> Result<Transcript> {
+    transcribe_auto_with_threshold(
+        google_credentials,
+        source,
+        recognizer,
+        config,
+        STREAMING_MAX_DURATION,
+    )
+    .await
+}
+
+/// Transcribes audio with API picked by select_mode: inline content by streaming
+/// recognition, Cloud Storage URI by batch recognition (waits until batch operation
+/// is done). Inline content longer than streaming_max_duration is rejected with error
+/// code AUDIO_TOO_LONG, i.e. it is not transcribed by batch recognition.
+pub async fn transcribe_auto_with_threshold(
+    // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+    google_credentials: impl AsRef<str>,
+    source: AudioInput,
+    // Required. The name of the Recognizer to use during recognition.
+    recognizer: String,
+    config: RecognitionConfig,
+    // Max duration of inline audio transcribed by streaming recognition, longer is rejected
+    streaming_max_duration: Duration,
+) -> Result<Transcript> {
+    select_mode(&source, &config, streaming_max_duration)?;
+    match source {
+        AudioInput::Content(content) => {
+            let streaming_config = StreamingRecognitionConfig {
+                config: Some(config),
+                ..Default::default()
+            };
+            let mut streaming_recognizer = Recognizer::create_streaming_recognizer(
+                google_credentials,
+                streaming_config,
+                None,
+                recognizer,
+            )
+            .await?;
+            transcribe_streaming(&mut streaming_recognizer, content).await
+        }
+        AudioInput::Uri(uri) => {
+            let mut batch_recognizer =
+                Recognizer::create_asynchronous_recognizer(google_credentials).await?;
+            transcribe_batch(&mut batch_recognizer, uri, recognizer, config).await
+        }
+    }
+}
+
+/// Returns API to be used for given audio, picked by source type:
+///
+/// * Cloud Storage URI - batch, regardless of duration (remote file is not inspected)
+/// * inline content up to streaming_max_duration - streaming
+/// * inline content longer than streaming_max_duration - rejected with error code
+///   AUDIO_TOO_LONG (such audio must be uploaded to Cloud Storage first)
+/// * inline content of unknown duration - streaming
+///
+/// Duration of inline content can be determined only for explicit raw PCM decoding configs.
+pub fn select_mode(
+    source: &AudioInput,
+    config: &RecognitionConfig,
+    streaming_max_duration: Duration,
+) -> Result<TranscriptionMode> {
+    match source {
+        AudioInput::Uri(_) => Ok(TranscriptionMode::Batch),
+        AudioInput::Content(content) => {
+            let duration = match &config.decoding_config {
+                Some(DecodingConfig::ExplicitDecodingConfig(explicit)) => {
+                    explicit_audio_duration(explicit, content.len())
+                }
+                _ => None,
+            };
+            match duration {
+                Some(duration) if duration > streaming_max_duration => Err(Error::new_with_code(
+                    format!(
+                        "Inline audio ({:?}) exceeds streaming limit ({:?}). \
+                             Upload it to Google Cloud Storage and use AudioInput::Uri.",
+                        duration, streaming_max_duration
+                    ),
+                    "AUDIO_TOO_LONG".to_string(),
+                )),
+                _ => Ok(TranscriptionMode::Streaming),
+            }
+        }
+    }
+}
+
+async fn transcribe_streaming(recognizer: &mut Recognizer, content: Vec<u8>) -> Result<Transcript> {
+    let audio_sink = recognizer.take_audio_sink().ok_or_else(|| {
+        Error::new("Audio sink of streaming recognizer is not available".to_string())
+    })?;
+
+    tokio::spawn(async move {
+        for chunk in content.chunks(STREAMING_CHUNK_SIZE) {
+            let request = Recognizer::streaming_request_from_bytes(chunk.to_vec(), String::new());
+            if audio_sink.send(request).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut segments = vec![];
+    let response_stream = recognizer.streaming_recognize_async_stream().await;
+    let mut response_stream = Box::pin(response_stream);
+    while let Some(response) = response_stream.next().await {
+        segments.extend(
+            response?
+                .results
+                .iter()
+                .filter(|result| result.is_final)
+                .map(TranscriptSegment::from),
+        );
+    }
+
+    Ok(Transcript {
+        mode: TranscriptionMode::Streaming,
+        segments,
+    })
+}
+
+async fn transcribe_batch(
+    recognizer: &mut Recognizer,
+    uri: String,
+    recognizer_name: String,
+    config: RecognitionConfig,
+) -> Result<Transcript> {
+    let results = batch_recognize_inline(recognizer, uri, recognizer_name, config).await?;
+    Ok(Transcript {
+        mode: TranscriptionMode::Batch,
+        segments: results.iter().map(TranscriptSegment::from).collect(),
+    })
+}
+
+/// Runs batch recognition of single Cloud Storage file with inline output,
+/// waits until operation is done and returns results of the file.
+pub(crate) async fn batch_recognize_inline(
+    recognizer: &mut Recognizer,
+    uri: String,
+    recognizer_name: String,
+    config: RecognitionConfig,
+) -> Result<Vec<SpeechRecognitionResult>> {
+    let request = BatchRecognizeRequest {
+        recognizer: recognizer_name,
+        config: Some(config),
+        files: vec![BatchRecognizeFileMetadata {
+            audio_source: Some(batch_recognize_file_metadata::AudioSource::Uri(uri.clone())),
+            ..Default::default()
+        }],
+        recognition_output_config: Some(RecognitionOutputConfig {
+            output: Some(recognition_output_config::Output::InlineResponseConfig(
+                InlineOutputConfig {},
+            )),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let operation = recognizer.batch_recognize(request).await?.into_inner();
+    let response = recognizer.batch_wait(operation, None).await?;
+
+    let file_result = response
+        .and_then(|mut response| response.results.remove(&uri))
+        .ok_or_else(|| Error::new(format!("Batch recognition returned no result for {}", uri)))?;
+
+    if let Some(rpc_status) = file_result.error {
+        return Err(Error::new_with_code(
+            rpc_status.message,
+            rpc_status.code.to_string(),
+        ));
+    }
+
+    Ok(match file_result.result {
+        Some(batch_recognize_file_result::Result::InlineResult(inline_result)) => inline_result
+            .transcript
+            .map(|transcript| transcript.results)
+            .unwrap_or_default(),
+        _ => vec![],
+    })
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::speechtotext::config::telephony_linear16;
+
+    // cargo test -- --show-output test_select_mode
+    #[test]
+    fn test_select_mode() {
+        let config = RecognitionConfig {
+            decoding_config: Some(DecodingConfig::ExplicitDecodingConfig(telephony_linear16())),
+            ..Default::default()
+        };
+        let threshold = Duration::from_secs(60);
+
+        let uri = AudioInput::Uri("gs://bucket/audio.wav".to_string());
+        assert_eq!(
+            select_mode(&uri, &config, threshold).unwrap(),
+            TranscriptionMode::Batch
+        );
+
+        // 8kHz LINEAR16 = 16000 bytes per second
+        let short = AudioInput::Content(vec![0u8; 16000 * 60]);
+        assert_eq!(
+            select_mode(&short, &config, threshold).unwrap(),
+            TranscriptionMode::Streaming
+        );
+
+        let long = AudioInput::Content(vec![0u8; 16000 * 61]);
+        let error = select_mode(&long, &config, threshold).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("AUDIO_TOO_LONG"));
+
+        // duration of content with auto detected decoding is unknown
+        assert_eq!(
+            select_mode(&long, &RecognitionConfig::default(), threshold).unwrap(),
+            TranscriptionMode::Streaming
+        );
+    }
+}
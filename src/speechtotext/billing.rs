@@ -0,0 +1,143 @@
+//! Estimation of transcription cost from audio duration.
+//! Estimate only, actual price depends on region, volume tiers, data logging opt-in
+//! and contract with Google, see https://cloud.google.com/speech-to-text/pricing.
+//! Audio duration is rounded up to Google's 15-second billing increments.
+//!
+//! Default rates (USD per minute of audio, list prices at the time of writing):
+//! * v2 models (long, short, telephony, chirp, chirp_2, chirp_3): 0.016
+//! * any other (v1) model: 0.024 standard, 0.036 enhanced
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Length of billing increment, i.e. billed duration is rounded up to its multiple.
+pub const BILLING_INCREMENT: Duration = Duration::from_secs(15);
+
+/// Price of one minute of audio (in currency of the pricing table).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelRate {
+    /// price per minute of standard recognition
+    pub standard_per_minute: f64,
+    /// price per minute of enhanced recognition (v1 use_enhanced)
+    pub enhanced_per_minute: f64,
+}
+
+impl ModelRate {
+    /// Creates rate with the same price for standard and enhanced recognition.
+    pub fn flat(per_minute: f64) -> Self {
+        ModelRate {
+            standard_per_minute: per_minute,
+            enhanced_per_minute: per_minute,
+        }
+    }
+}
+
+/// Pricing table used for cost estimation. Default table contains rates
+/// listed in module docs, rates can be overridden per model (e.g. for different
+/// regions or contracts).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    /// rate of models not listed in model_rates
+    pub default_rate: ModelRate,
+    /// rates by model name
+    pub model_rates: HashMap<String, ModelRate>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        let v2_rate = ModelRate::flat(0.016);
+        PricingTable {
+            default_rate: ModelRate {
+                standard_per_minute: 0.024,
+                enhanced_per_minute: 0.036,
+            },
+            model_rates: ["long", "short", "telephony", "chirp", "chirp_2", "chirp_3"]
+                .iter()
+                .map(|model| (model.to_string(), v2_rate))
+                .collect(),
+        }
+    }
+}
+
+impl PricingTable {
+    /// Sets (or replaces) rate of given model.
+    pub fn with_model_rate(mut self, model: impl Into<String>, rate: ModelRate) -> Self {
+        self.model_rates.insert(model.into(), rate);
+        self
+    }
+
+    /// Returns rate of given model (default rate if model is not listed).
+    pub fn rate(&self, model: &str) -> ModelRate {
+        self.model_rates
+            .get(model)
+            .copied()
+            .unwrap_or(self.default_rate)
+    }
+
+    /// Estimates cost of transcription of audio with given duration,
+    /// duration is rounded up to BILLING_INCREMENT.
+    pub fn estimate_cost(&self, duration: Duration, model: &str, enhanced: bool) -> f64 {
+        let rate = self.rate(model);
+        let per_minute = if enhanced {
+            rate.enhanced_per_minute
+        } else {
+            rate.standard_per_minute
+        };
+        let increments_per_minute = 60.0 / BILLING_INCREMENT.as_secs_f64();
+        billed_increments(duration) as f64 * per_minute / increments_per_minute
+    }
+}
+
+/// Estimates cost (USD) of transcription of audio with given duration by given model
+/// using default pricing table (see module docs), duration is rounded up to BILLING_INCREMENT.
+pub fn estimate_cost(duration: Duration, model: &str, enhanced: bool) -> f64 {
+    PricingTable::default().estimate_cost(duration, model, enhanced)
+}
+
+/// Returns number of billing increments of audio with given duration.
+pub fn billed_increments(duration: Duration) -> u64 {
+    let increment = BILLING_INCREMENT.as_nanos();
+    duration.as_nanos().div_ceil(increment) as u64
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_billed_increments
+    #[test]
+    fn test_billed_increments() {
+        assert_eq!(billed_increments(Duration::ZERO), 0);
+        assert_eq!(billed_increments(Duration::from_millis(1)), 1);
+        assert_eq!(billed_increments(Duration::from_secs(15)), 1);
+        assert_eq!(billed_increments(Duration::from_millis(15_001)), 2);
+    }
+
+    // cargo test -- --show-output test_estimate_cost
+    #[test]
+    fn test_estimate_cost() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        assert!(close(
+            estimate_cost(Duration::from_secs(60), "long", false),
+            0.016
+        ));
+        assert!(close(
+            estimate_cost(Duration::from_secs(60), "long", true),
+            0.016
+        ));
+        // 61 seconds are billed as 75 seconds
+        assert!(close(
+            estimate_cost(Duration::from_secs(61), "latest_long", false),
+            0.03
+        ));
+        assert!(close(
+            estimate_cost(Duration::from_secs(60), "phone_call", true),
+            0.036
+        ));
+
+        let pricing = PricingTable::default().with_model_rate("long", ModelRate::flat(0.02));
+        assert!(close(
+            pricing.estimate_cost(Duration::from_secs(30), "long", false),
+            0.01
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! Caching of synchronous recognition results so that identical audio is not
+//! transcribed (and billed) repeatedly, e.g. when the same clips are reprocessed
+//! in tests or retries. Caching is opt-in, see Recognizer::set_recognition_cache
+//! and Recognizer::recognize_bytes.
+//!
+//! Responses are keyed by hash of audio bytes, recognition config and recognizer name.
+//! Key is computed by std DefaultHasher, i.e. it is stable within one build but should
+//! not be persisted across Rust releases. Errors are never cached.
+use crate::api::grpc::google::cloud::speechtotext::v2::{RecognitionConfig, RecognizeResponse};
+use prost::Message;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Key of cached response, hash of (audio bytes, config, recognizer name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(pub u64);
+
+impl CacheKey {
+    /// Computes key of recognition of given audio with given config and recognizer.
+    pub fn new(audio: &[u8], config: &RecognitionConfig, recognizer: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        audio.hash(&mut hasher);
+        config.encode_to_vec().hash(&mut hasher);
+        recognizer.hash(&mut hasher);
+        CacheKey(hasher.finish())
+    }
+}
+
+/// Storage of recognition responses.
+pub trait RecognitionCache: Send + Sync + Debug {
+    /// Returns cached response for given key (if any).
+    fn get(&self, key: CacheKey) -> Option<RecognizeResponse>;
+
+    /// Stores response for given key.
+    fn put(&self, key: CacheKey, response: RecognizeResponse);
+}
+
+/// Number of cache hits and misses, see Recognizer::cache_stats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// requests answered from cache
+    pub hits: u64,
+    /// requests sent to Google
+    pub misses: u64,
+}
+
+/// Hit/miss counters shared by recognizer.
+#[derive(Debug, Default)]
+pub(crate) struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CacheCounters {
+    pub(crate) fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    // response and tick of its last use
+    entries: HashMap<CacheKey, (RecognizeResponse, u64)>,
+    tick: u64,
+}
+
+/// In-memory cache holding up to capacity responses, least recently
+/// used response is evicted when capacity is exceeded.
+#[derive(Debug)]
+pub struct LruRecognitionCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruRecognitionCache {
+    /// Creates cache holding up to capacity (at least 1) responses.
+    pub fn new(capacity: usize) -> Self {
+        LruRecognitionCache {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// Returns number of cached responses.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl RecognitionCache for LruRecognitionCache {
+    fn get(&self, key: CacheKey) -> Option<RecognizeResponse> {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.get_mut(&key).map(|(response, last_used)| {
+            *last_used = tick;
+            response.clone()
+        })
+    }
+
+    fn put(&self, key: CacheKey, response: RecognizeResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            let least_recently_used = state
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| *key);
+            if let Some(least_recently_used) = least_recently_used {
+                state.entries.remove(&least_recently_used);
+            }
+        }
+        state.entries.insert(key, (response, tick));
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_cache_key
+    #[test]
+    fn test_cache_key() {
+        let config = RecognitionConfig::default();
+        let key = CacheKey::new(&[1, 2, 3], &config, "r");
+        assert_eq!(key, CacheKey::new(&[1, 2, 3], &config, "r"));
+        assert_ne!(key, CacheKey::new(&[1, 2, 4], &config, "r"));
+        assert_ne!(key, CacheKey::new(&[1, 2, 3], &config, "other"));
+
+        let config = RecognitionConfig {
+            model: "long".to_string(),
+            ..Default::default()
+        };
+        assert_ne!(key, CacheKey::new(&[1, 2, 3], &config, "r"));
+    }
+
+    // cargo test -- --show-output test_lru_recognition_cache
+    #[test]
+    fn test_lru_recognition_cache() {
+        let cache = LruRecognitionCache::new(2);
+        assert!(cache.get(CacheKey(1)).is_none());
+        cache.put(CacheKey(1), RecognizeResponse::default());
+        cache.put(CacheKey(2), RecognizeResponse::default());
+        // key 1 becomes most recently used, key 2 is evicted
+        assert!(cache.get(CacheKey(1)).is_some());
+        cache.put(CacheKey(3), RecognizeResponse::default());
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(CacheKey(1)).is_some());
+        assert!(cache.get(CacheKey(2)).is_none());
+        assert!(cache.get(CacheKey(3)).is_some());
+    }
+}
@@ -0,0 +1,249 @@
+//! Buffering of streamed audio with checkpoint of already recognized audio.
+//! When streaming session must be re-established (e.g. after stream timeout
+//! or transient network error) only audio after last acknowledged
+//! result_end_offset is re-sent. This avoids re-billing of audio which was
+//! already processed by Google and duplicate transcripts.
+//!
+//! Buffer is unbounded by default. For long streams set resend_buffer_limit
+//! to cap memory usage. Oldest chunks beyond the limit are discarded even if
+//! not acknowledged yet, i.e. reconnect may lose some audio in such case.
+//!
+//! Recognizer does not reconnect by itself and does not use this buffer. It is meant
+//! for reconnect loops implemented by the caller: audio sent to the recognizer
+//! is pushed into the buffer, final responses acknowledge it and after failure
+//! unacknowledged audio is sent to newly created recognizer.
+use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognizeResponse;
+use crate::common::convert::to_std_duration;
+use crate::speechtotext::audio::{pcm_byte_len, pcm_duration};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Keeps audio chunks sent to the API which were not yet acknowledged
+/// by final recognition result. Audio must be raw PCM (e.g. LINEAR16) so that
+/// result offsets can be mapped to byte offsets.
+#[derive(Debug, Clone)]
+pub struct AudioCheckpointBuffer {
+    sample_rate: u32,
+    channels: u16,
+    bits_per_sample: u16,
+    /// not yet acknowledged chunks
+    chunks: VecDeque<Vec<u8>>,
+    /// absolute byte offset (from the very beginning of audio) of first buffered chunk
+    buffer_start: usize,
+    /// absolute byte offset of acknowledged audio
+    checkpoint: usize,
+    /// absolute byte offset where current streaming session started,
+    /// result offsets returned by API are relative to this offset
+    session_start: usize,
+    /// max number of bytes retained for resend, None means unbounded
+    resend_buffer_limit: Option<usize>,
+}
+
+impl AudioCheckpointBuffer {
+    /// Creates new empty buffer for audio with given PCM parameters.
+    pub fn new(sample_rate: u32, channels: u16, bits_per_sample: u16) -> Self {
+        AudioCheckpointBuffer {
+            sample_rate,
+            channels,
+            bits_per_sample,
+            chunks: VecDeque::new(),
+            buffer_start: 0,
+            checkpoint: 0,
+            session_start: 0,
+            resend_buffer_limit: None,
+        }
+    }
+
+    /// Caps number of bytes retained for resend. When limit is exceeded oldest chunks
+    /// are discarded, even if they were not acknowledged yet, i.e. reconnect
+    /// may lose some audio. Use pcm_byte_len to convert duration to bytes.
+    /// None (default) means unbounded buffer.
+    pub fn set_resend_buffer_limit(&mut self, resend_buffer_limit: Option<usize>) {
+        self.resend_buffer_limit = resend_buffer_limit;
+        self.enforce_limit();
+    }
+
+    /// Returns configured resend buffer limit (in bytes).
+    pub fn resend_buffer_limit(&self) -> Option<usize> {
+        self.resend_buffer_limit
+    }
+
+    /// Stores chunk of audio that is being sent to the API.
+    pub fn push(&mut self, chunk: Vec<u8>) {
+        self.chunks.push_back(chunk);
+        self.enforce_limit();
+    }
+
+    /// Processes response received from the API. End offset of final results
+    /// moves checkpoint forward and chunks which are completely before the checkpoint
+    /// are discarded.
+    pub fn acknowledge(&mut self, response: &StreamingRecognizeResponse) {
+        for result in response.results.iter().filter(|result| result.is_final) {
+            if let Some(end_offset) = &result.result_end_offset {
+                let end_offset = self.session_start
+                    + pcm_byte_len(
+                        to_std_duration(end_offset),
+                        self.sample_rate,
+                        self.channels,
+                        self.bits_per_sample,
+                    );
+                if end_offset > self.checkpoint {
+                    self.checkpoint = end_offset;
+                }
+            }
+        }
+        self.discard_acknowledged();
+    }
+
+    /// Returns offset (from the very beginning of audio, i.e. across reconnects)
+    /// up to which audio was acknowledged by final recognition results.
+    pub fn checkpoint_offset(&self) -> Duration {
+        pcm_duration(
+            self.checkpoint,
+            self.sample_rate,
+            self.channels,
+            self.bits_per_sample,
+        )
+    }
+
+    /// Returns number of buffered (not yet acknowledged) bytes.
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffer_end() - self.checkpoint.clamp(self.buffer_start, self.buffer_end())
+    }
+
+    /// To be called when new streaming session is established. Returns audio following
+    /// the checkpoint which must be re-sent to the new session. Result offsets of new session
+    /// are treated as relative to the checkpoint from now on.
+    pub fn reconnect(&mut self) -> Vec<Vec<u8>> {
+        self.discard_acknowledged();
+        // when unacknowledged audio was discarded due to resend_buffer_limit
+        // new session starts with oldest retained chunk
+        self.session_start = self.checkpoint.clamp(self.buffer_start, self.buffer_end());
+
+        let mut skip = self.session_start - self.buffer_start;
+        self.chunks
+            .iter()
+            .filter_map(|chunk| {
+                if skip >= chunk.len() {
+                    skip -= chunk.len();
+                    None
+                } else {
+                    let remaining = chunk[skip..].to_vec();
+                    skip = 0;
+                    Some(remaining)
+                }
+            })
+            .collect()
+    }
+
+    fn buffer_end(&self) -> usize {
+        self.buffer_start + self.chunks.iter().map(Vec::len).sum::<usize>()
+    }
+
+    fn enforce_limit(&mut self) {
+        if let Some(limit) = self.resend_buffer_limit {
+            let mut retained = self.buffer_end() - self.buffer_start;
+            while retained > limit {
+                match self.chunks.pop_front() {
+                    Some(chunk) => {
+                        self.buffer_start += chunk.len();
+                        retained -= chunk.len();
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn discard_acknowledged(&mut self) {
+        while let Some(chunk) = self.chunks.front() {
+            if self.buffer_start + chunk.len() > self.checkpoint {
+                break;
+            }
+            self.buffer_start += chunk.len();
+            self.chunks.pop_front();
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognitionResult;
+
+    #[allow(dead_code)]
+    fn final_response(end_offset_millis: i32) -> StreamingRecognizeResponse {
+        StreamingRecognizeResponse {
+            results: vec![StreamingRecognitionResult {
+                is_final: true,
+                result_end_offset: Some(prost_types::Duration {
+                    seconds: 0,
+                    nanos: end_offset_millis * 1_000_000,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_reconnect_mid_buffer
+    #[test]
+    fn test_reconnect_mid_buffer() {
+        // 16kHz mono LINEAR16, i.e. 32 bytes per millisecond
+        let mut buffer = AudioCheckpointBuffer::new(16000, 1, 16);
+        // 4 chunks, 100ms each
+        for i in 0..4u8 {
+            buffer.push(vec![i; 3200]);
+        }
+
+        // final result up to 150ms, first chunk discarded, second one is half acknowledged
+        buffer.acknowledge(&final_response(150));
+        assert_eq!(buffer.checkpoint_offset(), Duration::from_millis(150));
+        assert_eq!(buffer.buffered_bytes(), 8000);
+
+        // stream is broken, only audio after 150ms is re-sent
+        let replay = buffer.reconnect();
+        assert_eq!(replay.len(), 3);
+        assert_eq!(replay[0], vec![1u8; 1600]);
+        assert_eq!(replay[1], vec![2u8; 3200]);
+        assert_eq!(replay[2], vec![3u8; 3200]);
+
+        // offsets of new session are relative to checkpoint
+        buffer.acknowledge(&final_response(200));
+        assert_eq!(buffer.checkpoint_offset(), Duration::from_millis(350));
+        assert_eq!(buffer.buffered_bytes(), 1600);
+        assert_eq!(buffer.reconnect(), vec![vec![3u8; 1600]]);
+
+        // non final results do not move checkpoint
+        let mut interim = final_response(400);
+        interim.results[0].is_final = false;
+        buffer.acknowledge(&interim);
+        assert_eq!(buffer.checkpoint_offset(), Duration::from_millis(350));
+    }
+
+    // cargo test -- --show-output test_resend_buffer_limit
+    #[test]
+    fn test_resend_buffer_limit() {
+        let mut buffer = AudioCheckpointBuffer::new(16000, 1, 16);
+        buffer.set_resend_buffer_limit(Some(pcm_byte_len(
+            Duration::from_millis(200),
+            16000,
+            1,
+            16,
+        )));
+        for i in 0..4u8 {
+            buffer.push(vec![i; 3200]);
+        }
+        assert_eq!(buffer.buffered_bytes(), 6400);
+
+        // first two chunks were dropped although not acknowledged (audio lost)
+        let replay = buffer.reconnect();
+        assert_eq!(replay, vec![vec![2u8; 3200], vec![3u8; 3200]]);
+
+        // offsets of new session are relative to oldest retained chunk
+        buffer.acknowledge(&final_response(100));
+        assert_eq!(buffer.checkpoint_offset(), Duration::from_millis(300));
+        assert_eq!(buffer.reconnect(), vec![vec![3u8; 3200]]);
+    }
+}
@@ -0,0 +1,161 @@
+//! Streaming of the same audio into two recognizers (e.g. latest_long vs telephony model)
+//! for model selection experiments. Final results of both recognizers are paired by their
+//! index, i.e. n-th final response of recognizer A is paired with n-th final response of
+//! recognizer B. Responses with interim results only are not paired (and not returned).
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    StreamingRecognizeRequest, StreamingRecognizeResponse,
+};
+use crate::errors::{Error, Result};
+use crate::speechtotext::recognizer_v2::Recognizer;
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+
+/// Final responses of both recognizers with the same index. Response is None
+/// if respective recognizer returned fewer final responses than the other one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultPair {
+    /// index of final response (starting from 0)
+    pub index: usize,
+    /// response of recognizer A
+    pub a: Option<StreamingRecognizeResponse>,
+    /// response of recognizer B
+    pub b: Option<StreamingRecognizeResponse>,
+}
+
+/// Fans audio stream into two streaming recognizers.
+#[derive(Debug)]
+pub struct DualRecognizer {
+    recognizer_a: Recognizer,
+    recognizer_b: Recognizer,
+    audio_sender: Option<mpsc::Sender<StreamingRecognizeRequest>>,
+    audio_receiver: mpsc::Receiver<StreamingRecognizeRequest>,
+}
+
+impl DualRecognizer {
+    /// Creates dual recognizer from two streaming recognizers (streaming config must
+    /// be already sent). Audio sinks of both recognizers are taken, audio must be sent
+    /// via audio sink of dual recognizer instead.
+    pub fn new(mut recognizer_a: Recognizer, mut recognizer_b: Recognizer) -> Result<Self> {
+        match (recognizer_a.get_audio_sink(), recognizer_b.get_audio_sink()) {
+            (Some(_), Some(_)) => {}
+            _ => {
+                return Err(Error::new(
+                    "DualRecognizer: audio sink of both streaming recognizers must be available"
+                        .to_string(),
+                ))
+            }
+        }
+        let (audio_sender, audio_receiver) = mpsc::channel(1000);
+        Ok(DualRecognizer {
+            recognizer_a,
+            recognizer_b,
+            audio_sender: Some(audio_sender),
+            audio_receiver,
+        })
+    }
+
+    /// Returns sender used to stream in audio (see Recognizer::streaming_request_from_bytes).
+    /// Sender is taken out, once dropped audio streams of both recognizers are closed.
+    pub fn take_audio_sink(&mut self) -> Option<mpsc::Sender<StreamingRecognizeRequest>> {
+        self.audio_sender.take()
+    }
+
+    /// Streams audio into both recognizers and sends paired final responses into
+    /// result_sender as soon as both responses with given index are available.
+    /// Unpaired responses are sent once both streams are finished.
+    /// Returns first error returned by either of recognizers.
+    pub async fn streaming_recognize(self, result_sender: mpsc::Sender<ResultPair>) -> Result<()> {
+        let DualRecognizer {
+            mut recognizer_a,
+            mut recognizer_b,
+            audio_sender,
+            mut audio_receiver,
+        } = self;
+        drop(audio_sender);
+
+        let sinks: Vec<_> = vec![
+            recognizer_a.take_audio_sink(),
+            recognizer_b.take_audio_sink(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        tokio::spawn(async move {
+            while let Some(request) = audio_receiver.recv().await {
+                for sink in &sinks {
+                    // one of recognizers failing must not stop the other one
+                    let _ = sink.send(request.clone()).await;
+                }
+            }
+        });
+
+        let mut receiver_a = spawn_finals(recognizer_a);
+        let mut receiver_b = spawn_finals(recognizer_b);
+        let mut finals_a = VecDeque::new();
+        let mut finals_b = VecDeque::new();
+        let (mut done_a, mut done_b) = (false, false);
+        let mut index = 0;
+        let mut first_error = None;
+
+        while !done_a || !done_b {
+            tokio::select! {
+                response = receiver_a.recv(), if !done_a => match response {
+                    Some(Ok(response)) => finals_a.push_back(response),
+                    Some(Err(error)) => { first_error.get_or_insert(error); }
+                    None => done_a = true,
+                },
+                response = receiver_b.recv(), if !done_b => match response {
+                    Some(Ok(response)) => finals_b.push_back(response),
+                    Some(Err(error)) => { first_error.get_or_insert(error); }
+                    None => done_b = true,
+                },
+            }
+
+            while !finals_a.is_empty() && !finals_b.is_empty() {
+                let pair = ResultPair {
+                    index,
+                    a: finals_a.pop_front(),
+                    b: finals_b.pop_front(),
+                };
+                index += 1;
+                result_sender.send(pair).await?;
+            }
+        }
+
+        while !finals_a.is_empty() || !finals_b.is_empty() {
+            let pair = ResultPair {
+                index,
+                a: finals_a.pop_front(),
+                b: finals_b.pop_front(),
+            };
+            index += 1;
+            result_sender.send(pair).await?;
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Runs streaming recognition in separate task, returns receiver of responses
+/// containing final results.
+fn spawn_finals(mut recognizer: Recognizer) -> mpsc::Receiver<Result<StreamingRecognizeResponse>> {
+    let (sender, receiver) = mpsc::channel(100);
+    tokio::spawn(async move {
+        let stream = recognizer.streaming_recognize_async_stream().await;
+        let mut stream = Box::pin(stream);
+        while let Some(response) = stream.next().await {
+            let is_final = match &response {
+                Ok(response) => response.results.iter().any(|result| result.is_final),
+                Err(_) => true,
+            };
+            if is_final && sender.send(response).await.is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
@@ -0,0 +1,564 @@
+//! Helpers for building v2 speech-to-text recognition configuration.
+use crate::api::grpc::google::cloud::speechtotext::v1::{
+    recognition_config::AudioEncoding as V1AudioEncoding, RecognitionConfig as V1RecognitionConfig,
+};
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    explicit_decoding_config::AudioEncoding,
+    phrase_set::Phrase,
+    recognition_config::DecodingConfig,
+    recognition_features::MultiChannelMode as GrpcMultiChannelMode,
+    speech_adaptation::{adaptation_phrase_set, AdaptationPhraseSet},
+    AutoDetectDecodingConfig, ExplicitDecodingConfig, PhraseSet, RecognitionConfig,
+    RecognitionFeatures, SpeakerDiarizationConfig, SpeechAdaptation, StreamingRecognitionConfig,
+    StreamingRecognitionFeatures,
+};
+use crate::errors::{Error, Result};
+use crate::speechtotext::audio::parse_wav_header;
+use crate::speechtotext::locales;
+use log::*;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Typed counterpart of RecognitionFeatures.multi_channel_mode
+/// which is represented as raw i32 in GRPC structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiChannelMode {
+    /// Only the first channel is transcribed, other channels are ignored.
+    Unspecified,
+    /// Each channel in the provided audio is transcribed independently.
+    SeparateRecognitionPerChannel,
+}
+
+impl MultiChannelMode {
+    /// Returns i32 value expected by RecognitionFeatures.multi_channel_mode.
+    pub fn as_i32(&self) -> i32 {
+        match self {
+            MultiChannelMode::Unspecified => GrpcMultiChannelMode::Unspecified as i32,
+            MultiChannelMode::SeparateRecognitionPerChannel => {
+                GrpcMultiChannelMode::SeparateRecognitionPerChannel as i32
+            }
+        }
+    }
+
+    /// Converts raw i32 value into MultiChannelMode. Returns None for unknown values.
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match GrpcMultiChannelMode::from_i32(value) {
+            Some(GrpcMultiChannelMode::Unspecified) => Some(MultiChannelMode::Unspecified),
+            Some(GrpcMultiChannelMode::SeparateRecognitionPerChannel) => {
+                Some(MultiChannelMode::SeparateRecognitionPerChannel)
+            }
+            None => None,
+        }
+    }
+}
+
+/// Builder of v2 RecognitionConfig. Spares caller from filling
+/// all the GRPC struct attributes (and their raw i32 enum values) manually.
+#[derive(Debug, Clone, Default)]
+pub struct RecognitionConfigBuilder {
+    config: RecognitionConfig,
+}
+
+impl RecognitionConfigBuilder {
+    /// Creates new builder with empty configuration.
+    pub fn new() -> Self {
+        RecognitionConfigBuilder::default()
+    }
+
+    /// Sets recognition model, e.g. latest_long, telephony, chirp_2.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.config.model = model.into();
+        self
+    }
+
+    /// Adds language code (BCP-47, e.g. en-US) to the list of language codes.
+    /// Logs warning if language code is not known to be supported (see locales::is_supported).
+    pub fn language_code(mut self, language_code: impl Into<String>) -> Self {
+        let language_code = language_code.into();
+        if !locales::is_supported(&language_code) {
+            warn!(
+                "RecognitionConfigBuilder: language code '{}' is not known to be supported",
+                language_code
+            );
+        }
+        self.config.language_codes.push(language_code);
+        self
+    }
+
+    /// Sets decoding config (explicit or auto-detect).
+    pub fn decoding_config(mut self, decoding_config: DecodingConfig) -> Self {
+        self.config.decoding_config = Some(decoding_config);
+        self
+    }
+
+    /// Configures recognition of OGG_OPUS audio (Opus frames in Ogg container, e.g. encoded
+    /// on mobile device), i.e. sets auto-detect decoding config (Ogg headers carry sample
+    /// rate and channel count). When streaming, audio must be sent as continuous Ogg
+    /// byte stream starting with the first page (OpusHead header, see
+    /// audio::validate_ogg_opus_header), chunks may be split at any byte but must not be
+    /// dropped or reordered. Raw Opus packets without Ogg container are not supported.
+    /// See Recognizer::pump_ogg_opus_from_reader.
+    pub fn ogg_opus(self) -> Self {
+        self.decoding_config(DecodingConfig::AutoDecodingConfig(
+            AutoDetectDecodingConfig {},
+        ))
+    }
+
+    /// Sets how multi-channel audio is recognized.
+    pub fn multi_channel(mut self, mode: MultiChannelMode) -> Self {
+        self.features_mut().multi_channel_mode = mode.as_i32();
+        self
+    }
+
+    /// Enables word level time offsets (see words::WordTiming).
+    pub fn enable_word_time_offsets(mut self, enable: bool) -> Self {
+        self.features_mut().enable_word_time_offsets = enable;
+        self
+    }
+
+    /// Enables word level confidence (see words::WordTiming).
+    pub fn enable_word_confidence(mut self, enable: bool) -> Self {
+        self.features_mut().enable_word_confidence = enable;
+        self
+    }
+
+    /// Returns built RecognitionConfig.
+    pub fn build(self) -> RecognitionConfig {
+        self.config
+    }
+
+    fn features_mut(&mut self) -> &mut RecognitionFeatures {
+        self.config
+            .features
+            .get_or_insert_with(RecognitionFeatures::default)
+    }
+}
+
+/// Builder of v2 StreamingRecognitionConfig.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingConfigBuilder {
+    config: StreamingRecognitionConfig,
+}
+
+impl StreamingConfigBuilder {
+    /// Creates new builder with given recognition config (see RecognitionConfigBuilder).
+    pub fn new(config: RecognitionConfig) -> Self {
+        StreamingConfigBuilder {
+            config: StreamingRecognitionConfig {
+                config: Some(config),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Enables/disables streaming of interim (non-final) results. When disabled only final
+    /// results are returned and recognizer drops interim results received anyway.
+    /// Voice activity events (see enable_voice_activity_events) are not affected, i.e. they
+    /// are returned (as responses without results) regardless of this setting.
+    pub fn interim_results(mut self, enable: bool) -> Self {
+        self.features_mut().interim_results = enable;
+        self
+    }
+
+    /// Enables responses with voice activity speech events (SPEECH_ACTIVITY_BEGIN/END).
+    pub fn enable_voice_activity_events(mut self, enable: bool) -> Self {
+        self.features_mut().enable_voice_activity_events = enable;
+        self
+    }
+
+    /// Returns built StreamingRecognitionConfig.
+    pub fn build(self) -> StreamingRecognitionConfig {
+        self.config
+    }
+
+    fn features_mut(&mut self) -> &mut StreamingRecognitionFeatures {
+        self.config
+            .streaming_features
+            .get_or_insert_with(StreamingRecognitionFeatures::default)
+    }
+}
+
+/// Validates RecognitionConfig before it is sent to the API. Catches misconfigurations
+/// which API does not reject but which lead to unexpected results:
+/// * separate recognition per channel requested for explicitly single channel audio
+///   (code CHANNEL_COUNT_CONFLICT)
+pub fn validate(config: &RecognitionConfig) -> Result<()> {
+    let multi_channel_mode = config
+        .features
+        .as_ref()
+        .and_then(|features| MultiChannelMode::from_i32(features.multi_channel_mode));
+
+    if let (
+        Some(MultiChannelMode::SeparateRecognitionPerChannel),
+        Some(DecodingConfig::ExplicitDecodingConfig(explicit)),
+    ) = (multi_channel_mode, &config.decoding_config)
+    {
+        if explicit.audio_channel_count <= 1 {
+            return Err(Error::new_with_code(
+                format!(
+                    "Separate recognition per channel requires audio_channel_count > 1, got {}",
+                    explicit.audio_channel_count
+                ),
+                "CHANNEL_COUNT_CONFLICT".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts v1 RecognitionConfig into v2 RecognitionConfig. Returns converted config
+/// together with warnings describing v1 fields which have no v2 equivalent (and were dropped)
+/// or need manual review. Mapping:
+/// * encoding, sample_rate_hertz, audio_channel_count -> explicit decoding config
+///   (auto-detect decoding config if encoding is not specified or not supported by v2)
+/// * language_code -> language_codes, model -> model
+/// * enable_separate_recognition_per_channel -> features.multi_channel_mode
+/// * max_alternatives, profanity_filter, enable_word_time_offsets,
+///   enable_automatic_punctuation, diarization_config -> features
+/// * speech_contexts -> adaptation with inline phrase sets
+///
+/// Fields without v2 equivalent: metadata, use_enhanced.
+pub fn v1_to_v2(old: V1RecognitionConfig) -> (RecognitionConfig, Vec<String>) {
+    let mut warnings = vec![];
+
+    let encoding = match V1AudioEncoding::from_i32(old.encoding) {
+        Some(V1AudioEncoding::Linear16) => Some(AudioEncoding::Linear16),
+        Some(V1AudioEncoding::Flac) => Some(AudioEncoding::Flac),
+        Some(V1AudioEncoding::Mulaw) => Some(AudioEncoding::Mulaw),
+        Some(V1AudioEncoding::Amr) => Some(AudioEncoding::Amr),
+        Some(V1AudioEncoding::AmrWb) => Some(AudioEncoding::AmrWb),
+        Some(V1AudioEncoding::OggOpus) => Some(AudioEncoding::OggOpus),
+        Some(V1AudioEncoding::SpeexWithHeaderByte) => {
+            warnings.push(
+                "encoding SPEEX_WITH_HEADER_BYTE is not supported by v2, auto-detect decoding is used"
+                    .to_string(),
+            );
+            None
+        }
+        Some(V1AudioEncoding::EncodingUnspecified) | None => None,
+    };
+
+    let decoding_config = match encoding {
+        Some(encoding) => DecodingConfig::ExplicitDecodingConfig(ExplicitDecodingConfig {
+            encoding: encoding as i32,
+            sample_rate_hertz: old.sample_rate_hertz,
+            audio_channel_count: old.audio_channel_count,
+        }),
+        None => DecodingConfig::AutoDecodingConfig(AutoDetectDecodingConfig::default()),
+    };
+
+    let multi_channel_mode = if old.enable_separate_recognition_per_channel {
+        MultiChannelMode::SeparateRecognitionPerChannel
+    } else {
+        MultiChannelMode::Unspecified
+    };
+
+    let diarization_config = match old.diarization_config {
+        Some(diarization) if diarization.enable_speaker_diarization => {
+            Some(SpeakerDiarizationConfig {
+                min_speaker_count: diarization.min_speaker_count,
+                max_speaker_count: diarization.max_speaker_count,
+            })
+        }
+        _ => None,
+    };
+
+    let phrase_sets: Vec<AdaptationPhraseSet> = old
+        .speech_contexts
+        .into_iter()
+        .filter(|context| !context.phrases.is_empty())
+        .map(|context| AdaptationPhraseSet {
+            value: Some(adaptation_phrase_set::Value::InlinePhraseSet(PhraseSet {
+                phrases: context
+                    .phrases
+                    .into_iter()
+                    .map(|value| Phrase { value, boost: 0.0 })
+                    .collect(),
+                ..Default::default()
+            })),
+        })
+        .collect();
+
+    if old.metadata.is_some() {
+        warnings.push("metadata has no v2 equivalent".to_string());
+    }
+    if old.use_enhanced {
+        warnings.push("use_enhanced has no v2 equivalent, choose v2 model instead".to_string());
+    }
+    if old.model.is_empty() {
+        warnings.push("model is not set, v2 requires model (e.g. latest_long)".to_string());
+    }
+
+    let config = RecognitionConfig {
+        model: old.model,
+        language_codes: if old.language_code.is_empty() {
+            vec![]
+        } else {
+            vec![old.language_code]
+        },
+        features: Some(RecognitionFeatures {
+            profanity_filter: old.profanity_filter,
+            enable_word_time_offsets: old.enable_word_time_offsets,
+            enable_automatic_punctuation: old.enable_automatic_punctuation,
+            multi_channel_mode: multi_channel_mode.as_i32(),
+            diarization_config,
+            max_alternatives: old.max_alternatives,
+            ..Default::default()
+        }),
+        adaptation: if phrase_sets.is_empty() {
+            None
+        } else {
+            Some(SpeechAdaptation {
+                phrase_sets,
+                custom_classes: vec![],
+            })
+        },
+        decoding_config: Some(decoding_config),
+        ..Default::default()
+    };
+
+    (config, warnings)
+}
+
+/// Decoding config of typical telephony audio: 8kHz mono MULAW (G.711 u-law).
+pub fn telephony_mulaw() -> ExplicitDecodingConfig {
+    ExplicitDecodingConfig {
+        encoding: AudioEncoding::Mulaw as i32,
+        sample_rate_hertz: 8000,
+        audio_channel_count: 1,
+    }
+}
+
+/// Decoding config of telephony audio in raw PCM: 8kHz mono LINEAR16.
+pub fn telephony_linear16() -> ExplicitDecodingConfig {
+    ExplicitDecodingConfig {
+        encoding: AudioEncoding::Linear16 as i32,
+        sample_rate_hertz: 8000,
+        audio_channel_count: 1,
+    }
+}
+
+/// Decoding config of CD quality audio: 44.1kHz stereo LINEAR16.
+/// Note that stereo audio is transcribed per channel only if
+/// multi channel mode is SeparateRecognitionPerChannel.
+pub fn cd_quality_linear16() -> ExplicitDecodingConfig {
+    ExplicitDecodingConfig {
+        encoding: AudioEncoding::Linear16 as i32,
+        sample_rate_hertz: 44100,
+        audio_channel_count: 2,
+    }
+}
+
+/// Number of bytes read from the beginning of WAV file when looking for fmt chunk.
+const WAV_HEADER_READ_LEN: u64 = 64 * 1024;
+
+/// Creates recognition config with decoding config matching audio file extension
+/// (case insensitive) and given language code:
+/// * .wav: WAV header is parsed, only 16-bit PCM, A-law and u-law are accepted
+/// * .flac, .ogg, .opus (OGG_OPUS), .mp3
+///
+/// Auto-detect decoding config is used for all formats since Google reads the same
+/// headers (explicit decoding config is meant for headerless audio only).
+/// Returns error with code UNSUPPORTED_FORMAT for other extensions or unsupported WAV files.
+pub fn config_for_file(path: impl AsRef<Path>, language: &str) -> Result<RecognitionConfig> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let unsupported = |reason: String| {
+        Err(Error::new_with_code(
+            format!("Unsupported audio file {}: {}", path.display(), reason),
+            "UNSUPPORTED_FORMAT".to_string(),
+        ))
+    };
+
+    match extension.as_str() {
+        "wav" => {
+            let mut header = vec![];
+            File::open(path)?
+                .take(WAV_HEADER_READ_LEN)
+                .read_to_end(&mut header)?;
+            let format = parse_wav_header(&header)?;
+            if format.encoding().is_none() {
+                return unsupported(format!(
+                    "WAV format tag {} with {} bits per sample (16-bit PCM, A-law or u-law expected)",
+                    format.format_tag, format.bits_per_sample
+                ));
+            }
+        }
+        "flac" | "ogg" | "opus" | "mp3" => {}
+        _ => return unsupported(format!("unknown extension '{}'", extension)),
+    }
+
+    Ok(RecognitionConfigBuilder::new()
+        .language_code(language)
+        .decoding_config(DecodingConfig::AutoDecodingConfig(
+            AutoDetectDecodingConfig {},
+        ))
+        .build())
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_multi_channel_mode
+    #[test]
+    fn test_multi_channel_mode() {
+        assert_eq!(MultiChannelMode::Unspecified.as_i32(), 0);
+        assert_eq!(MultiChannelMode::SeparateRecognitionPerChannel.as_i32(), 1);
+        assert_eq!(
+            MultiChannelMode::from_i32(1),
+            Some(MultiChannelMode::SeparateRecognitionPerChannel)
+        );
+        assert_eq!(MultiChannelMode::from_i32(2), None);
+
+        let config = RecognitionConfigBuilder::new()
+            .language_code("en-US")
+            .multi_channel(MultiChannelMode::SeparateRecognitionPerChannel)
+            .build();
+        assert_eq!(config.features.unwrap().multi_channel_mode, 1);
+    }
+
+    // cargo test -- --show-output test_streaming_config_builder
+    #[test]
+    fn test_streaming_config_builder() {
+        let config = StreamingConfigBuilder::new(
+            RecognitionConfigBuilder::new()
+                .language_code("en-US")
+                .build(),
+        )
+        .interim_results(false)
+        .enable_voice_activity_events(true)
+        .build();
+        assert_eq!(config.config.unwrap().language_codes, vec!["en-US"]);
+        let features = config.streaming_features.unwrap();
+        assert!(!features.interim_results);
+        assert!(features.enable_voice_activity_events);
+    }
+
+    // cargo test -- --show-output test_validate_channel_count
+    #[test]
+    fn test_validate_channel_count() {
+        use crate::api::grpc::google::cloud::speechtotext::v2::ExplicitDecodingConfig;
+
+        let explicit = |audio_channel_count| {
+            DecodingConfig::ExplicitDecodingConfig(ExplicitDecodingConfig {
+                encoding: 1,
+                sample_rate_hertz: 8000,
+                audio_channel_count,
+            })
+        };
+
+        let config = RecognitionConfigBuilder::new()
+            .decoding_config(explicit(1))
+            .multi_channel(MultiChannelMode::SeparateRecognitionPerChannel)
+            .build();
+        let error = validate(&config).unwrap_err();
+        assert_eq!(error.code, Some("CHANNEL_COUNT_CONFLICT".to_string()));
+
+        let config = RecognitionConfigBuilder::new()
+            .decoding_config(explicit(2))
+            .multi_channel(MultiChannelMode::SeparateRecognitionPerChannel)
+            .build();
+        assert!(validate(&config).is_ok());
+
+        let config = RecognitionConfigBuilder::new()
+            .decoding_config(explicit(1))
+            .build();
+        assert!(validate(&config).is_ok());
+    }
+
+    // cargo test -- --show-output test_v1_to_v2
+    #[test]
+    fn test_v1_to_v2() {
+        use crate::api::grpc::google::cloud::speechtotext::v1::SpeechContext;
+
+        let old = V1RecognitionConfig {
+            encoding: V1AudioEncoding::Linear16 as i32,
+            sample_rate_hertz: 8000,
+            audio_channel_count: 2,
+            enable_separate_recognition_per_channel: true,
+            language_code: "en-US".to_string(),
+            max_alternatives: 2,
+            speech_contexts: vec![SpeechContext {
+                phrases: vec!["hello".to_string()],
+            }],
+            use_enhanced: true,
+            model: "latest_long".to_string(),
+            ..Default::default()
+        };
+
+        let (config, warnings) = v1_to_v2(old);
+        assert_eq!(config.language_codes, vec!["en-US".to_string()]);
+        assert_eq!(
+            config.decoding_config,
+            Some(DecodingConfig::ExplicitDecodingConfig(
+                ExplicitDecodingConfig {
+                    encoding: AudioEncoding::Linear16 as i32,
+                    sample_rate_hertz: 8000,
+                    audio_channel_count: 2,
+                }
+            ))
+        );
+        let features = config.features.unwrap();
+        assert_eq!(features.multi_channel_mode, 1);
+        assert_eq!(features.max_alternatives, 2);
+        assert_eq!(config.adaptation.unwrap().phrase_sets.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("use_enhanced"));
+    }
+
+    // cargo test -- --show-output test_decoding_presets
+    #[test]
+    fn test_decoding_presets() {
+        assert_eq!(telephony_mulaw().encoding, AudioEncoding::Mulaw as i32);
+        assert_eq!(telephony_linear16().sample_rate_hertz, 8000);
+
+        let per_channel = |decoding_config: ExplicitDecodingConfig| {
+            RecognitionConfigBuilder::new()
+                .decoding_config(DecodingConfig::ExplicitDecodingConfig(decoding_config))
+                .multi_channel(MultiChannelMode::SeparateRecognitionPerChannel)
+                .build()
+        };
+        assert!(validate(&per_channel(cd_quality_linear16())).is_ok());
+        assert!(validate(&per_channel(telephony_mulaw())).is_err());
+    }
+
+    // cargo test -- --show-output test_config_for_file
+    #[test]
+    fn test_config_for_file() {
+        let config = config_for_file("/tmp/recording.FLAC", "en-US").unwrap();
+        assert_eq!(config.language_codes, vec!["en-US"]);
+        assert_eq!(
+            config.decoding_config,
+            Some(DecodingConfig::AutoDecodingConfig(
+                AutoDetectDecodingConfig {}
+            ))
+        );
+        assert!(config_for_file("call.mp3", "en-US").is_ok());
+
+        let error = config_for_file("notes.txt", "en-US").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("UNSUPPORTED_FORMAT"));
+        assert!(config_for_file("no_extension", "en-US").is_err());
+
+        let path = std::env::temp_dir().join("config_for_file_test.wav");
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0".to_vec();
+        // 8kHz mono 32-bit float
+        wav.extend([3, 0, 1, 0, 0x40, 0x1f, 0, 0, 0, 0x7d, 0, 0, 4, 0, 32, 0]);
+        std::fs::write(&path, &wav).unwrap();
+        let error = config_for_file(&path, "en-US").unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("UNSUPPORTED_FORMAT"));
+
+        // 16-bit PCM
+        wav[20] = 1;
+        wav[34] = 16;
+        std::fs::write(&path, &wav).unwrap();
+        assert!(config_for_file(&path, "en-US").is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
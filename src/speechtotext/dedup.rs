@@ -0,0 +1,100 @@
+//! Cleanup of the seam between streams when stream is restarted. First final results
+//! of new stream may repeat words of the last final results of previous stream
+//! (audio replayed from checkpoint is recognized twice).
+//!
+//! Words are compared case insensitively and without punctuation. Overlap shorter
+//! than MIN_OVERLAP_WORDS is kept, since single repeated word (e.g. "the") is often
+//! genuine speech rather than replayed audio.
+
+/// Minimal number of words repeated at the boundary to be treated as overlap.
+pub const MIN_OVERLAP_WORDS: usize = 2;
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '\'')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Trims words at the beginning of new_finals (final transcripts of restarted stream)
+/// which repeat the end of prev_tail (end of transcript before restart). Overlap may span
+/// several new finals, finals which become empty are dropped. Returns new_finals unchanged
+/// if overlap is shorter than MIN_OVERLAP_WORDS.
+pub fn dedup_finals(prev_tail: &str, new_finals: &[String]) -> Vec<String> {
+    let tail: Vec<String> = prev_tail.split_whitespace().map(normalize).collect();
+    let new_words: Vec<String> = new_finals
+        .iter()
+        .flat_map(|text| text.split_whitespace())
+        .map(normalize)
+        .collect();
+
+    // longest suffix of tail equal to prefix of new words
+    let max_overlap = tail.len().min(new_words.len());
+    let overlap = (MIN_OVERLAP_WORDS..=max_overlap)
+        .rev()
+        .find(|len| tail[tail.len() - len..] == new_words[..*len])
+        .unwrap_or(0);
+    if overlap == 0 {
+        return new_finals.to_vec();
+    }
+
+    let mut to_skip = overlap;
+    let mut deduplicated = vec![];
+    for text in new_finals {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let skipped = to_skip.min(words.len());
+        to_skip -= skipped;
+        if skipped == 0 {
+            deduplicated.push(text.clone());
+        } else if skipped < words.len() {
+            deduplicated.push(words[skipped..].join(" "));
+        }
+    }
+    deduplicated
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn finals(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    // cargo test -- --show-output test_dedup_finals
+    #[test]
+    fn test_dedup_finals() {
+        // replayed audio recognized again, punctuation and case differ
+        assert_eq!(
+            dedup_finals(
+                "so I told him we should meet on Monday.",
+                &finals(&["meet on monday and discuss the budget"])
+            ),
+            finals(&["and discuss the budget"])
+        );
+
+        // overlap spans several finals
+        assert_eq!(
+            dedup_finals(
+                "please transfer the money to my savings account",
+                &finals(&["to my", "savings account", "today"])
+            ),
+            finals(&["today"])
+        );
+
+        // no overlap
+        assert_eq!(
+            dedup_finals("the weather is nice", &finals(&["let's go outside"])),
+            finals(&["let's go outside"])
+        );
+
+        // single repeated word is kept
+        assert_eq!(
+            dedup_finals("I need the", &finals(&["the report by Friday"])),
+            finals(&["the report by Friday"])
+        );
+
+        assert!(dedup_finals("", &[]).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+//! Evaluation of transcription quality, e.g. for gating model/config changes in CI.
+//! Error rates are computed as edit distance (substitutions + deletions + insertions)
+//! between normalized reference and hypothesis divided by length of the reference.
+
+/// Normalization applied to both reference and hypothesis before comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// compare case insensitively
+    pub ignore_case: bool,
+    /// remove punctuation (apostrophes inside words, e.g. don't, are kept)
+    pub ignore_punctuation: bool,
+}
+
+impl Default for Normalization {
+    /// Ignores both case and punctuation.
+    fn default() -> Self {
+        Normalization {
+            ignore_case: true,
+            ignore_punctuation: true,
+        }
+    }
+}
+
+impl Normalization {
+    /// Returns normalized words of text.
+    fn words(&self, text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| {
+                let word = if self.ignore_punctuation {
+                    word.chars()
+                        .filter(|c| !c.is_ascii_punctuation() || *c == '\'')
+                        .collect::<String>()
+                        .trim_matches('\'')
+                        .to_string()
+                } else {
+                    word.to_string()
+                };
+                if self.ignore_case {
+                    word.to_lowercase()
+                } else {
+                    word
+                }
+            })
+            .filter(|word| !word.is_empty())
+            .collect()
+    }
+}
+
+/// Returns Levenshtein distance of two sequences.
+fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    // distances[j] = distance of reference[..i] and hypothesis[..j]
+    let mut distances: Vec<usize> = (0..=hypothesis.len()).collect();
+    for i in 1..=reference.len() {
+        let mut diagonal = distances[0];
+        distances[0] = i;
+        for j in 1..=hypothesis.len() {
+            let substitution = diagonal + usize::from(reference[i - 1] != hypothesis[j - 1]);
+            diagonal = distances[j];
+            distances[j] = substitution.min(distances[j] + 1).min(distances[j - 1] + 1);
+        }
+    }
+    distances[hypothesis.len()]
+}
+
+/// Returns edit distance divided by reference length. Empty reference yields 0.0
+/// for empty hypothesis and 1.0 otherwise.
+fn error_rate<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> f64 {
+    match reference.len() {
+        0 if hypothesis.is_empty() => 0.0,
+        0 => 1.0,
+        len => edit_distance(reference, hypothesis) as f64 / len as f64,
+    }
+}
+
+/// Same as word_error_rate_with with default normalization (case and punctuation ignored).
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    word_error_rate_with(reference, hypothesis, Normalization::default())
+}
+
+/// Returns word error rate (WER) of hypothesis. Can exceed 1.0 if hypothesis
+/// contains more words than reference.
+pub fn word_error_rate_with(
+    reference: &str,
+    hypothesis: &str,
+    normalization: Normalization,
+) -> f64 {
+    error_rate(
+        &normalization.words(reference),
+        &normalization.words(hypothesis),
+    )
+}
+
+/// Same as character_error_rate_with with default normalization (case and punctuation ignored).
+pub fn character_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    character_error_rate_with(reference, hypothesis, Normalization::default())
+}
+
+/// Returns character error rate (CER) of hypothesis. Normalized words are joined by single
+/// space, i.e. differences in whitespace are ignored. Useful for languages without
+/// word delimiters (e.g. Chinese, Japanese).
+pub fn character_error_rate_with(
+    reference: &str,
+    hypothesis: &str,
+    normalization: Normalization,
+) -> f64 {
+    let chars = |text: &str| -> Vec<char> { normalization.words(text).join(" ").chars().collect() };
+    error_rate(&chars(reference), &chars(hypothesis))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_word_error_rate
+    #[test]
+    fn test_word_error_rate() {
+        assert_eq!(word_error_rate("hello world", "hello world"), 0.0);
+        assert_eq!(word_error_rate("Hello, world!", "hello world"), 0.0);
+        // one substitution out of 4 words
+        assert_eq!(
+            word_error_rate("the cat sat down", "the bat sat down"),
+            0.25
+        );
+        // one deletion and one insertion out of 3 words
+        assert!((word_error_rate("a b c", "a c d") - 2.0 / 3.0).abs() < 1e-9);
+        // more insertions than reference words
+        assert_eq!(word_error_rate("yes", "yes yes yes"), 2.0);
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "noise"), 1.0);
+        assert_eq!(word_error_rate("don't stop", "dont stop"), 0.5);
+
+        let strict = Normalization {
+            ignore_case: false,
+            ignore_punctuation: false,
+        };
+        assert_eq!(
+            word_error_rate_with("Hello world.", "hello world", strict),
+            1.0
+        );
+    }
+
+    // cargo test -- --show-output test_character_error_rate
+    #[test]
+    fn test_character_error_rate() {
+        assert_eq!(character_error_rate("kitten", "sitting"), 3.0 / 6.0);
+        assert_eq!(character_error_rate("Hello  World", "hello world"), 0.0);
+        assert_eq!(character_error_rate("你好世界", "你好世"), 0.25);
+    }
+}
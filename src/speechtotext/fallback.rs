@@ -0,0 +1,460 @@
+//! Synchronous recognition which falls back from v2 to v1 API when v2-only feature
+//! is not available (e.g. translation_config in some regions).
+//!
+//! Fallback is attempted only for InvalidArgument errors whose message mentions one
+//! of V2_ONLY_FEATURES. Request is then converted to v1 request and following
+//! v2 features are dropped (with warning):
+//! * translation_config, denoiser_config, transcript_normalization
+//! * adaptation (v1 uses different speech contexts)
+//! * features.enable_spoken_punctuation, features.enable_spoken_emojis,
+//!   features.enable_word_confidence, features.custom_prompt_config
+//! * all language_codes except the first one
+//! * config_mask (request config is used as is)
+//!
+//! v2 models long, short and telephony are mapped to v1 latest_long, latest_short
+//! and phone_call, other models (e.g. chirp) are replaced with v1 default model.
+//!
+//! recognize_with_model_fallbacks retries v2 request with other models when
+//! requested model is not available (e.g. temporarily or in given region).
+use crate::api::grpc::google::cloud::speechtotext::v1::{
+    recognition_audio, recognition_config::AudioEncoding as V1AudioEncoding,
+    RecognitionAudio as V1RecognitionAudio, RecognitionConfig as V1RecognitionConfig,
+    RecognizeRequest as V1RecognizeRequest, SpeakerDiarizationConfig as V1DiarizationConfig,
+};
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    explicit_decoding_config::AudioEncoding, recognition_config::DecodingConfig,
+    recognition_features::MultiChannelMode, recognize_request::AudioSource, RecognitionConfig,
+    RecognizeRequest, RecognizeResponse,
+};
+use crate::errors::{Error, ErrorKind, Result};
+use crate::speechtotext::auto::TranscriptSegment;
+use crate::speechtotext::recognizer::Recognizer as RecognizerV1;
+use crate::speechtotext::recognizer_v2::Recognizer;
+use log::*;
+
+/// Names of v2-only features. InvalidArgument error mentioning any of them
+/// (snake case or space separated) triggers fallback to v1.
+pub const V2_ONLY_FEATURES: &[&str] = &[
+    "translation_config",
+    "translation",
+    "denoiser_config",
+    "denoiser",
+    "transcript_normalization",
+    "custom_prompt_config",
+    "enable_spoken_punctuation",
+    "enable_spoken_emojis",
+];
+
+/// API which produced the transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechApi {
+    /// v2 API (request was processed as is)
+    V2,
+    /// v1 API (v2-only features were dropped)
+    V1,
+}
+
+/// Result of recognition with fallback.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackTranscript {
+    /// API which was used
+    pub api: SpeechApi,
+
+    /// final results in order returned by the API. For v1 results
+    /// result_end_offset is not available (zero) and language_code
+    /// is the one from request.
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// Performs synchronous recognition by v2 API. If v2 API rejects v2-only feature,
+/// request is retried by v1 API with equivalent config (see module docs).
+pub async fn recognize_with_fallback(
+    // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+    google_credentials: impl AsRef<str>,
+    request: RecognizeRequest,
+) -> Result<FallbackTranscript> {
+    let mut recognizer =
+        Recognizer::create_synchronous_recognizer(google_credentials.as_ref()).await?;
+    recognize_with_fallback_using(&mut recognizer, google_credentials, request).await
+}
+
+/// Same as recognize_with_fallback, v2 request is sent by provided recognizer.
+/// v1 recognizer is created from credentials only when fallback is needed.
+pub async fn recognize_with_fallback_using(
+    recognizer: &mut Recognizer,
+    // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+    google_credentials: impl AsRef<str>,
+    request: RecognizeRequest,
+) -> Result<FallbackTranscript> {
+    let error = match recognizer.recognize(request.clone()).await {
+        Ok(response) => {
+            return Ok(FallbackTranscript {
+                api: SpeechApi::V2,
+                segments: response
+                    .results
+                    .iter()
+                    .map(TranscriptSegment::from)
+                    .collect(),
+            })
+        }
+        Err(error) if is_unsupported_feature_error(&error) => error,
+        Err(error) => return Err(error),
+    };
+
+    warn!(
+        "v2 feature not available ({}), retrying with v1 API, v2-only features are dropped",
+        error.message
+    );
+    let language_code = request
+        .config
+        .as_ref()
+        .and_then(|config| config.language_codes.first().cloned())
+        .unwrap_or_default();
+    let v1_request = v1_request_from_v2(&request)?;
+    let mut v1_recognizer = RecognizerV1::create_synchronous_recognizer(google_credentials).await?;
+    let response = v1_recognizer.recognize(v1_request).await?;
+
+    Ok(FallbackTranscript {
+        api: SpeechApi::V1,
+        segments: response
+            .results
+            .iter()
+            .map(|result| TranscriptSegment {
+                text: result
+                    .alternatives
+                    .first()
+                    .map(|alternative| alternative.transcript.clone())
+                    .unwrap_or_default(),
+                result_end_offset: Default::default(),
+                channel_tag: result.channel_tag,
+                language_code: language_code.clone(),
+            })
+            .collect(),
+    })
+}
+
+/// Phrases which (together with word model) identify model availability errors.
+const MODEL_UNAVAILABLE_PHRASES: &[&str] = &[
+    "not available",
+    "unavailable",
+    "not supported",
+    "does not exist",
+    "not found",
+];
+
+/// Response of recognition with model fallbacks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFallbackResponse {
+    /// model which produced the response
+    pub model: String,
+
+    /// response of the API
+    pub response: RecognizeResponse,
+}
+
+/// Performs synchronous recognition by v2 API with model of the request. If the model
+/// is not available (see is_model_unavailable_error) request is retried with
+/// model_fallbacks in order. Error of the last attempted model is returned when
+/// none of the models is available. Request without config only overrides model
+/// of the Recognizer resource (config_mask model).
+pub async fn recognize_with_model_fallbacks(
+    recognizer: &mut Recognizer,
+    request: RecognizeRequest,
+    model_fallbacks: Vec<String>,
+) -> Result<ModelFallbackResponse> {
+    let primary_model = request
+        .config
+        .as_ref()
+        .map(|config| config.model.clone())
+        .unwrap_or_default();
+    let mut response = recognizer.recognize(request.clone()).await;
+    let mut model = primary_model;
+
+    for fallback_model in model_fallbacks {
+        match &response {
+            Err(error) if is_model_unavailable_error(error) => {
+                warn!(
+                    "model '{}' not available ({}), retrying with model '{}'",
+                    model, error.message, fallback_model
+                );
+            }
+            _ => break,
+        }
+        response = recognizer
+            .recognize(request_with_model(&request, &fallback_model))
+            .await;
+        model = fallback_model;
+    }
+
+    let response = response?;
+    info!("recognized with model '{}'", model);
+    Ok(ModelFallbackResponse { model, response })
+}
+
+/// Returns true if error is InvalidArgument or NOT_FOUND error reporting that requested
+/// model is not available (not supported in the location, does not exist, ...).
+pub fn is_model_unavailable_error(error: &Error) -> bool {
+    let not_found = error
+        .as_tonic_status()
+        .map(|status| status.code() == tonic::Code::NotFound)
+        .unwrap_or(false);
+    if error.kind() != ErrorKind::InvalidArgument && !not_found {
+        return false;
+    }
+    let message = error.message.to_lowercase();
+    message.contains("model")
+        && MODEL_UNAVAILABLE_PHRASES
+            .iter()
+            .any(|phrase| message.contains(phrase))
+}
+
+/// Returns copy of request with given model. When request has no config, config
+/// with model only is added and masked so that other fields of Recognizer resource
+/// config are kept.
+fn request_with_model(request: &RecognizeRequest, model: &str) -> RecognizeRequest {
+    let mut request = request.clone();
+    match &mut request.config {
+        Some(config) => {
+            config.model = model.to_string();
+            if let Some(config_mask) = &mut request.config_mask {
+                if !config_mask.paths.is_empty()
+                    && !config_mask.paths.iter().any(|path| path == "model")
+                {
+                    config_mask.paths.push("model".to_string());
+                }
+            }
+        }
+        None => {
+            request.config = Some(RecognitionConfig {
+                model: model.to_string(),
+                ..Default::default()
+            });
+            request.config_mask = Some(prost_types::FieldMask {
+                paths: vec!["model".to_string()],
+            });
+        }
+    }
+    request
+}
+
+/// Returns true if error is InvalidArgument mentioning one of V2_ONLY_FEATURES.
+pub fn is_unsupported_feature_error(error: &Error) -> bool {
+    if error.kind() != ErrorKind::InvalidArgument {
+        return false;
+    }
+    let message = error.message.to_lowercase().replace(' ', "_");
+    V2_ONLY_FEATURES
+        .iter()
+        .any(|feature| message.contains(feature))
+}
+
+/// Converts v2 request into equivalent v1 request, v2-only features are dropped
+/// (see module docs). Returns error with code FALLBACK_UNSUPPORTED if audio
+/// encoding is not supported by v1 API (ALAW, MP3, WEBM_OPUS).
+pub fn v1_request_from_v2(request: &RecognizeRequest) -> Result<V1RecognizeRequest> {
+    let audio_source = match &request.audio_source {
+        Some(AudioSource::Content(content)) => {
+            Some(recognition_audio::AudioSource::Content(content.clone()))
+        }
+        Some(AudioSource::Uri(uri)) => Some(recognition_audio::AudioSource::Uri(uri.clone())),
+        None => None,
+    };
+    Ok(V1RecognizeRequest {
+        config: Some(v1_config_from_v2(
+            request
+                .config
+                .as_ref()
+                .unwrap_or(&RecognitionConfig::default()),
+        )?),
+        audio: Some(V1RecognitionAudio { audio_source }),
+    })
+}
+
+/// Converts v2 recognition config into equivalent v1 config, v2-only features are
+/// dropped (see module docs).
+pub fn v1_config_from_v2(config: &RecognitionConfig) -> Result<V1RecognitionConfig> {
+    let mut v1_config = V1RecognitionConfig {
+        language_code: config.language_codes.first().cloned().unwrap_or_default(),
+        model: v1_model(&config.model).to_string(),
+        ..Default::default()
+    };
+
+    // auto decoding maps to unspecified encoding (v1 detects FLAC and WAV headers)
+    if let Some(DecodingConfig::ExplicitDecodingConfig(explicit)) = &config.decoding_config {
+        v1_config.encoding = v1_encoding(explicit.encoding)? as i32;
+        v1_config.sample_rate_hertz = explicit.sample_rate_hertz;
+        v1_config.audio_channel_count = explicit.audio_channel_count;
+    }
+
+    if let Some(features) = &config.features {
+        v1_config.profanity_filter = features.profanity_filter;
+        v1_config.enable_word_time_offsets = features.enable_word_time_offsets;
+        v1_config.enable_automatic_punctuation = features.enable_automatic_punctuation;
+        v1_config.max_alternatives = features.max_alternatives;
+        v1_config.enable_separate_recognition_per_channel =
+            features.multi_channel_mode == MultiChannelMode::SeparateRecognitionPerChannel as i32;
+        v1_config.diarization_config =
+            features
+                .diarization_config
+                .as_ref()
+                .map(|diarization| V1DiarizationConfig {
+                    enable_speaker_diarization: true,
+                    min_speaker_count: diarization.min_speaker_count,
+                    max_speaker_count: diarization.max_speaker_count,
+                    ..Default::default()
+                });
+    }
+
+    Ok(v1_config)
+}
+
+fn v1_model(model: &str) -> &str {
+    match model {
+        "long" | "latest_long" => "latest_long",
+        "short" | "latest_short" => "latest_short",
+        "telephony" => "phone_call",
+        _ => "",
+    }
+}
+
+fn v1_encoding(encoding: i32) -> Result<V1AudioEncoding> {
+    match AudioEncoding::from_i32(encoding) {
+        Some(AudioEncoding::Linear16) => Ok(V1AudioEncoding::Linear16),
+        Some(AudioEncoding::Mulaw) => Ok(V1AudioEncoding::Mulaw),
+        Some(AudioEncoding::Amr) => Ok(V1AudioEncoding::Amr),
+        Some(AudioEncoding::AmrWb) => Ok(V1AudioEncoding::AmrWb),
+        Some(AudioEncoding::Flac) => Ok(V1AudioEncoding::Flac),
+        Some(AudioEncoding::OggOpus) => Ok(V1AudioEncoding::OggOpus),
+        Some(AudioEncoding::Unspecified) | None => Ok(V1AudioEncoding::EncodingUnspecified),
+        Some(other) => Err(Error::new_with_code(
+            format!(
+                "Audio encoding {} is not supported by v1 API",
+                other.as_str_name()
+            ),
+            "FALLBACK_UNSUPPORTED".to_string(),
+        )),
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        ExplicitDecodingConfig, RecognitionFeatures, SpeakerDiarizationConfig, TranslationConfig,
+    };
+
+    // cargo test -- --show-output test_is_unsupported_feature_error
+    #[test]
+    fn test_is_unsupported_feature_error() {
+        let error: Error =
+            tonic::Status::invalid_argument("Translation config is not supported in region").into();
+        assert!(is_unsupported_feature_error(&error));
+
+        let error: Error = tonic::Status::invalid_argument("Invalid sample rate").into();
+        assert!(!is_unsupported_feature_error(&error));
+
+        let error: Error = tonic::Status::unavailable("translation_config").into();
+        assert!(!is_unsupported_feature_error(&error));
+    }
+
+    // cargo test -- --show-output test_is_model_unavailable_error
+    #[test]
+    fn test_is_model_unavailable_error() {
+        let error: Error = tonic::Status::invalid_argument(
+            "The model \"chirp_2\" is not supported in location \"global\"",
+        )
+        .into();
+        assert!(is_model_unavailable_error(&error));
+
+        let error: Error = tonic::Status::not_found("Model chirp_3 does not exist").into();
+        assert!(is_model_unavailable_error(&error));
+
+        let error: Error = tonic::Status::invalid_argument("Invalid sample rate").into();
+        assert!(!is_model_unavailable_error(&error));
+
+        let error: Error = tonic::Status::unavailable("model not available").into();
+        assert!(!is_model_unavailable_error(&error));
+    }
+
+    // cargo test -- --show-output test_request_with_model
+    #[test]
+    fn test_request_with_model() {
+        let request = request_with_model(&RecognizeRequest::default(), "long");
+        assert_eq!(request.config.unwrap().model, "long");
+        assert_eq!(request.config_mask.unwrap().paths, vec!["model"]);
+
+        let request = RecognizeRequest {
+            config: Some(RecognitionConfig {
+                model: "chirp".to_string(),
+                language_codes: vec!["en-US".to_string()],
+                ..Default::default()
+            }),
+            config_mask: Some(prost_types::FieldMask {
+                paths: vec!["language_codes".to_string()],
+            }),
+            ..Default::default()
+        };
+        let request = request_with_model(&request, "long");
+        let config = request.config.unwrap();
+        assert_eq!(config.model, "long");
+        assert_eq!(config.language_codes, vec!["en-US"]);
+        assert_eq!(
+            request.config_mask.unwrap().paths,
+            vec!["language_codes", "model"]
+        );
+    }
+
+    // cargo test -- --show-output test_v1_config_from_v2
+    #[test]
+    fn test_v1_config_from_v2() {
+        let config = RecognitionConfig {
+            model: "telephony".to_string(),
+            language_codes: vec!["en-US".to_string(), "de-DE".to_string()],
+            features: Some(RecognitionFeatures {
+                enable_automatic_punctuation: true,
+                enable_spoken_emojis: true,
+                multi_channel_mode: MultiChannelMode::SeparateRecognitionPerChannel as i32,
+                diarization_config: Some(SpeakerDiarizationConfig {
+                    min_speaker_count: 2,
+                    max_speaker_count: 3,
+                }),
+                ..Default::default()
+            }),
+            translation_config: Some(TranslationConfig {
+                target_language: "de-DE".to_string(),
+            }),
+            decoding_config: Some(DecodingConfig::ExplicitDecodingConfig(
+                ExplicitDecodingConfig {
+                    encoding: AudioEncoding::Linear16 as i32,
+                    sample_rate_hertz: 8000,
+                    audio_channel_count: 2,
+                },
+            )),
+            ..Default::default()
+        };
+        let v1_config = v1_config_from_v2(&config).unwrap();
+        assert_eq!(v1_config.language_code, "en-US");
+        assert_eq!(v1_config.model, "phone_call");
+        assert_eq!(v1_config.encoding, V1AudioEncoding::Linear16 as i32);
+        assert_eq!(v1_config.sample_rate_hertz, 8000);
+        assert_eq!(v1_config.audio_channel_count, 2);
+        assert!(v1_config.enable_automatic_punctuation);
+        assert!(v1_config.enable_separate_recognition_per_channel);
+        let diarization = v1_config.diarization_config.unwrap();
+        assert!(diarization.enable_speaker_diarization);
+        assert_eq!(diarization.max_speaker_count, 3);
+
+        let config = RecognitionConfig {
+            decoding_config: Some(DecodingConfig::ExplicitDecodingConfig(
+                ExplicitDecodingConfig {
+                    encoding: AudioEncoding::Mp3 as i32,
+                    ..Default::default()
+                },
+            )),
+            ..Default::default()
+        };
+        let error = v1_config_from_v2(&config).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("FALLBACK_UNSUPPORTED"));
+    }
+}
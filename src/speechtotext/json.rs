@@ -0,0 +1,177 @@
+//! One call transcription returning word aligned JSON document.
+//!
+//! Produced document has following schema:
+//! ```text
+//! {
+//!   "transcript": "hello world",   // top alternatives of all results joined by space
+//!   "words": [
+//!     {
+//!       "word": "hello",           // recognized word
+//!       "start": 0.1,              // start offset in seconds from the beginning of the audio
+//!       "end": 0.5,                // end offset in seconds from the beginning of the audio
+//!       "confidence": 0.92         // confidence (0.0 - 1.0), null if not provided by the API
+//!     }
+//!   ]
+//! }
+//! ```
+//! Words are listed in order of recognition results, only words of top
+//! alternatives are included.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    RecognitionConfig, RecognitionFeatures, SpeechRecognitionResult,
+};
+use crate::errors::{Error, Result};
+use crate::speechtotext::auto::batch_recognize_inline;
+use crate::speechtotext::recognizer_v2::Recognizer;
+use crate::speechtotext::words::extract_words;
+use serde_json::{json, Value};
+
+/// Transcribes file and returns word aligned JSON document (see module docs).
+/// Cloud Storage URI (gs://...) is transcribed by batch recognition, local file
+/// by synchronous recognition (Recognizer::transcribe_file, local file exceeding
+/// synchronous limits returns error with code AUDIO_TOO_LONG and must be uploaded
+/// to Cloud Storage first). Word time offsets and word confidence are enabled
+/// regardless of provided config. Default recognizer (`_`) of project from credentials
+/// in global location is used.
+pub async fn transcribe_to_json(
+    // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+    google_credentials: impl AsRef<str>,
+    // local path or Google Cloud Storage URI, e.g. gs://bucket/recording.wav
+    file: impl AsRef<str>,
+    config: RecognitionConfig,
+) -> Result<String> {
+    let google_credentials = google_credentials.as_ref();
+    let file = file.as_ref();
+    let recognizer_name = default_recognizer(google_credentials)?;
+    let config = with_word_details(config);
+
+    let results = if file.starts_with("gs://") {
+        let mut recognizer = Recognizer::create_asynchronous_recognizer(google_credentials).await?;
+        batch_recognize_inline(&mut recognizer, file.to_string(), recognizer_name, config).await?
+    } else {
+        let mut recognizer = Recognizer::create_synchronous_recognizer(google_credentials).await?;
+        recognizer
+            .transcribe_file(file, recognizer_name, config)
+            .await?
+            .results
+    };
+
+    transcript_json(&results)
+}
+
+/// Serializes recognition results into word aligned JSON document (see module docs).
+pub fn transcript_json(results: &[SpeechRecognitionResult]) -> Result<String> {
+    let mut transcripts = vec![];
+    let mut words = vec![];
+    for alternative in results
+        .iter()
+        .filter_map(|result| result.alternatives.first())
+    {
+        let transcript = alternative.transcript.trim();
+        if !transcript.is_empty() {
+            transcripts.push(transcript);
+        }
+        words.extend(extract_words(alternative).into_iter().map(|word| {
+            json!({
+                "word": word.word,
+                "start": word.start_offset.as_secs_f64(),
+                "end": word.end_offset.as_secs_f64(),
+                "confidence": word.confidence,
+            })
+        }));
+    }
+
+    Ok(serde_json::to_string_pretty(&json!({
+        "transcript": transcripts.join(" "),
+        "words": words,
+    }))?)
+}
+
+/// Returns default recognizer of project from credentials, i.e.
+/// projects/{project_id}/locations/global/recognizers/_
+fn default_recognizer(google_credentials: &str) -> Result<String> {
+    let credentials: Value = serde_json::from_str(google_credentials)?;
+    match credentials["project_id"].as_str() {
+        Some(project_id) if !project_id.is_empty() => Ok(format!(
+            "projects/{}/locations/global/recognizers/_",
+            project_id
+        )),
+        _ => Err(Error::new(
+            "Credentials do not contain project_id, recognizer cannot be determined".to_string(),
+        )),
+    }
+}
+
+fn with_word_details(mut config: RecognitionConfig) -> RecognitionConfig {
+    let features = config
+        .features
+        .get_or_insert_with(RecognitionFeatures::default);
+    features.enable_word_time_offsets = true;
+    features.enable_word_confidence = true;
+    config
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        SpeechRecognitionAlternative, WordInfo,
+    };
+
+    // cargo test -- --show-output test_transcript_json
+    #[test]
+    fn test_transcript_json() {
+        let word = |word: &str, start_millis: i32, end_millis: i32, confidence: f32| WordInfo {
+            word: word.to_string(),
+            start_offset: Some(prost_types::Duration {
+                seconds: 0,
+                nanos: start_millis * 1_000_000,
+            }),
+            end_offset: Some(prost_types::Duration {
+                seconds: 0,
+                nanos: end_millis * 1_000_000,
+            }),
+            confidence,
+            ..Default::default()
+        };
+        let results = vec![
+            SpeechRecognitionResult {
+                alternatives: vec![SpeechRecognitionAlternative {
+                    transcript: "hello ".to_string(),
+                    words: vec![word("hello", 100, 500, 0.5)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            SpeechRecognitionResult::default(),
+            SpeechRecognitionResult {
+                alternatives: vec![SpeechRecognitionAlternative {
+                    transcript: "world".to_string(),
+                    words: vec![word("world", 600, 900, 0.0)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ];
+
+        let document: Value = serde_json::from_str(&transcript_json(&results).unwrap()).unwrap();
+        assert_eq!(document["transcript"], "hello world");
+        assert_eq!(
+            document["words"],
+            json!([
+                {"word": "hello", "start": 0.1, "end": 0.5, "confidence": 0.5},
+                {"word": "world", "start": 0.6, "end": 0.9, "confidence": null},
+            ])
+        );
+    }
+
+    // cargo test -- --show-output test_default_recognizer
+    #[test]
+    fn test_default_recognizer() {
+        assert_eq!(
+            default_recognizer(r#"{"project_id": "my-project"}"#).unwrap(),
+            "projects/my-project/locations/global/recognizers/_"
+        );
+        assert!(default_recognizer("{}").is_err());
+    }
+}
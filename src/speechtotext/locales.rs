@@ -0,0 +1,176 @@
+//! Curated list of language codes supported by Speech-to-Text v2.
+//! The v2 API does not provide method listing supported languages, i.e. list below
+//! is maintained manually and may lag behind Google. Language support also differs
+//! per model and location, see https://cloud.google.com/speech-to-text/v2/docs/speech-to-text-supported-languages.
+//! Intended for catching typos early rather than for strict validation.
+
+/// BCP-47 language codes supported by at least one v2 model.
+pub const SUPPORTED_LANGUAGES: &[&str] = &[
+    "af-ZA",
+    "am-ET",
+    "ar-AE",
+    "ar-BH",
+    "ar-DZ",
+    "ar-EG",
+    "ar-IL",
+    "ar-IQ",
+    "ar-JO",
+    "ar-KW",
+    "ar-LB",
+    "ar-MA",
+    "ar-OM",
+    "ar-PS",
+    "ar-QA",
+    "ar-SA",
+    "ar-TN",
+    "ar-YE",
+    "az-AZ",
+    "bg-BG",
+    "bn-BD",
+    "bn-IN",
+    "bs-BA",
+    "ca-ES",
+    "cmn-Hans-CN",
+    "cmn-Hans-HK",
+    "cmn-Hant-TW",
+    "cs-CZ",
+    "da-DK",
+    "de-AT",
+    "de-CH",
+    "de-DE",
+    "el-GR",
+    "en-AU",
+    "en-CA",
+    "en-GB",
+    "en-GH",
+    "en-HK",
+    "en-IE",
+    "en-IN",
+    "en-KE",
+    "en-NG",
+    "en-NZ",
+    "en-PH",
+    "en-PK",
+    "en-SG",
+    "en-TZ",
+    "en-US",
+    "en-ZA",
+    "es-AR",
+    "es-BO",
+    "es-CL",
+    "es-CO",
+    "es-CR",
+    "es-DO",
+    "es-EC",
+    "es-ES",
+    "es-GT",
+    "es-HN",
+    "es-MX",
+    "es-NI",
+    "es-PA",
+    "es-PE",
+    "es-PR",
+    "es-PY",
+    "es-SV",
+    "es-US",
+    "es-UY",
+    "es-VE",
+    "et-EE",
+    "eu-ES",
+    "fa-IR",
+    "fi-FI",
+    "fil-PH",
+    "fr-BE",
+    "fr-CA",
+    "fr-CH",
+    "fr-FR",
+    "gl-ES",
+    "gu-IN",
+    "hi-IN",
+    "hr-HR",
+    "hu-HU",
+    "hy-AM",
+    "id-ID",
+    "is-IS",
+    "it-CH",
+    "it-IT",
+    "iw-IL",
+    "ja-JP",
+    "jv-ID",
+    "ka-GE",
+    "kk-KZ",
+    "km-KH",
+    "kn-IN",
+    "ko-KR",
+    "lo-LA",
+    "lt-LT",
+    "lv-LV",
+    "mk-MK",
+    "ml-IN",
+    "mn-MN",
+    "mr-IN",
+    "ms-MY",
+    "my-MM",
+    "ne-NP",
+    "nl-BE",
+    "nl-NL",
+    "no-NO",
+    "pa-Guru-IN",
+    "pl-PL",
+    "pt-BR",
+    "pt-PT",
+    "ro-RO",
+    "ru-RU",
+    "si-LK",
+    "sk-SK",
+    "sl-SI",
+    "sq-AL",
+    "sr-RS",
+    "su-ID",
+    "sv-SE",
+    "sw-KE",
+    "sw-TZ",
+    "ta-IN",
+    "ta-LK",
+    "ta-MY",
+    "ta-SG",
+    "te-IN",
+    "th-TH",
+    "tr-TR",
+    "uk-UA",
+    "ur-IN",
+    "ur-PK",
+    "uz-UZ",
+    "vi-VN",
+    "yue-Hant-HK",
+    "zu-ZA",
+];
+
+/// Special language code enabling automatic language detection (chirp models).
+pub const AUTO_DETECT: &str = "auto";
+
+/// Returns true if language code is in SUPPORTED_LANGUAGES (or is AUTO_DETECT).
+/// Comparison is case insensitive, i.e. en-us is supported as well.
+pub fn is_supported(code: &str) -> bool {
+    code.eq_ignore_ascii_case(AUTO_DETECT)
+        || SUPPORTED_LANGUAGES
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(code))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_is_supported
+    #[test]
+    fn test_is_supported() {
+        assert!(is_supported("en-US"));
+        assert!(is_supported("en-us"));
+        assert!(is_supported("cmn-Hans-CN"));
+        assert!(is_supported("auto"));
+        assert!(!is_supported("en-UX"));
+        assert!(!is_supported("english"));
+        assert!(!is_supported(""));
+    }
+}
@@ -0,0 +1,91 @@
+//! Typed access to response metadata (RecognitionResponseMetadata) of v2 responses.
+//! v2 API does not report model version in response metadata, model used for recognition
+//! is the one set in RecognitionConfig (or in the Recognizer resource).
+//! Neither RecognitionResponseMetadata nor SpeechRecognitionResult carry served model
+//! (version) in the protos, i.e. there is nothing to extract from RecognizeResponse or
+//! StreamSummary until Google adds such field.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    RecognitionResponseMetadata, RecognizeResponse, StreamingRecognizeResponse,
+};
+use crate::common::convert::to_std_duration;
+use std::time::Duration;
+
+/// Response metadata converted to idiomatic Rust types.
+/// Fields not populated by the API are None (instead of empty strings).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    /// global request identifier generated by the API
+    pub request_id: Option<String>,
+
+    /// billed audio duration of the request (when available)
+    pub total_billed_duration: Option<Duration>,
+
+    /// prompt used for the recognition request (chirp models)
+    pub prompt: Option<String>,
+}
+
+impl From<&RecognitionResponseMetadata> for ResponseMetadata {
+    fn from(metadata: &RecognitionResponseMetadata) -> Self {
+        ResponseMetadata {
+            request_id: Some(metadata.request_id.clone()).filter(|id| !id.is_empty()),
+            total_billed_duration: metadata.total_billed_duration.as_ref().map(to_std_duration),
+            prompt: metadata.prompt.clone().filter(|prompt| !prompt.is_empty()),
+        }
+    }
+}
+
+/// Returns metadata of recognize response (if present).
+pub fn metadata(response: &RecognizeResponse) -> Option<ResponseMetadata> {
+    response.metadata.as_ref().map(ResponseMetadata::from)
+}
+
+/// Returns metadata of streaming response (if present). Google sends metadata
+/// only with some of the streaming responses.
+pub fn streaming_metadata(response: &StreamingRecognizeResponse) -> Option<ResponseMetadata> {
+    response.metadata.as_ref().map(ResponseMetadata::from)
+}
+
+/// Returns billed audio duration of recognize response (if available).
+pub fn total_billed_duration(response: &RecognizeResponse) -> Option<Duration> {
+    metadata(response).and_then(|metadata| metadata.total_billed_duration)
+}
+
+/// Returns global request identifier of recognize response (if available).
+/// See recognizer_v2::request_id for streaming responses.
+pub fn request_id(response: &RecognizeResponse) -> Option<String> {
+    metadata(response).and_then(|metadata| metadata.request_id)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_response_metadata
+    #[test]
+    fn test_response_metadata() {
+        let response = RecognizeResponse {
+            results: vec![],
+            metadata: Some(RecognitionResponseMetadata {
+                request_id: "123".to_string(),
+                total_billed_duration: Some(prost_types::Duration {
+                    seconds: 15,
+                    nanos: 0,
+                }),
+                prompt: None,
+            }),
+        };
+        assert_eq!(request_id(&response).as_deref(), Some("123"));
+        assert_eq!(
+            total_billed_duration(&response),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(metadata(&response).unwrap().prompt, None);
+
+        let empty = RecognizeResponse {
+            results: vec![],
+            metadata: Some(RecognitionResponseMetadata::default()),
+        };
+        assert_eq!(metadata(&empty), Some(ResponseMetadata::default()));
+        assert_eq!(request_id(&RecognizeResponse::default()), None);
+    }
+}
@@ -0,0 +1,203 @@
+//! Ordering of streaming results across stream restarts.
+//! When stream is re-established while previous stream is still draining (e.g. audio
+//! after last acknowledged result is re-sent, see AudioCheckpointBuffer::reconnect),
+//! responses of both streams may arrive interleaved and a result of later audio may
+//! arrive before a result of earlier audio. ResultReorderBuffer takes responses of all
+//! streams (together with offset where their session started, see TimelineTracker) and
+//! releases them in audio-time order of their (rebased) result_end_offset.
+//!
+//! Ordering is achieved by holding each response until results at least `window` of
+//! audio later were received (or until the buffer is flushed), i.e. forwarding of every
+//! result is delayed by roughly `window` of audio. Window should therefore cover only
+//! the expected overlap of old and new stream (typically few hundred milliseconds).
+//! Response arriving later than window allows (i.e. ending before already released
+//! result) is released immediately, ordering can not be guaranteed for it.
+//!
+//! ResultReorderBuffer is a standalone utility for reconnect loops implemented by the caller.
+//! Recognizer never reconnects by itself and forwards responses of its single stream
+//! as they arrive (which is already audio-time order), i.e. responses must be pushed
+//! into the buffer by the caller, e.g. from result receivers of old and new recognizer.
+use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognizeResponse;
+use crate::common::convert::to_std_duration;
+use log::*;
+use std::time::Duration;
+
+/// Default reorder window, i.e. added latency of forwarded results.
+pub const DEFAULT_REORDER_WINDOW: Duration = Duration::from_millis(300);
+
+/// Buffer releasing streaming responses of overlapping streams in audio-time order.
+#[derive(Debug, Clone)]
+pub struct ResultReorderBuffer {
+    window: Duration,
+    /// held responses sorted by rebased end offset (stable for equal offsets)
+    pending: Vec<(Duration, StreamingRecognizeResponse)>,
+    /// latest rebased end offset received so far
+    latest: Duration,
+    /// rebased end offset of last released response
+    released: Duration,
+}
+
+impl Default for ResultReorderBuffer {
+    fn default() -> Self {
+        ResultReorderBuffer::new(DEFAULT_REORDER_WINDOW)
+    }
+}
+
+impl ResultReorderBuffer {
+    /// Creates buffer holding responses for given window of audio time.
+    /// Zero window disables reordering (responses are released as they are pushed).
+    pub fn new(window: Duration) -> Self {
+        ResultReorderBuffer {
+            window,
+            pending: vec![],
+            latest: Duration::ZERO,
+            released: Duration::ZERO,
+        }
+    }
+
+    /// Returns reorder window, i.e. added latency of released responses.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns number of responses currently held.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Pushes response of stream whose session started at given offset (from the very
+    /// beginning of audio) and returns responses which can be released in audio-time order.
+    /// Response without result offsets (e.g. speech event) is ordered right after
+    /// latest result received so far.
+    pub fn push(
+        &mut self,
+        session_start: Duration,
+        response: StreamingRecognizeResponse,
+    ) -> Vec<StreamingRecognizeResponse> {
+        let end_offset = response
+            .results
+            .iter()
+            .filter_map(|result| result.result_end_offset.as_ref())
+            .map(|offset| session_start + to_std_duration(offset))
+            .max()
+            .unwrap_or(self.latest);
+
+        if end_offset < self.released {
+            warn!(
+                "streaming result ending at {:?} arrived after result ending at {:?} was released, releasing out of order",
+                end_offset, self.released
+            );
+            return vec![response];
+        }
+
+        let position = self
+            .pending
+            .partition_point(|(pending_end, _)| *pending_end <= end_offset);
+        self.pending.insert(position, (end_offset, response));
+        self.latest = self.latest.max(end_offset);
+        self.release_until(self.latest.saturating_sub(self.window))
+    }
+
+    /// Releases all held responses in audio-time order, to be called once
+    /// all streams finished.
+    pub fn flush(&mut self) -> Vec<StreamingRecognizeResponse> {
+        self.release_until(self.latest)
+    }
+
+    fn release_until(&mut self, offset: Duration) -> Vec<StreamingRecognizeResponse> {
+        let count = self
+            .pending
+            .partition_point(|(pending_end, _)| *pending_end <= offset);
+        let released: Vec<_> = self.pending.drain(..count).collect();
+        if let Some((end_offset, _)) = released.last() {
+            self.released = *end_offset;
+        }
+        released.into_iter().map(|(_, response)| response).collect()
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        SpeechRecognitionAlternative, StreamingRecognitionResult,
+    };
+
+    #[allow(dead_code)]
+    fn response(transcript: &str, end_offset_millis: i32) -> StreamingRecognizeResponse {
+        StreamingRecognizeResponse {
+            results: vec![StreamingRecognitionResult {
+                alternatives: vec![SpeechRecognitionAlternative {
+                    transcript: transcript.to_string(),
+                    ..Default::default()
+                }],
+                result_end_offset: Some(prost_types::Duration {
+                    seconds: 0,
+                    nanos: end_offset_millis * 1_000_000,
+                }),
+                is_final: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[allow(dead_code)]
+    fn transcripts(responses: Vec<StreamingRecognizeResponse>) -> Vec<String> {
+        responses
+            .into_iter()
+            .map(|response| response.results[0].alternatives[0].transcript.clone())
+            .collect()
+    }
+
+    // cargo test -- --show-output test_reorder_overlapping_streams
+    #[test]
+    fn test_reorder_overlapping_streams() {
+        let mut buffer = ResultReorderBuffer::new(Duration::from_millis(300));
+        let old_stream = Duration::ZERO;
+        // new stream re-sends audio from checkpoint at 600ms
+        let new_stream = Duration::from_millis(600);
+
+        assert!(transcripts(buffer.push(old_stream, response("one", 400))).is_empty());
+        // new stream result (ends at 900ms) overtakes draining old stream
+        assert_eq!(
+            transcripts(buffer.push(new_stream, response("three", 300))),
+            vec!["one"]
+        );
+        assert!(transcripts(buffer.push(old_stream, response("two", 700))).is_empty());
+        assert_eq!(
+            transcripts(buffer.push(new_stream, response("four", 700))),
+            vec!["two", "three"]
+        );
+        assert_eq!(buffer.pending(), 1);
+        assert_eq!(transcripts(buffer.flush()), vec!["four"]);
+        assert_eq!(buffer.pending(), 0);
+
+        // result arriving after later result was released is not held back
+        assert_eq!(
+            transcripts(buffer.push(old_stream, response("late", 500))),
+            vec!["late"]
+        );
+    }
+
+    // cargo test -- --show-output test_reorder_zero_window
+    #[test]
+    fn test_reorder_zero_window() {
+        let mut buffer = ResultReorderBuffer::new(Duration::ZERO);
+        assert_eq!(
+            transcripts(buffer.push(Duration::ZERO, response("one", 400))),
+            vec!["one"]
+        );
+        assert_eq!(
+            buffer
+                .push(Duration::ZERO, StreamingRecognizeResponse::default())
+                .len(),
+            1
+        );
+        assert_eq!(
+            transcripts(buffer.push(Duration::ZERO, response("two", 800))),
+            vec!["two"]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Post-processing of final transcripts (cleanup, masking, replacements) applied by
+//! recognizer before results reach the caller, see Recognizer::add_transcript_processor.
+//! Processors are applied to transcripts of all alternatives of final results, word level
+//! details (words, offsets) are left untouched. Interim results are not processed.
+use std::fmt::Debug;
+
+/// Transformation of single final transcript.
+pub trait TranscriptProcessor: Send + Sync + Debug {
+    /// Returns processed transcript.
+    fn process(&self, transcript: String) -> String;
+}
+
+/// Trims transcript and collapses runs of whitespace into single space.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeWhitespace;
+
+impl TranscriptProcessor for NormalizeWhitespace {
+    fn process(&self, transcript: String) -> String {
+        transcript.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Upper-cases the first letter of transcript.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Capitalize;
+
+impl TranscriptProcessor for Capitalize {
+    fn process(&self, transcript: String) -> String {
+        match transcript.char_indices().find(|(_, c)| c.is_alphabetic()) {
+            Some((index, c)) => {
+                let mut capitalized = String::with_capacity(transcript.len());
+                capitalized.push_str(&transcript[..index]);
+                capitalized.extend(c.to_uppercase());
+                capitalized.push_str(&transcript[index + c.len_utf8()..]);
+                capitalized
+            }
+            None => transcript,
+        }
+    }
+}
+
+/// Replaces substrings (e.g. product names misrecognized by the model),
+/// replacements are applied in order, matching is case sensitive.
+#[derive(Debug, Clone, Default)]
+pub struct Replace {
+    replacements: Vec<(String, String)>,
+}
+
+impl Replace {
+    /// Creates processor without replacements.
+    pub fn new() -> Self {
+        Replace::default()
+    }
+
+    /// Adds replacement of from by to.
+    pub fn replace(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        let from = from.into();
+        if !from.is_empty() {
+            self.replacements.push((from, to.into()));
+        }
+        self
+    }
+}
+
+impl TranscriptProcessor for Replace {
+    fn process(&self, transcript: String) -> String {
+        self.replacements
+            .iter()
+            .fold(transcript, |transcript, (from, to)| {
+                transcript.replace(from, to)
+            })
+    }
+}
+
+/// Masks listed words (whole words, case insensitive) keeping their first letter,
+/// e.g. "darn" becomes "d***". Punctuation around words is preserved.
+/// Complements profanity_filter of recognition config with custom word lists.
+#[derive(Debug, Clone, Default)]
+pub struct MaskWords {
+    words: Vec<String>,
+}
+
+impl MaskWords {
+    /// Creates processor masking given words.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        MaskWords {
+            words: words
+                .into_iter()
+                .map(|word| word.as_ref().to_lowercase())
+                .collect(),
+        }
+    }
+
+    fn mask(&self, token: &str) -> String {
+        let trimmed_start = token.trim_start_matches(|c: char| !c.is_alphanumeric());
+        let word = trimmed_start.trim_end_matches(|c: char| !c.is_alphanumeric());
+        if word.is_empty() || !self.words.contains(&word.to_lowercase()) {
+            return token.to_string();
+        }
+        let start = token.len() - trimmed_start.len();
+        let mut chars = word.chars();
+        let masked: String = chars.next().into_iter().chain(chars.map(|_| '*')).collect();
+        format!(
+            "{}{}{}",
+            &token[..start],
+            masked,
+            &token[start + word.len()..]
+        )
+    }
+}
+
+impl TranscriptProcessor for MaskWords {
+    fn process(&self, transcript: String) -> String {
+        if self.words.is_empty() {
+            return transcript;
+        }
+        // whitespace is kept as is
+        let mut processed = String::with_capacity(transcript.len());
+        let mut token = String::new();
+        for c in transcript.chars() {
+            if c.is_whitespace() {
+                processed.push_str(&self.mask(&token));
+                token.clear();
+                processed.push(c);
+            } else {
+                token.push(c);
+            }
+        }
+        processed.push_str(&self.mask(&token));
+        processed
+    }
+}
+
+/// Applies processors in order to transcript.
+pub fn process_transcript(
+    processors: &[Box<dyn TranscriptProcessor>],
+    transcript: String,
+) -> String {
+    processors.iter().fold(transcript, |transcript, processor| {
+        processor.process(transcript)
+    })
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_builtin_processors
+    #[test]
+    fn test_builtin_processors() {
+        assert_eq!(
+            NormalizeWhitespace.process("  hello \t  world ".to_string()),
+            "hello world"
+        );
+        assert_eq!(Capitalize.process(" élan vital".to_string()), " Élan vital");
+        assert_eq!(Capitalize.process("123".to_string()), "123");
+
+        let replace = Replace::new()
+            .replace("cube control", "kubectl")
+            .replace("", "ignored");
+        assert_eq!(
+            replace.process("run cube control apply".to_string()),
+            "run kubectl apply"
+        );
+
+        let mask = MaskWords::new(["darn", "heck"]);
+        assert_eq!(
+            mask.process("Darn, what the heck!  darnit".to_string()),
+            "D***, what the h***!  darnit"
+        );
+    }
+
+    // cargo test -- --show-output test_process_transcript
+    #[test]
+    fn test_process_transcript() {
+        let processors: Vec<Box<dyn TranscriptProcessor>> = vec![
+            Box::new(NormalizeWhitespace),
+            Box::new(MaskWords::new(["heck"])),
+            Box::new(Capitalize),
+        ];
+        assert_eq!(
+            process_transcript(&processors, " what  the heck ".to_string()),
+            "What the h***"
+        );
+        assert_eq!(process_transcript(&[], " a ".to_string()), " a ");
+    }
+}
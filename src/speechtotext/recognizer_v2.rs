@@ -17,28 +17,59 @@
 #![allow(clippy::manual_map)]
 #![allow(unused_imports)]
 use crate::api::grpc::google::cloud::speechtotext::v2::{
-    BatchRecognizeRequest, BatchRecognizeResponse, 
+    explicit_decoding_config::AudioEncoding as ExplicitAudioEncoding,
+    recognition_config::DecodingConfig, recognize_request::AudioSource,
     speech_client::SpeechClient, streaming_recognize_request::StreamingRequest,
-    RecognizeRequest, RecognizeResponse,
-    StreamingRecognitionConfig, StreamingRecognizeRequest, StreamingRecognizeResponse,
+    BatchRecognizeRequest, BatchRecognizeResponse, RecognitionConfig, RecognizeRequest,
+    RecognizeResponse, StreamingRecognitionConfig, StreamingRecognizeRequest,
+    StreamingRecognizeResponse,
 };
 use crate::api::grpc::google::longrunning::{
-    operation::Result as OperationResult, operations_client::OperationsClient, GetOperationRequest,
-    Operation,
+    operation::Result as OperationResult, operations_client::OperationsClient,
+    CancelOperationRequest, GetOperationRequest, ListOperationsRequest, Operation,
 };
-use crate::common::{get_token, new_grpc_channel, new_interceptor, TokenInterceptor};
+use crate::common::convert::to_std_duration;
+use crate::common::{
+    get_token, new_grpc_channel, new_interceptor, new_provider_interceptor, validate_token,
+    ConnectionInfo, ConnectionState, StreamLimiter, TokenInterceptor,
+};
+use crate::credentials::CredentialProvider;
 use crate::errors::{Error, Result};
+use crate::speechtotext::audio::{
+    explicit_audio_duration, f32_to_linear16, i16_to_linear16, pcm_byte_len, pcm_duration,
+    validate_ogg_opus_header, PumpOptions, OGG_PAGE_HEADER_LEN,
+};
+use crate::speechtotext::cache::{CacheCounters, CacheKey, CacheStats, RecognitionCache};
+use crate::speechtotext::config::config_for_file;
+use crate::speechtotext::processing::{process_transcript, TranscriptProcessor};
+use crate::speechtotext::recording::write_delimited;
+use crate::speechtotext::sink::{result_channel, OverflowQueue, ResultOverflowPolicy, ResultSink};
 use async_stream::try_stream;
 use futures_core::stream::Stream;
+use futures_sink::Sink;
+use gouth::Token;
 use log::*;
 use prost::Message;
-use std::io::Cursor;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::future::Future;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::pin::Pin;
 use std::result::Result as StdResult;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, watch, Notify, OwnedSemaphorePermit};
 use tokio::time::sleep;
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::codegen::InterceptedService;
+use tokio_stream::StreamExt;
+use tokio_util::sync::PollSender;
+use tonic::codegen::{CompressionEncoding, InterceptedService};
+use tonic::metadata::{Ascii, MetadataKey, MetadataValue};
+use tonic::Request as TonicRequest;
 use tonic::Response as TonicResponse;
 use tonic::Status as TonicStatus;
 use tonic::{transport::Channel, Response as GrpcResponse, Streaming};
@@ -46,6 +77,895 @@ use tonic::{transport::Channel, Response as GrpcResponse, Streaming};
 const GRPC_API_DOMAIN: &str = "speech.googleapis.com";
 const GRPC_API_URL: &str = "https://speech.googleapis.com";
 
+/// Maximal size of inline audio content accepted by synchronous recognition.
+pub const SYNC_RECOGNIZE_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+/// Maximal duration of audio accepted by synchronous recognition.
+pub const SYNC_RECOGNIZE_MAX_DURATION: Duration = Duration::from_secs(60);
+
+/// Duration of audio chunks sent by Recognizer::pump_audio_realtime.
+pub const REALTIME_CHUNK_DURATION: Duration = Duration::from_millis(100);
+
+/// Capacity of audio sink of low latency streaming recognizers
+/// (see create_streaming_recognizer_low_latency).
+pub const LOW_LATENCY_BUFFER_SIZE: usize = 1;
+
+/// Returns audio sink capacity for low_latency flag (None stands for default capacity).
+fn low_latency_buffer_size(low_latency: bool) -> Option<usize> {
+    if low_latency {
+        Some(LOW_LATENCY_BUFFER_SIZE)
+    } else {
+        None
+    }
+}
+
+/// Aggregated statistics of finished streaming recognition.
+/// See Recognizer::streaming_recognize_with_summary.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamSummary {
+    /// total number of audio bytes sent to Google
+    pub audio_bytes_sent: u64,
+
+    /// duration of audio processed by Google, i.e. greatest result_end_offset
+    /// seen in the responses
+    pub audio_duration: Duration,
+
+    /// number of final results received
+    pub final_results: usize,
+
+    /// total number of words in top alternatives of final results
+    pub total_words: usize,
+
+    /// wall-clock time from start of streaming until the stream was closed
+    pub elapsed: Duration,
+
+    /// request id assigned by Google (see request_id), needed when filing support cases
+    pub request_id: Option<String>,
+
+    /// true if stream was closed because session deadline was reached
+    /// (see Recognizer::set_session_deadline)
+    pub deadline_reached: bool,
+}
+
+impl StreamSummary {
+    /// Updates summary with data from single streaming response.
+    fn record_response(&mut self, response: &StreamingRecognizeResponse) {
+        for result in &response.results {
+            if let Some(end_offset) = &result.result_end_offset {
+                let end_offset = to_std_duration(end_offset);
+                if end_offset > self.audio_duration {
+                    self.audio_duration = end_offset;
+                }
+            }
+
+            if result.is_final {
+                self.final_results += 1;
+                if let Some(alternative) = result.alternatives.first() {
+                    self.total_words += alternative.transcript.split_whitespace().count();
+                }
+            }
+        }
+    }
+}
+
+/// Receives requests already queued in audio receiver up to (and including) the first
+/// audio chunk. Returns error with code NO_AUDIO_SENT if audio stream was closed before
+/// any audio was sent (e.g. capture device failed), i.e. without opening GRPC call
+/// which would be rejected by Google.
+fn receive_queued_until_audio(
+    audio_receiver: &mut mpsc::Receiver<StreamingRecognizeRequest>,
+) -> Result<Vec<StreamingRecognizeRequest>> {
+    let mut received = vec![];
+    loop {
+        match audio_receiver.try_recv() {
+            Ok(request) => {
+                let is_audio =
+                    matches!(request.streaming_request, Some(StreamingRequest::Audio(_)));
+                received.push(request);
+                if is_audio {
+                    return Ok(received);
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => return Ok(received),
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                return Err(Error::new_with_code(
+                    "Audio stream was closed before any audio was sent".to_string(),
+                    "NO_AUDIO_SENT".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Sends streaming response to result sink (if any), see get_streaming_result_receiver.
+async fn send_result(
+    result_sender: Option<Arc<dyn ResultSink>>,
+    response: StreamingRecognizeResponse,
+) -> Result<()> {
+    if let Some(result_sender) = result_sender {
+        result_sender.send(response).await?;
+    }
+    Ok(())
+}
+
+/// Returns global request identifier generated by Google contained in response metadata
+/// (if present). Google support asks for this id when investigating issues.
+pub fn request_id(response: &StreamingRecognizeResponse) -> Option<String> {
+    response
+        .metadata
+        .as_ref()
+        .map(|metadata| metadata.request_id.clone())
+        .filter(|request_id| !request_id.is_empty())
+}
+
+/// Returns Debug representation of streaming request with audio bytes replaced
+/// by their length, e.g. for logging of outgoing requests.
+pub fn redact_streaming_request(request: &StreamingRecognizeRequest) -> String {
+    match &request.streaming_request {
+        Some(StreamingRequest::Audio(audio_bytes)) => format!(
+            "StreamingRecognizeRequest {{ recognizer: {:?}, streaming_request: Some(Audio(<{} bytes>)) }}",
+            request.recognizer,
+            audio_bytes.len()
+        ),
+        _ => format!("{:?}", request),
+    }
+}
+
+/// Returns Debug representation of recognize request with inline audio content
+/// replaced by its length, e.g. for logging of outgoing requests.
+pub fn redact_recognize_request(request: &RecognizeRequest) -> String {
+    match &request.audio_source {
+        Some(AudioSource::Content(content)) => {
+            let redacted = RecognizeRequest {
+                audio_source: Some(AudioSource::Content(vec![])),
+                ..request.clone()
+            };
+            format!("{:?}", redacted).replace(
+                "Content([])",
+                &format!("Content(<{} bytes>)", content.len()),
+            )
+        }
+        _ => format!("{:?}", request),
+    }
+}
+
+/// Forwards transcripts from receiver to sender. Interim transcripts are forwarded
+/// at most once per window (only the latest one), final transcripts immediately.
+/// Pending interim transcript is discarded when final transcript of the same channel arrives.
+async fn debounce_transcripts(
+    mut receiver: broadcast::Receiver<StreamingTranscript>,
+    sender: mpsc::Sender<StreamingTranscript>,
+    window: Duration,
+) {
+    let mut pending: Option<StreamingTranscript> = None;
+    let mut last_sent: Option<tokio::time::Instant> = None;
+
+    loop {
+        // None means that window of pending interim transcript has elapsed
+        let received = match (&pending, last_sent) {
+            (Some(_), Some(last_sent)) => {
+                tokio::time::timeout_at(last_sent + window, receiver.recv())
+                    .await
+                    .ok()
+            }
+            _ => Some(receiver.recv().await),
+        };
+
+        let to_send = match received {
+            None => pending.take(),
+            Some(Ok(transcript)) if transcript.is_final => {
+                if pending
+                    .as_ref()
+                    .is_some_and(|pending| pending.channel_tag == transcript.channel_tag)
+                {
+                    pending = None;
+                }
+                Some(transcript)
+            }
+            Some(Ok(transcript)) => {
+                if last_sent.is_none_or(|last_sent| last_sent.elapsed() >= window) {
+                    Some(transcript)
+                } else {
+                    pending = Some(transcript);
+                    None
+                }
+            }
+            Some(Err(broadcast::error::RecvError::Lagged(_))) => None,
+            Some(Err(broadcast::error::RecvError::Closed)) => {
+                if let Some(transcript) = pending.take() {
+                    let _ = sender.send(transcript).await;
+                }
+                return;
+            }
+        };
+
+        if let Some(transcript) = to_send {
+            if sender.send(transcript).await.is_err() {
+                return;
+            }
+            last_sent = Some(tokio::time::Instant::now());
+        }
+    }
+}
+
+/// Response tagged with client side context, e.g. session identifier used by
+/// multiplexing servers to route responses back to the right session.
+/// Context is never sent to Google.
+#[derive(Debug, Clone)]
+pub struct ContextualResponse<C, T> {
+    /// context provided by the caller
+    pub context: C,
+    /// response received from the API
+    pub response: T,
+}
+
+/// Transcript of single streaming recognition result (top alternative only).
+/// See Recognizer::subscribe_transcripts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamingTranscript {
+    /// transcript of the top alternative
+    pub transcript: String,
+
+    /// whether this is final (true) or interim (false) result
+    pub is_final: bool,
+
+    /// estimated likelihood that interim result will not change (0.0 - 1.0)
+    pub stability: f32,
+
+    /// offset of the end of this result relative to the beginning of the audio
+    pub result_end_offset: Duration,
+
+    /// channel number for multi-channel audio
+    pub channel_tag: i32,
+
+    /// language detected in this result
+    pub language_code: String,
+}
+
+impl StreamingTranscript {
+    /// Extracts transcripts from all results contained in response.
+    /// Results without alternatives are skipped.
+    pub fn from_response(response: &StreamingRecognizeResponse) -> Vec<StreamingTranscript> {
+        response
+            .results
+            .iter()
+            .filter_map(|result| {
+                result
+                    .alternatives
+                    .first()
+                    .map(|alternative| StreamingTranscript {
+                        transcript: alternative.transcript.clone(),
+                        is_final: result.is_final,
+                        stability: result.stability,
+                        result_end_offset: result
+                            .result_end_offset
+                            .as_ref()
+                            .map(to_std_duration)
+                            .unwrap_or_default(),
+                        channel_tag: result.channel_tag,
+                        language_code: result.language_code.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Min interval between repeated high-water mark warnings of one audio sink.
+const SINK_WARNING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Signal of the first audio chunk pulled from audio channel by GRPC request
+/// stream, see Recognizer::first_audio_sent.
+#[derive(Debug, Default)]
+struct FirstAudioSignal {
+    sent: AtomicBool,
+    notify: Notify,
+}
+
+impl FirstAudioSignal {
+    fn observe(&self, request: &StreamingRecognizeRequest) {
+        if !self.sent.load(Ordering::Acquire)
+            && matches!(request.streaming_request, Some(StreamingRequest::Audio(_)))
+        {
+            self.sent.store(true, Ordering::Release);
+            self.notify.notify_waiters();
+        }
+    }
+
+    async fn wait(&self) {
+        loop {
+            // registered before flag is checked so that notification is not missed
+            let notified = self.notify.notified();
+            if self.sent.load(Ordering::Acquire) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Byte based limit of audio buffered in audio channel, see Recognizer::set_max_buffered_bytes.
+/// Bytes are reserved by AudioProducer/AudioCallbackSink before send and released once
+/// respective chunk is pulled by GRPC request stream.
+#[derive(Debug, Default)]
+struct AudioBytesBudget {
+    buffered: AtomicUsize,
+    // zero means unlimited
+    max: AtomicUsize,
+    released: Notify,
+}
+
+impl AudioBytesBudget {
+    /// Reserves len bytes if limit allows it. Chunk larger than the limit
+    /// is accepted when nothing is buffered so that producers never get stuck.
+    fn try_reserve(&self, len: usize) -> bool {
+        let max = self.max.load(Ordering::Relaxed);
+        self.buffered
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |buffered| {
+                if max == 0 || buffered == 0 || buffered + len <= max {
+                    Some(buffered + len)
+                } else {
+                    None
+                }
+            })
+            .is_ok()
+    }
+
+    /// Waits until len bytes can be reserved.
+    async fn reserve(&self, len: usize) {
+        loop {
+            // registered before reservation is attempted so that release is not missed
+            let released = self.released.notified();
+            if self.try_reserve(len) {
+                return;
+            }
+            released.await;
+        }
+    }
+
+    fn release(&self, len: usize) {
+        // chunks sent by plain audio sink were not reserved, i.e. counter saturates at zero
+        let _ = self
+            .buffered
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |buffered| {
+                Some(buffered.saturating_sub(len))
+            });
+        self.released.notify_waiters();
+    }
+
+    fn release_request(&self, request: &StreamingRecognizeRequest) {
+        if let Some(StreamingRequest::Audio(audio_bytes)) = &request.streaming_request {
+            self.release(audio_bytes.len());
+        }
+    }
+}
+
+/// Fill level of audio sink computed from channel capacity, see Recognizer::sink_pressure.
+#[derive(Debug, Clone)]
+struct SinkGauge {
+    capacity: usize,
+    high_water_mark: Option<f32>,
+    last_warning: Arc<Mutex<Option<Instant>>>,
+    log_tag: String,
+}
+
+impl SinkGauge {
+    /// Returns fill level of the channel (0.0 - 1.0).
+    fn pressure(&self, sender: &mpsc::Sender<StreamingRecognizeRequest>) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        let queued = self.capacity.saturating_sub(sender.capacity());
+        queued as f32 / self.capacity as f32
+    }
+
+    /// Logs warning (at most once per SINK_WARNING_INTERVAL) if fill level
+    /// reached high-water mark.
+    fn check(&self, sender: &mpsc::Sender<StreamingRecognizeRequest>) {
+        let high_water_mark = match self.high_water_mark {
+            Some(high_water_mark) => high_water_mark,
+            None => return,
+        };
+        let pressure = self.pressure(sender);
+        if pressure < high_water_mark {
+            return;
+        }
+        if let Ok(mut last_warning) = self.last_warning.lock() {
+            if last_warning
+                .is_none_or(|last_warning| last_warning.elapsed() >= SINK_WARNING_INTERVAL)
+            {
+                *last_warning = Some(Instant::now());
+                warn!(
+                    "{}audio sink is {:.0}% full (high-water mark {:.0}%), audio producers will block or drop audio soon",
+                    self.log_tag,
+                    pressure * 100.0,
+                    high_water_mark * 100.0
+                );
+            }
+        }
+    }
+}
+
+/// Adapter feeding 16-bit (or normalized f32) audio samples delivered by synchronous callbacks
+/// (e.g. cpal or PortAudio input stream callback) into audio sink of streaming recognizer.
+/// Since callbacks cannot await, samples are sent with try_send. When audio sink is full
+/// (or closed) chunk is dropped and drop counter is incremented.
+/// See Recognizer::audio_callback_sink.
+#[derive(Debug, Clone)]
+pub struct AudioCallbackSink {
+    audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+    dropped_chunks: Arc<AtomicU64>,
+    sink_gauge: SinkGauge,
+    audio_bytes: Arc<AudioBytesBudget>,
+}
+
+impl AudioCallbackSink {
+    /// Converts samples into little-endian LINEAR16 bytes and sends them into audio sink.
+    /// Returns false if chunk was dropped (sink is full, closed or limit of buffered
+    /// bytes was reached, see Recognizer::set_max_buffered_bytes).
+    pub fn push(&self, samples: &[i16]) -> bool {
+        self.push_bytes(i16_to_linear16(samples))
+    }
+
+    /// Same as push, but accepts normalized f32 samples (-1.0 - 1.0, e.g. cpal f32
+    /// stream or WebAudio) which are clamped and converted to LINEAR16.
+    pub fn push_f32(&self, samples: &[f32]) -> bool {
+        self.push_bytes(f32_to_linear16(samples))
+    }
+
+    fn push_bytes(&self, audio_bytes: Vec<u8>) -> bool {
+        let len = audio_bytes.len();
+        if !self.audio_bytes.try_reserve(len) {
+            self.dropped_chunks.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        let request = Recognizer::streaming_request_from_bytes(audio_bytes, String::new());
+        self.sink_gauge.check(&self.audio_sender);
+        match self.audio_sender.try_send(request) {
+            Ok(()) => true,
+            Err(_) => {
+                self.audio_bytes.release(len);
+                self.dropped_chunks.fetch_add(1, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
+    /// Returns fill level of audio sink (0.0 - 1.0), see Recognizer::sink_pressure.
+    pub fn sink_pressure(&self) -> f32 {
+        self.sink_gauge.pressure(&self.audio_sender)
+    }
+
+    /// Returns number of chunks dropped so far.
+    pub fn dropped_chunks(&self) -> u64 {
+        self.dropped_chunks.load(Ordering::Relaxed)
+    }
+
+    /// Returns shared drop counter so that drops can be monitored
+    /// after the sink was moved into callback.
+    pub fn dropped_chunks_counter(&self) -> Arc<AtomicU64> {
+        self.dropped_chunks.clone()
+    }
+
+    /// Converts sink into closure which can be passed directly as audio callback.
+    pub fn into_callback(self) -> impl FnMut(&[i16]) + Send + 'static {
+        move |samples: &[i16]| {
+            self.push(samples);
+        }
+    }
+
+    /// Converts sink into closure which can be passed directly as f32 audio callback.
+    pub fn into_callback_f32(self) -> impl FnMut(&[f32]) + Send + 'static {
+        move |samples: &[f32]| {
+            self.push_f32(samples);
+        }
+    }
+}
+
+/// What PacedAudioSink does with pushed buffer when its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOverflowPolicy {
+    /// oldest queued buffer is dropped to make room for the pushed one
+    DropOldest,
+    /// pushed buffer is dropped
+    DropNewest,
+}
+
+/// Adapter for media frameworks delivering LINEAR16 buffers from their own (non-async)
+/// threads, e.g. GStreamer appsink new-sample callback or FFmpeg/libav decode loop.
+/// push only queues the buffer and never blocks the media thread, spawned task forwards
+/// queued buffers into audio sink paced to real-time speed (multiplied by pace), i.e.
+/// sources running faster than real time (file sources, pull mode) are slowed down
+/// without stalling the pipeline. When queue is full (e.g. network is slow) buffers
+/// are dropped according to PushOverflowPolicy. Typical integration:
+/// * create sink with Recognizer::paced_audio_sink matching caps of the pipeline
+///   (audio/x-raw, format=S16LE, rate, channels)
+/// * move it (or Arc of it) into the callback and call push with mapped buffer bytes
+/// * drop the sink on end-of-stream, audio stream is closed once queue is drained
+///
+/// Pacing clock starts with the first forwarded buffer, after gaps in the source
+/// queued audio is forwarded without delay until it catches up with the clock.
+#[derive(Debug)]
+pub struct PacedAudioSink {
+    queue: Arc<OverflowQueue<Vec<u8>>>,
+    dropped_buffers: Arc<AtomicU64>,
+}
+
+impl PacedAudioSink {
+    /// Queues copy of buffer without waiting. Returns false if some buffer
+    /// was dropped (queue is full) or audio sink is closed.
+    pub fn push(&self, bytes: &[u8]) -> bool {
+        if self.queue.is_consumer_closed() || !self.queue.push(bytes.to_vec()) {
+            self.dropped_buffers.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
+    /// Returns number of buffers dropped so far.
+    pub fn dropped_buffers(&self) -> u64 {
+        self.dropped_buffers.load(Ordering::Relaxed)
+    }
+
+    /// Returns shared drop counter so that drops can be monitored
+    /// after the sink was moved into callback.
+    pub fn dropped_buffers_counter(&self) -> Arc<AtomicU64> {
+        self.dropped_buffers.clone()
+    }
+}
+
+impl Drop for PacedAudioSink {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+/// Forwards buffers queued by PacedAudioSink into audio sink at real-time speed
+/// multiplied by pace. Ends once sink is dropped (and queue drained) or audio sink is closed.
+async fn forward_paced(
+    queue: Arc<OverflowQueue<Vec<u8>>>,
+    audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+    sample_rate: u32,
+    channels: u16,
+    pace: f64,
+) {
+    let mut started = None;
+    let mut total_bytes: u64 = 0;
+    while let Some(chunk) = queue.pop().await {
+        let started = *started.get_or_insert_with(tokio::time::Instant::now);
+        total_bytes += chunk.len() as u64;
+        if audio_sender
+            .send(Recognizer::streaming_request_from_bytes(
+                chunk,
+                String::new(),
+            ))
+            .await
+            .is_err()
+        {
+            queue.close_consumer();
+            return;
+        }
+        let sent_duration = pcm_duration(total_bytes as usize, sample_rate, channels, 16);
+        tokio::time::sleep_until(started + sent_duration.div_f64(pace)).await;
+    }
+}
+
+/// Validation of LINEAR16 audio chunks sent to streaming recognition. Chunk length must be
+/// multiple of frame size (channels * 2 bytes), otherwise samples are misaligned and audio
+/// is turned into noise. Validation is applied only when streaming config with explicit
+/// LINEAR16 decoding config was sent by the recognizer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameValidation {
+    /// chunks are not validated (default)
+    Disabled,
+    /// misaligned chunks are logged (warning) and sent anyway
+    Log,
+    /// misaligned chunk terminates audio stream, streaming_recognize then
+    /// returns error with code FRAME_MISALIGNED
+    Strict,
+}
+
+/// Handle of streaming recognition which allows to stop it externally,
+/// see Recognizer::streaming_recognize_async_stream_with_handle.
+#[derive(Debug, Clone)]
+pub struct StreamHandle {
+    abort_sender: Arc<watch::Sender<bool>>,
+    // transcripts (top alternative) of final results received so far
+    finals: Arc<Mutex<Vec<String>>>,
+}
+
+impl StreamHandle {
+    /// Stops streaming immediately, i.e. closes audio stream and cancels GRPC call.
+    pub fn abort(&self) {
+        let _ = self.abort_sender.send(true);
+    }
+
+    /// Returns true if abort was called.
+    pub fn is_aborted(&self) -> bool {
+        *self.abort_sender.borrow()
+    }
+
+    /// Stops streaming (see abort) and returns transcripts of final results received
+    /// up to this point joined by space, e.g. to use partial voice command cancelled early.
+    /// Responses still in flight are discarded.
+    pub fn abort_and_collect(self) -> Result<String> {
+        self.abort();
+        let finals = self
+            .finals
+            .lock()
+            .map_err(|_| Error::new("Stream handle finals lock poisoned".to_string()))?;
+        Ok(finals
+            .iter()
+            .map(|transcript| transcript.trim())
+            .filter(|transcript| !transcript.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    fn collect_finals(&self, response: &StreamingRecognizeResponse) {
+        if let Ok(mut finals) = self.finals.lock() {
+            finals.extend(
+                response
+                    .results
+                    .iter()
+                    .filter(|result| result.is_final)
+                    .filter_map(|result| result.alternatives.first())
+                    .map(|alternative| alternative.transcript.clone()),
+            );
+        }
+    }
+}
+
+/// Resolves once abort is signalled (see StreamHandle::abort), never if there is no signal.
+async fn abort_requested(abort: &mut Option<watch::Receiver<bool>>) {
+    if let Some(abort) = abort {
+        while !*abort.borrow_and_update() {
+            if abort.changed().await.is_err() {
+                break;
+            }
+        }
+        if *abort.borrow() {
+            return;
+        }
+    }
+    std::future::pending::<()>().await
+}
+
+/// Ends request stream once abort is signalled, requests still queued are not sent.
+fn abortable_requests(
+    requests: impl Stream<Item = StreamingRecognizeRequest>,
+    mut abort: Option<watch::Receiver<bool>>,
+) -> impl Stream<Item = StreamingRecognizeRequest> {
+    async_stream::stream! {
+        tokio::pin!(requests);
+        loop {
+            let request = tokio::select! {
+                biased;
+                _ = abort_requested(&mut abort) => None,
+                request = requests.next() => request,
+            };
+            match request {
+                Some(request) => yield request,
+                None => break,
+            }
+        }
+    }
+}
+
+/// End of stream coordination shared by recognizer and its StreamCloser handles.
+#[derive(Debug)]
+struct StreamLifecycle {
+    /// set once end of audio was requested, reset when new stream is prepared (reconfigure)
+    close_requested: watch::Sender<bool>,
+    /// true while streaming call (including delivery of responses) is in progress
+    running: watch::Sender<bool>,
+}
+
+impl StreamLifecycle {
+    fn new() -> Arc<Self> {
+        Arc::new(StreamLifecycle {
+            close_requested: watch::channel(false).0,
+            running: watch::channel(false).0,
+        })
+    }
+
+    /// Marks stream as running until returned guard is dropped.
+    fn start(self: &Arc<Self>) -> RunningStream {
+        self.running.send_replace(true);
+        RunningStream(self.clone())
+    }
+
+    /// Waits until running stream (if any) is finished.
+    async fn finished(&self) {
+        let mut running = self.running.subscribe();
+        while *running.borrow_and_update() {
+            if running.changed().await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Wraps audio receiver into stream which ends once all senders are dropped or end
+    /// of audio is requested. Requests queued before the request are still sent.
+    fn closable_audio(
+        self: &Arc<Self>,
+        mut audio_receiver: mpsc::Receiver<StreamingRecognizeRequest>,
+    ) -> impl Stream<Item = StreamingRecognizeRequest> {
+        // lifecycle is kept alive so that close_requested sender is never dropped
+        let lifecycle = self.clone();
+        let mut close_requested = self.close_requested.subscribe();
+        async_stream::stream! {
+            let _lifecycle = lifecycle;
+            loop {
+                let request = if *close_requested.borrow_and_update() {
+                    audio_receiver.try_recv().ok()
+                } else {
+                    tokio::select! {
+                        request = audio_receiver.recv() => request,
+                        _ = close_requested.changed() => continue,
+                    }
+                };
+                match request {
+                    Some(request) => yield request,
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+/// Marks stream as finished when dropped (on success, error or cancellation).
+struct RunningStream(Arc<StreamLifecycle>);
+
+impl Drop for RunningStream {
+    fn drop(&mut self) {
+        self.0.running.send_replace(false);
+    }
+}
+
+/// Streaming call opened by Recognizer::open_stream, kept until the stream is finished.
+struct OpenStream {
+    response_stream: Streaming<StreamingRecognizeResponse>,
+    /// length of misaligned chunk which terminated audio stream (see check_frame_alignment)
+    misaligned: Arc<Mutex<Option<usize>>>,
+    /// abort signal of StreamHandle, response stream ends once signalled
+    abort: Option<watch::Receiver<bool>>,
+    /// permit of stream limiter, released when stream is finished
+    _stream_permit: Option<OwnedSemaphorePermit>,
+    _running: RunningStream,
+}
+
+/// Handle which ends audio of running streaming recognition and waits for remaining
+/// responses, e.g. from task other than the one driving streaming_recognize (which holds
+/// the recognizer). See Recognizer::stream_closer.
+#[derive(Debug, Clone)]
+pub struct StreamCloser {
+    lifecycle: Arc<StreamLifecycle>,
+}
+
+impl StreamCloser {
+    /// Ends audio stream and waits until response stream signals completion, i.e. until
+    /// all remaining responses are received (and sent to channel returned by
+    /// get_streaming_result_receiver). Audio already queued in audio sink is still sent,
+    /// audio sent afterwards is rejected (senders report closed channel). Senders obtained
+    /// via get_audio_sink/take_audio_sink do not have to be dropped. If stream was not
+    /// started yet returns immediately, stream started later sends only audio queued
+    /// so far. Calling this method repeatedly (or concurrently) is safe, every call waits
+    /// for the same completion. Errors of the stream are returned by streaming_recognize.
+    pub async fn close_audio_and_flush(&self) -> Result<()> {
+        self.lifecycle.close_requested.send_replace(true);
+        self.lifecycle.finished().await;
+        Ok(())
+    }
+
+    /// Returns true while streaming call is in progress.
+    pub fn is_streaming(&self) -> bool {
+        *self.lifecycle.running.borrow()
+    }
+}
+
+/// Cheaply clonable audio sender for multiple producer tasks mixing audio into one
+/// recognition, see Recognizer::audio_producer_handle. Audio stream is closed (and
+/// recognition finalized) once all producers are closed or dropped.
+#[derive(Debug)]
+pub struct AudioProducer {
+    sender: mpsc::Sender<StreamingRecognizeRequest>,
+    producers: Arc<AtomicUsize>,
+    sink_gauge: SinkGauge,
+    audio_bytes: Arc<AudioBytesBudget>,
+}
+
+impl AudioProducer {
+    /// Sends audio bytes into the stream. Waits while audio sink is full
+    /// or limit of buffered bytes is reached (see Recognizer::set_max_buffered_bytes).
+    pub async fn send_bytes(&self, audio_bytes: Vec<u8>) -> Result<()> {
+        let len = audio_bytes.len();
+        self.audio_bytes.reserve(len).await;
+        self.sink_gauge.check(&self.sender);
+        let sent = self
+            .sender
+            .send(Recognizer::streaming_request_from_bytes(
+                audio_bytes,
+                String::new(),
+            ))
+            .await;
+        if sent.is_err() {
+            self.audio_bytes.release(len);
+        }
+        sent?;
+        Ok(())
+    }
+
+    /// Closes this producer. Audio stream stays open until the last producer is closed.
+    pub fn close(self) {}
+
+    /// Returns fill level of audio sink (0.0 - 1.0), see Recognizer::sink_pressure.
+    pub fn sink_pressure(&self) -> f32 {
+        self.sink_gauge.pressure(&self.sender)
+    }
+
+    /// Returns number of producers (clones of this handle) which are still open.
+    pub fn producer_count(&self) -> usize {
+        self.producers.load(Ordering::SeqCst)
+    }
+}
+
+impl Clone for AudioProducer {
+    fn clone(&self) -> Self {
+        self.producers.fetch_add(1, Ordering::SeqCst);
+        AudioProducer {
+            sender: self.sender.clone(),
+            producers: self.producers.clone(),
+            sink_gauge: self.sink_gauge.clone(),
+            audio_bytes: self.audio_bytes.clone(),
+        }
+    }
+}
+
+impl Drop for AudioProducer {
+    fn drop(&mut self) {
+        self.producers.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// futures Sink of audio bytes feeding audio sink of streaming recognizer,
+/// see Recognizer::into_sink_and_stream. Sink is ready only when audio sink has free
+/// capacity, i.e. sending (e.g. SinkExt::send or StreamExt::forward) waits while
+/// buffer_size chunks are queued and not yet sent to Google. Closing (or dropping)
+/// the sink ends audio stream.
+#[derive(Debug)]
+pub struct AudioStreamSink {
+    sender: PollSender<StreamingRecognizeRequest>,
+}
+
+impl Sink<Vec<u8>> for AudioStreamSink {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.sender
+            .poll_reserve(cx)
+            .map_err(|_| Error::new("Audio sink closed".to_string()))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, audio_bytes: Vec<u8>) -> Result<()> {
+        self.sender
+            .send_item(Recognizer::streaming_request_from_bytes(
+                audio_bytes,
+                String::new(),
+            ))
+            .map_err(|_| Error::new("Audio sink closed".to_string()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        // chunks are flushed into audio sink by start_send
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.sender.close();
+        Poll::Ready(Ok(()))
+    }
+}
+
 /// Google Speech API recognizer
 #[derive(Debug)]
 pub struct Recognizer {
@@ -64,11 +984,107 @@ pub struct Recognizer {
     /// For channel based streaming this is the internal channel sender
     /// where STT results will be sent. Library client is using respective
     /// receiver to get the results. See example recognizer_streaming for details
-    result_sender: Option<mpsc::Sender<StreamingRecognizeResponse>>,
-}
+    result_sender: Option<Arc<dyn ResultSink>>,
 
-impl Recognizer {
-    /// Creates new speech recognizer from provided
+    /// results dropped because result channel was full (see dropped_results)
+    dropped_results: Arc<AtomicU64>,
+
+    /// endpoint & connection details recorded at construction time
+    connection_info: ConnectionInfo,
+
+    /// broadcast of raw responses, created by subscribe_raw
+    raw_broadcast: Option<broadcast::Sender<StreamingRecognizeResponse>>,
+
+    /// broadcast of parsed transcripts, created by subscribe_transcripts
+    transcript_broadcast: Option<broadcast::Sender<StreamingTranscript>>,
+
+    /// hard cap of total streaming duration, see set_max_total_duration
+    max_total_duration: Option<Duration>,
+
+    /// wall-clock budget of single streaming call, see set_session_deadline
+    session_deadline: Option<Duration>,
+
+    /// instant when session deadline of current streaming call expires
+    session_deadline_at: Option<Instant>,
+
+    /// true if last streaming call was closed because of session deadline
+    deadline_reached: bool,
+
+    /// time when streaming was started for the first time
+    streaming_started: Option<Instant>,
+
+    /// optional prefix (e.g. session id) prepended to all log lines of this recognizer
+    log_prefix: Option<String>,
+
+    /// name of the recognizer for which streaming config was not sent yet,
+    /// see create_streaming_recognizer_deferred
+    pending_recognizer: Option<String>,
+
+    /// first request id seen in streaming responses
+    request_id: Option<String>,
+
+    /// validation of audio chunk alignment, see set_frame_validation
+    frame_validation: FrameValidation,
+
+    /// size of single LINEAR16 frame (all channels) in bytes,
+    /// known only when streaming config with explicit LINEAR16 decoding was sent
+    audio_frame_size: Option<usize>,
+
+    /// false when streaming config disabled interim results,
+    /// interim results received anyway are then dropped (see next_response)
+    interim_results: bool,
+
+    /// file into which outgoing streaming requests are recorded (see record_requests)
+    request_recorder: Option<Arc<Mutex<fs::File>>>,
+
+    /// file into which received streaming responses are recorded (see record_responses)
+    response_recorder: Option<fs::File>,
+
+    /// limiter of concurrent streams shared with other recognizers (see set_stream_limiter)
+    stream_limiter: Option<StreamLimiter>,
+
+    /// processors applied to final transcripts (see add_transcript_processor)
+    transcript_processors: Vec<Box<dyn TranscriptProcessor>>,
+
+    /// cache of recognize_bytes responses (see set_recognition_cache)
+    recognition_cache: Option<Arc<dyn RecognitionCache>>,
+
+    /// hits and misses of recognition cache
+    cache_counters: Arc<CacheCounters>,
+
+    /// interceptor used by clients, kept to acquire tokens ahead of time (see warm_up)
+    token_interceptor: TokenInterceptor,
+
+    /// outgoing requests are logged (redacted) at debug level (see set_log_requests)
+    log_requests: bool,
+
+    /// name of the recognizer used by streaming config (see reconfigure)
+    recognizer_name: String,
+
+    /// capacity of audio sink, None for non-streaming recognizers
+    audio_buffer_size: Option<usize>,
+
+    /// signalled when first audio chunk of current stream is sent (see first_audio_sent)
+    first_audio: Arc<FirstAudioSignal>,
+
+    /// end of audio requests and completion of running stream (see stream_closer)
+    stream_lifecycle: Arc<StreamLifecycle>,
+
+    /// audio bytes buffered in audio channel (see set_max_buffered_bytes)
+    audio_bytes: Arc<AudioBytesBudget>,
+
+    /// audio bytes sent by all streaming calls (see StreamSummary::audio_bytes_sent)
+    audio_bytes_sent: Arc<AtomicU64>,
+
+    /// fill level of audio sink above which warnings are logged (see set_sink_high_water_mark)
+    sink_high_water_mark: Option<f32>,
+
+    /// time of last high-water mark warning, shared by all audio handles
+    last_sink_warning: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Recognizer {
+    /// Creates new speech recognizer from provided
     /// Google credentials and google speech configuration.
     /// This kind of recognizer can be used for streaming recognition.
     pub async fn create_streaming_recognizer(
@@ -78,39 +1094,355 @@ impl Recognizer {
         config: StreamingRecognitionConfig,
         // Capacity of audio sink (tokio channel used by caller to send audio data).
         // If not provided defaults to 1000.
-        buffer_size: Option<usize>,        
+        buffer_size: Option<usize>,
         // Required. The name of the Recognizer to use during recognition. The expected format is projects/{project}/locations/{location}/recognizers/{recognizer}.
         // The {recognizer} segment may be set to _ to use an empty implicit Recognizer.
         recognizer: String,
     ) -> Result<Self> {
-        let channel = new_grpc_channel(GRPC_API_DOMAIN, GRPC_API_URL, None).await?;
-
         let token_header_val = get_token(google_credentials)?;
 
-        let speech_client =
-            SpeechClient::with_interceptor(channel, new_interceptor(token_header_val));
+        Recognizer::new_streaming(
+            None,
+            new_interceptor(token_header_val),
+            Some(config),
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
 
-        let (audio_sender, audio_receiver) =
-            mpsc::channel::<StreamingRecognizeRequest>(buffer_size.unwrap_or(1000));
+    /// Creates new speech recognizer from Google credentials stored
+    /// directly (as JSON string) in environment variable var_name.
+    /// Returns error with code CREDENTIALS_ENV if variable is not set or is empty.
+    pub async fn create_streaming_recognizer_from_env(
+        // Name of environment variable holding Google Cloud Platform JSON credentials
+        var_name: &str,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        let google_credentials = match env::var(var_name) {
+            Ok(credentials) if !credentials.trim().is_empty() => credentials,
+            _ => {
+                return Err(Error::new_with_code(
+                    format!(
+                        "Environment variable {} with Google credentials is not set or is empty",
+                        var_name
+                    ),
+                    "CREDENTIALS_ENV".to_string(),
+                ))
+            }
+        };
+
+        Recognizer::create_streaming_recognizer(google_credentials, config, buffer_size, recognizer)
+            .await
+    }
+
+    /// Creates new speech recognizer from provided token. Token is not validated, see
+    /// create_streaming_recognizer_from_token_with_validation.
+    pub async fn create_streaming_recognizer_from_token(
+        // Google auth token
+        token: String,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        //
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::create_streaming_recognizer_from_token_with_validation(
+            token,
+            config,
+            buffer_size,
+            recognizer,
+            false,
+        )
+        .await
+    }
+
+    /// Same as create_streaming_recognizer_from_token. If validate_clock is true, validity
+    /// window of JWT token is checked against local clock first (see common::validate_token),
+    /// i.e. confusing Unauthenticated errors caused by unsynchronized local clock are turned
+    /// into error with code CLOCK_SKEW returned before connecting to Google.
+    pub async fn create_streaming_recognizer_from_token_with_validation(
+        // Google auth token
+        token: String,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        //
+        recognizer: String,
+        // check token validity window against local clock
+        validate_clock: bool,
+    ) -> Result<Self> {
+        if validate_clock {
+            validate_token(&token)?;
+        }
+        Recognizer::new_streaming(
+            None,
+            new_interceptor(token.into()),
+            Some(config),
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
+
+    /// Creates new streaming speech recognizer which obtains (and refreshes)
+    /// auth tokens from provided credential provider. Token is refreshed before every call
+    /// on blocking thread pool, failing provider calls are retried with (non-blocking)
+    /// backoff. Persistent failure makes streaming_recognize (and other calls) return
+    /// error with code AUTH_REFRESH. The error can not be delivered through result channel,
+    /// get_streaming_result_receiver is typed as mpsc::Receiver<StreamingRecognizeResponse>
+    /// and carries responses only, the channel just receives no further responses.
+    /// Stream based variants (streaming_recognize_async_stream) yield the error as their
+    /// last item.
+    pub async fn create_streaming_recognizer_with_provider(
+        // Source of Google auth tokens
+        provider: impl CredentialProvider + 'static,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::new_streaming(
+            None,
+            new_provider_interceptor(Arc::new(provider)),
+            Some(config),
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
+
+    /// Creates new streaming speech recognizer from existing gouth token, e.g. token
+    /// shared with Dialogflow or other Google API clients. Credentials are not re-read,
+    /// token is refreshed (by gouth) through the shared handle once expired.
+    pub async fn create_streaming_recognizer_from_gouth_token(
+        // Shared gouth token
+        token: Arc<Token>,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::create_streaming_recognizer_with_provider(
+            token,
+            config,
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
+
+    /// Creates new streaming speech recognizer which does NOT send streaming config
+    /// automatically. Streaming config must be sent explicitly with send_streaming_config
+    /// (e.g. once language is decided) before audio sink can be retrieved and streaming started.
+    pub async fn create_streaming_recognizer_deferred(
+        // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+        google_credentials: impl AsRef<str>,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        let token_header_val = get_token(google_credentials)?;
+
+        Recognizer::new_streaming(
+            None,
+            new_interceptor(token_header_val),
+            None,
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
 
-        let streaming_config = StreamingRecognizeRequest {
+    /// Same as create_streaming_recognizer_deferred, but provided GRPC channel and token are used.
+    pub async fn create_streaming_recognizer_deferred_with_channel(
+        channel: Channel,
+        // Google auth token
+        token: String,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::new_streaming(
+            Some(channel),
+            new_interceptor(token.into()),
+            None,
+            buffer_size,
             recognizer,
-            streaming_request: Some(StreamingRequest::StreamingConfig(config)),
+        )
+        .await
+    }
+
+    /// Creates new streaming speech recognizer in raw stream mode, i.e. recognizer forwards
+    /// exactly what the caller sends to audio sink and does not inject streaming config.
+    /// Intended for replaying previously captured request streams. Caller is responsible
+    /// for sending StreamingRecognizeRequest with streaming config (and recognizer name)
+    /// as the very first message, send_streaming_config cannot be used.
+    pub async fn create_streaming_recognizer_raw(
+        // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+        google_credentials: impl AsRef<str>,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+    ) -> Result<Self> {
+        let token_header_val = get_token(google_credentials)?;
+        Recognizer::new_raw_streaming(None, new_interceptor(token_header_val), buffer_size).await
+    }
+
+    /// Same as create_streaming_recognizer_raw, but provided GRPC channel and token are used.
+    pub async fn create_streaming_recognizer_raw_with_channel(
+        channel: Channel,
+        // Google auth token
+        token: String,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+    ) -> Result<Self> {
+        Recognizer::new_raw_streaming(Some(channel), new_interceptor(token.into()), buffer_size)
+            .await
+    }
+
+    /// Sends streaming config as the first message of the stream. To be used with
+    /// recognizers created by create_streaming_recognizer_deferred, other streaming
+    /// recognizers send config automatically. Returns error with code STREAMING_CONFIG_SENT
+    /// if config was already sent, AUDIO_SINK_CLOSED if audio sink was dropped before
+    /// (see drop_audio_sink), recognizer then stays without config.
+    pub async fn send_streaming_config(
+        &mut self,
+        config: StreamingRecognitionConfig,
+    ) -> Result<()> {
+        // pending recognizer is consumed only once config can be sent
+        let (recognizer, audio_sender) = match (&self.pending_recognizer, &self.audio_sender) {
+            (Some(recognizer), Some(audio_sender)) => (recognizer.clone(), audio_sender.clone()),
+            (None, _) => {
+                return Err(Error::new_with_code(
+                    "Streaming config was already sent".to_string(),
+                    "STREAMING_CONFIG_SENT".to_string(),
+                ))
+            }
+            (Some(_), None) => {
+                return Err(Error::new_with_code(
+                    "Audio sink was dropped, streaming config cannot be sent".to_string(),
+                    "AUDIO_SINK_CLOSED".to_string(),
+                ))
+            }
         };
+        self.pending_recognizer = None;
 
-        audio_sender.send(streaming_config).await?;
+        self.audio_frame_size = match config
+            .config
+            .as_ref()
+            .and_then(|config| config.decoding_config.as_ref())
+        {
+            Some(DecodingConfig::ExplicitDecodingConfig(explicit))
+                if explicit.encoding == ExplicitAudioEncoding::Linear16 as i32 =>
+            {
+                Some(explicit.audio_channel_count.max(1) as usize * 2)
+            }
+            _ => None,
+        };
+        self.interim_results = config
+            .streaming_features
+            .as_ref()
+            .is_none_or(|features| features.interim_results);
 
-        Ok(Recognizer {
-            speech_client,
-            operations_client: None,
-            audio_sender: Some(audio_sender),
-            audio_receiver: Some(audio_receiver),
-            result_sender: None
-        })
+        audio_sender
+            .send(StreamingRecognizeRequest {
+                recognizer,
+                streaming_request: Some(StreamingRequest::StreamingConfig(config)),
+            })
+            .await?;
+        Ok(())
     }
 
-    /// Creates new speech recognizer from provided token
-    pub async fn create_streaming_recognizer_from_token(
+    /// Returns error with code STREAMING_CONFIG_MISSING if streaming config was not sent yet.
+    fn ensure_streaming_config_sent(&self) -> Result<()> {
+        if self.pending_recognizer.is_some() {
+            return Err(Error::new_with_code(
+                "Streaming config must be sent (send_streaming_config) before audio".to_string(),
+                "STREAMING_CONFIG_MISSING".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Creates new speech recognizer from provided
+    /// Google credentials. This kind of recognizer can be used
+    /// for long running recognition.
+    pub async fn create_asynchronous_recognizer(
+        google_credentials: impl AsRef<str>,
+    ) -> Result<Self> {
+        let token_header_val = get_token(google_credentials)?;
+
+        Recognizer::new_non_streaming(None, new_interceptor(token_header_val), true).await
+    }
+
+    /// Same as create_asynchronous_recognizer, existing (shared) gouth token is used.
+    pub async fn create_asynchronous_recognizer_from_gouth_token(
+        token: Arc<Token>,
+    ) -> Result<Self> {
+        Recognizer::create_asynchronous_recognizer_with_provider(token).await
+    }
+
+    /// Same as create_asynchronous_recognizer, auth tokens are obtained
+    /// from provided credential provider.
+    pub async fn create_asynchronous_recognizer_with_provider(
+        provider: impl CredentialProvider + 'static,
+    ) -> Result<Self> {
+        Recognizer::new_non_streaming(None, new_provider_interceptor(Arc::new(provider)), true)
+            .await
+    }
+
+    /// Creates new speech recognizer from provided
+    /// Google credentials. This kind of recognizer can be used
+    /// for synchronous recognition.
+    pub async fn create_synchronous_recognizer(
+        google_credentials: impl AsRef<str>,
+    ) -> Result<Self> {
+        let token_header_val = get_token(google_credentials)?;
+
+        Recognizer::new_non_streaming(None, new_interceptor(token_header_val), false).await
+    }
+
+    /// Same as create_synchronous_recognizer, existing (shared) gouth token is used.
+    pub async fn create_synchronous_recognizer_from_gouth_token(token: Arc<Token>) -> Result<Self> {
+        Recognizer::create_synchronous_recognizer_with_provider(token).await
+    }
+
+    /// Same as create_synchronous_recognizer, auth tokens are obtained
+    /// from provided credential provider.
+    pub async fn create_synchronous_recognizer_with_provider(
+        provider: impl CredentialProvider + 'static,
+    ) -> Result<Self> {
+        Recognizer::new_non_streaming(None, new_provider_interceptor(Arc::new(provider)), false)
+            .await
+    }
+
+    /// Creates new streaming speech recognizer using provided (already connected) GRPC channel,
+    /// e.g. channel pointing to mock server (see module testing) or custom endpoint.
+    /// Token is sent as authorization header value.
+    pub async fn create_streaming_recognizer_with_channel(
+        channel: Channel,
         // Google auth token
         token: String,
         //  Streaming recognition configuration
@@ -118,85 +1450,783 @@ impl Recognizer {
         // Capacity of audio sink (tokio channel used by caller to send audio data).
         // If not provided defaults to 1000.
         buffer_size: Option<usize>,
-        //
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::new_streaming(
+            Some(channel),
+            new_interceptor(token.into()),
+            Some(config),
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
+
+    /// Same as create_streaming_recognizer_with_provider but uses provided (already
+    /// connected) GRPC channel, e.g. channel pointing to mock server (see module testing).
+    pub async fn create_streaming_recognizer_with_provider_and_channel(
+        channel: Channel,
+        // Source of Google auth tokens
+        provider: impl CredentialProvider + 'static,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // Capacity of audio sink (tokio channel used by caller to send audio data).
+        // If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        Recognizer::new_streaming(
+            Some(channel),
+            new_provider_interceptor(Arc::new(provider)),
+            Some(config),
+            buffer_size,
+            recognizer,
+        )
+        .await
+    }
+
+    /// Creates streaming recognizer optimized for latency (e.g. live captioning) if low_latency
+    /// is true. Audio sink then has capacity LOW_LATENCY_BUFFER_SIZE, i.e. sending of audio chunk
+    /// waits until previous chunk was taken by GRPC stream and chunks never queue up in
+    /// the recognizer. Audio chunks are never coalesced, each chunk is sent as separate
+    /// GRPC message as soon as it is taken. Tradeoff is throughput: audio producer is slowed
+    /// down to the pace of the network (prefer default capacity for sending of recorded audio).
+    /// Leave gzip compression disabled for lowest latency.
+    pub async fn create_streaming_recognizer_low_latency(
+        // Google Cloud Platform JSON credentials for project with Speech APIs enabled
+        google_credentials: impl AsRef<str>,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // If true audio sink capacity is LOW_LATENCY_BUFFER_SIZE, otherwise default (1000)
+        low_latency: bool,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+    ) -> Result<Self> {
+        let token_header_val = get_token(google_credentials)?;
+
+        Recognizer::new_streaming(
+            None,
+            new_interceptor(token_header_val),
+            Some(config),
+            low_latency_buffer_size(low_latency),
+            recognizer,
+        )
+        .await
+    }
+
+    /// Same as create_streaming_recognizer_low_latency, but provided GRPC channel and token are used.
+    pub async fn create_streaming_recognizer_low_latency_with_channel(
+        channel: Channel,
+        // Google auth token
+        token: String,
+        //  Streaming recognition configuration
+        config: StreamingRecognitionConfig,
+        // If true audio sink capacity is LOW_LATENCY_BUFFER_SIZE, otherwise default (1000)
+        low_latency: bool,
+        // Required. The name of the Recognizer to use during recognition.
         recognizer: String,
     ) -> Result<Self> {
-        let channel = new_grpc_channel(GRPC_API_DOMAIN, GRPC_API_URL, None).await?;
+        Recognizer::new_streaming(
+            Some(channel),
+            new_interceptor(token.into()),
+            Some(config),
+            low_latency_buffer_size(low_latency),
+            recognizer,
+        )
+        .await
+    }
+
+    /// Same as create_asynchronous_recognizer, but provided GRPC channel and token are used.
+    pub async fn create_asynchronous_recognizer_with_channel(
+        channel: Channel,
+        token: String,
+    ) -> Result<Self> {
+        Recognizer::new_non_streaming(Some(channel), new_interceptor(token.into()), true).await
+    }
+
+    /// Same as create_synchronous_recognizer, but provided GRPC channel and token are used.
+    pub async fn create_synchronous_recognizer_with_channel(
+        channel: Channel,
+        token: String,
+    ) -> Result<Self> {
+        Recognizer::new_non_streaming(Some(channel), new_interceptor(token.into()), false).await
+    }
+
+    /// Returns log prefix formatted for log lines, empty string if no prefix is set.
+    fn log_tag(&self) -> String {
+        match &self.log_prefix {
+            Some(log_prefix) => format!("[{}] ", log_prefix),
+            None => String::new(),
+        }
+    }
+
+    /// Receives next streaming response. Returns None when stream is closed
+    /// or when max_total_duration is exceeded. Records first seen request id
+    /// and logs it when stream fails. If interim results were disabled
+    /// in streaming config, interim results are never forwarded.
+    async fn next_response(
+        &mut self,
+        response_stream: &mut Streaming<StreamingRecognizeResponse>,
+    ) -> Result<Option<StreamingRecognizeResponse>> {
+        let response = loop {
+            let mut response = self.next_response_with_deadline(response_stream).await;
+            if let Ok(Some(response)) = &mut response {
+                if !self.prepare_response(response) {
+                    continue;
+                }
+            }
+            break response;
+        };
+        if let (Ok(Some(response)), Some(recorder)) = (&response, &mut self.response_recorder) {
+            if let Err(error) = write_delimited(recorder, response) {
+                warn!(
+                    "{}streaming_recognize: recording of response failed: {}",
+                    self.log_tag(),
+                    error.message
+                );
+            }
+        }
+        match &response {
+            Ok(Some(response)) if self.request_id.is_none() => {
+                self.request_id = request_id(response);
+            }
+            Err(error) => error!(
+                "{}streaming_recognize: error (request id {:?}): {}",
+                self.log_tag(),
+                self.request_id,
+                error.message
+            ),
+            _ => {}
+        }
+        response
+    }
+
+    async fn next_response_with_deadline(
+        &mut self,
+        response_stream: &mut Streaming<StreamingRecognizeResponse>,
+    ) -> Result<Option<StreamingRecognizeResponse>> {
+        let started = *self.streaming_started.get_or_insert_with(Instant::now);
+        let total_deadline = self
+            .max_total_duration
+            .map(|max_total_duration| started + max_total_duration);
+        let deadline = match (total_deadline, self.session_deadline_at) {
+            (Some(total_deadline), Some(session_deadline)) => {
+                Some(total_deadline.min(session_deadline))
+            }
+            (total_deadline, session_deadline) => total_deadline.or(session_deadline),
+        };
+        let deadline = match deadline {
+            Some(deadline) => deadline,
+            None => return Ok(response_stream.message().await?),
+        };
+        match tokio::time::timeout_at(
+            tokio::time::Instant::from_std(deadline),
+            response_stream.message(),
+        )
+        .await
+        {
+            Ok(response) => Ok(response?),
+            Err(_) => {
+                if Some(deadline) == self.session_deadline_at {
+                    warn!(
+                        "{}streaming_recognize: session deadline {:?} reached, closing stream",
+                        self.log_tag(),
+                        self.session_deadline
+                    );
+                    self.deadline_reached = true;
+                } else {
+                    warn!(
+                        "{}streaming_recognize: max total duration {:?} exceeded, closing stream",
+                        self.log_tag(),
+                        self.max_total_duration
+                    );
+                }
+                self.drop_audio_sink();
+                Ok(None)
+            }
+        }
+    }
+
+    /// Filters interim results (see filter_interim_results) and applies transcript processors
+    /// to final results. Returns false if response should not be forwarded at all.
+    fn prepare_response(&self, response: &mut StreamingRecognizeResponse) -> bool {
+        if !self.filter_interim_results(response) {
+            return false;
+        }
+        if !self.transcript_processors.is_empty() {
+            for result in response.results.iter_mut().filter(|result| result.is_final) {
+                for alternative in &mut result.alternatives {
+                    let transcript = std::mem::take(&mut alternative.transcript);
+                    alternative.transcript =
+                        process_transcript(&self.transcript_processors, transcript);
+                }
+            }
+        }
+        true
+    }
+
+    /// Drops interim results of response if interim results were disabled in streaming config
+    /// (consistency guard, API should not send them at all). Returns false if nothing but interim
+    /// results was received, i.e. if response should not be forwarded at all.
+    fn filter_interim_results(&self, response: &mut StreamingRecognizeResponse) -> bool {
+        if self.interim_results || response.results.iter().all(|result| result.is_final) {
+            return true;
+        }
+        warn!(
+            "{}streaming_recognize: interim results received although disabled, dropping them",
+            self.log_tag()
+        );
+        response.results.retain(|result| result.is_final);
+        !response.results.is_empty() || response.speech_event_type != 0
+    }
+
+    /// Wraps audio receiver into request stream validating audio chunks (see FrameValidation).
+    /// Requests already received (see receive_queued_until_audio) are sent first.
+    /// In strict mode stream ends with the first misaligned chunk, length of which
+    /// is stored into returned cell (see check_frame_alignment).
+    fn validated_request_stream(
+        &self,
+        received: Vec<StreamingRecognizeRequest>,
+        audio_receiver: mpsc::Receiver<StreamingRecognizeRequest>,
+    ) -> (
+        impl Stream<Item = StreamingRecognizeRequest>,
+        Arc<Mutex<Option<usize>>>,
+    ) {
+        let misaligned = Arc::new(Mutex::new(None));
+        let misaligned_chunk = misaligned.clone();
+        let frame_validation = self.frame_validation;
+        let frame_size = self.audio_frame_size;
+        let log_tag = self.log_tag();
+
+        let request_stream = tokio_stream::iter(received)
+            .chain(self.stream_lifecycle.closable_audio(audio_receiver))
+            .take_while(move |request| {
+            let chunk_len = match (frame_size, &request.streaming_request) {
+                (Some(frame_size), Some(StreamingRequest::Audio(audio_bytes)))
+                    if audio_bytes.len() % frame_size != 0 =>
+                {
+                    audio_bytes.len()
+                }
+                _ => return true,
+            };
+            match frame_validation {
+                FrameValidation::Disabled => true,
+                FrameValidation::Log => {
+                    warn!(
+                        "{}streaming_recognize: audio chunk of {} bytes is not aligned to LINEAR16 frames ({:?} bytes)",
+                        log_tag, chunk_len, frame_size
+                    );
+                    true
+                }
+                FrameValidation::Strict => {
+                    if let Ok(mut misaligned_chunk) = misaligned_chunk.lock() {
+                        *misaligned_chunk = Some(chunk_len);
+                    }
+                    false
+                }
+            }
+        });
+
+        let recorder = self.request_recorder.clone();
+        let log_requests = self.log_requests;
+        let log_tag = self.log_tag();
+        let first_audio = self.first_audio.clone();
+        let audio_bytes = self.audio_bytes.clone();
+        let audio_bytes_sent = self.audio_bytes_sent.clone();
+        let request_stream = request_stream.map(move |request| {
+            first_audio.observe(&request);
+            audio_bytes.release_request(&request);
+            if let Some(StreamingRequest::Audio(audio)) = &request.streaming_request {
+                audio_bytes_sent.fetch_add(audio.len() as u64, Ordering::Relaxed);
+            }
+            if log_requests {
+                debug!(
+                    "{}streaming_recognize: sending {}",
+                    log_tag,
+                    redact_streaming_request(&request)
+                );
+            }
+            if let Some(recorder) = &recorder {
+                let recorded = match recorder.lock() {
+                    Ok(mut file) => write_delimited(&mut *file, &request),
+                    Err(_) => Err(Error::new("Request recorder lock poisoned".to_string())),
+                };
+                if let Err(error) = recorded {
+                    warn!(
+                        "{}streaming_recognize: recording of request failed: {}",
+                        log_tag, error.message
+                    );
+                }
+            }
+            request
+        });
+
+        (request_stream, misaligned)
+    }
+
+    /// Returns error with code FRAME_MISALIGNED if strict frame validation terminated audio stream.
+    fn check_frame_alignment(&self, misaligned: &Mutex<Option<usize>>) -> Result<()> {
+        match misaligned.lock().ok().and_then(|misaligned| *misaligned) {
+            Some(chunk_len) => Err(Error::new_with_code(
+                format!(
+                    "Audio chunk of {} bytes is not aligned to LINEAR16 frames ({:?} bytes), audio stream was terminated",
+                    chunk_len, self.audio_frame_size
+                ),
+                "FRAME_MISALIGNED".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Sends response to broadcast subscribers (if any). Broadcast send fails only
+    /// when there are no active receivers, in which case response is dropped.
+    fn publish(&self, response: &StreamingRecognizeResponse) {
+        if let Some(raw_broadcast) = &self.raw_broadcast {
+            let _ = raw_broadcast.send(response.clone());
+        }
+        if let Some(transcript_broadcast) = &self.transcript_broadcast {
+            for transcript in StreamingTranscript::from_response(response) {
+                let _ = transcript_broadcast.send(transcript);
+            }
+        }
+    }
+
+    /// Common start of all streaming calls: checks that streaming config was sent, starts
+    /// session deadline, acquires permit of stream limiter, refreshes token and opens
+    /// streaming call with validated request stream. Returns None if audio receiver was already consumed.
+    /// Once abort (if any) is signalled both request and response streams end.
+    async fn open_stream(
+        &mut self,
+        abort: Option<&watch::Sender<bool>>,
+    ) -> Result<Option<OpenStream>> {
+        self.ensure_streaming_config_sent()?;
+        self.start_session_deadline();
+        let stream_permit = match &self.stream_limiter {
+            Some(stream_limiter) => Some(stream_limiter.acquire().await?),
+            None => None,
+        };
+        self.token_interceptor.refresh().await?;
+        // yank self.audio_receiver so that we can consume it
+        let mut audio_receiver = match self.audio_receiver.take() {
+            Some(audio_receiver) => audio_receiver,
+            None => return Ok(None),
+        };
+        let running = self.stream_lifecycle.start();
+        let received = receive_queued_until_audio(&mut audio_receiver)?;
+        trace!("{}streaming_recognize: starting stream", self.log_tag());
+        let (request_stream, misaligned) = self.validated_request_stream(received, audio_receiver);
+        let request_stream =
+            abortable_requests(request_stream, abort.map(|abort| abort.subscribe()));
+        let streaming_recognize_result: StdResult<
+            TonicResponse<Streaming<StreamingRecognizeResponse>>,
+            TonicStatus,
+        > = self.speech_client.streaming_recognize(request_stream).await;
+
+        Ok(Some(OpenStream {
+            response_stream: streaming_recognize_result?.into_inner(),
+            misaligned,
+            abort: abort.map(|abort| abort.subscribe()),
+            _stream_permit: stream_permit,
+            _running: running,
+        }))
+    }
+
+    /// Receives next response of opened stream (see next_response) and publishes it
+    /// to broadcast subscribers. Returns None once the stream is closed, or error with
+    /// code FRAME_MISALIGNED if strict frame validation terminated audio stream.
+    async fn next_stream_response(
+        &mut self,
+        stream: &mut OpenStream,
+    ) -> Result<Option<StreamingRecognizeResponse>> {
+        let response = tokio::select! {
+            biased;
+            _ = abort_requested(&mut stream.abort) => None,
+            response = self.next_response(&mut stream.response_stream) => response?,
+        };
+        match response {
+            Some(response) => {
+                self.publish(&response);
+                Ok(Some(response))
+            }
+            None => {
+                trace!("{}streaming_recognize: stream closed", self.log_tag());
+                self.check_frame_alignment(&stream.misaligned)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Drives streaming call to completion, every response is passed to on_response.
+    /// Error returned by on_response terminates the stream.
+    async fn run_stream<F, Fut>(&mut self, mut on_response: F) -> Result<()>
+    where
+        F: FnMut(StreamingRecognizeResponse) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        if let Some(mut stream) = self.open_stream(None).await? {
+            while let Some(response) = self.next_stream_response(&mut stream).await? {
+                on_response(response).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns injected channel (endpoint of which is not known) or creates
+    /// new channel connected to Google Speech API.
+    async fn channel_or_default(channel: Option<Channel>) -> Result<(Channel, ConnectionInfo)> {
+        match channel {
+            Some(channel) => Ok((
+                channel,
+                ConnectionInfo::new("", "", ConnectionState::Unknown),
+            )),
+            None => Ok((
+                new_grpc_channel(GRPC_API_DOMAIN, GRPC_API_URL, None).await?,
+                ConnectionInfo::new(GRPC_API_DOMAIN, GRPC_API_URL, ConnectionState::Connected),
+            )),
+        }
+    }
+
+    /// Creates streaming recognizer and sends initial streaming config into audio sink.
+    async fn new_streaming(
+        channel: Option<Channel>,
+        interceptor: TokenInterceptor,
+        config: Option<StreamingRecognitionConfig>,
+        buffer_size: Option<usize>,
+        recognizer: String,
+    ) -> Result<Self> {
+        let (channel, connection_info) = Recognizer::channel_or_default(channel).await?;
+
+        let token_interceptor = interceptor.clone();
+        let speech_client = SpeechClient::with_interceptor(channel, interceptor);
+
+        let (audio_sender, audio_receiver) =
+            mpsc::channel::<StreamingRecognizeRequest>(buffer_size.unwrap_or(1000));
+
+        let mut recognizer = Recognizer {
+            speech_client,
+            operations_client: None,
+            audio_sender: Some(audio_sender),
+            audio_receiver: Some(audio_receiver),
+            result_sender: None,
+            dropped_results: Arc::new(AtomicU64::new(0)),
+            connection_info,
+            raw_broadcast: None,
+            transcript_broadcast: None,
+            max_total_duration: None,
+            session_deadline: None,
+            session_deadline_at: None,
+            deadline_reached: false,
+            streaming_started: None,
+            log_prefix: None,
+            pending_recognizer: Some(recognizer.clone()),
+            request_id: None,
+            frame_validation: FrameValidation::Disabled,
+            audio_frame_size: None,
+            interim_results: true,
+            request_recorder: None,
+            response_recorder: None,
+            stream_limiter: None,
+            recognition_cache: None,
+            transcript_processors: vec![],
+            cache_counters: Arc::new(CacheCounters::default()),
+            token_interceptor,
+            log_requests: false,
+            sink_high_water_mark: None,
+            last_sink_warning: Arc::new(Mutex::new(None)),
+            first_audio: Arc::new(FirstAudioSignal::default()),
+            stream_lifecycle: StreamLifecycle::new(),
+            audio_bytes: Arc::new(AudioBytesBudget::default()),
+            audio_bytes_sent: Arc::new(AtomicU64::new(0)),
+            recognizer_name: recognizer,
+            audio_buffer_size: Some(buffer_size.unwrap_or(1000)),
+        };
+
+        if let Some(config) = config {
+            recognizer.send_streaming_config(config).await?;
+        }
+
+        Ok(recognizer)
+    }
+
+    /// Creates streaming recognizer which neither sends nor requires streaming config.
+    async fn new_raw_streaming(
+        channel: Option<Channel>,
+        interceptor: TokenInterceptor,
+        buffer_size: Option<usize>,
+    ) -> Result<Self> {
+        let mut recognizer =
+            Recognizer::new_streaming(channel, interceptor, None, buffer_size, String::new())
+                .await?;
+        // nothing is pending, i.e. audio sink is available right away
+        recognizer.pending_recognizer = None;
+        Ok(recognizer)
+    }
+
+    /// Creates synchronous recognizer, or asynchronous one (with operations client) if requested.
+    async fn new_non_streaming(
+        channel: Option<Channel>,
+        interceptor: TokenInterceptor,
+        with_operations: bool,
+    ) -> Result<Self> {
+        let (channel, connection_info) = Recognizer::channel_or_default(channel).await?;
+
+        let operations_client = if with_operations {
+            Some(OperationsClient::with_interceptor(
+                channel.clone(),
+                interceptor.clone(),
+            ))
+        } else {
+            None
+        };
+
+        let token_interceptor = interceptor.clone();
+        let speech_client = SpeechClient::with_interceptor(channel, interceptor);
+
+        Ok(Recognizer {
+            speech_client,
+            operations_client,
+            audio_sender: None,
+            audio_receiver: None,
+            result_sender: None,
+            dropped_results: Arc::new(AtomicU64::new(0)),
+            connection_info,
+            raw_broadcast: None,
+            transcript_broadcast: None,
+            max_total_duration: None,
+            session_deadline: None,
+            session_deadline_at: None,
+            deadline_reached: false,
+            streaming_started: None,
+            log_prefix: None,
+            pending_recognizer: None,
+            request_id: None,
+            frame_validation: FrameValidation::Disabled,
+            audio_frame_size: None,
+            interim_results: true,
+            request_recorder: None,
+            response_recorder: None,
+            stream_limiter: None,
+            recognition_cache: None,
+            transcript_processors: vec![],
+            cache_counters: Arc::new(CacheCounters::default()),
+            token_interceptor,
+            log_requests: false,
+            sink_high_water_mark: None,
+            last_sink_warning: Arc::new(Mutex::new(None)),
+            first_audio: Arc::new(FirstAudioSignal::default()),
+            stream_lifecycle: StreamLifecycle::new(),
+            audio_bytes: Arc::new(AudioBytesBudget::default()),
+            audio_bytes_sent: Arc::new(AtomicU64::new(0)),
+            recognizer_name: String::new(),
+            audio_buffer_size: None,
+        })
+    }
+
+    /// Sets hard cap of total streaming duration (guardrail against runaway cost of stuck
+    /// streams). Once exceeded, streaming call (any streaming_recognize variant or stream API)
+    /// closes the stream, logs warning and returns normally (with results accumulated so far).
+    /// Duration is measured from the first streaming call, i.e. it is total lifetime
+    /// of the recognizer streaming, not per stream. Disabled (None) by default.
+    pub fn set_max_total_duration(&mut self, max_total_duration: Option<Duration>) {
+        self.max_total_duration = max_total_duration;
+    }
+
+    /// Sets fixed wall-clock budget of single streaming call (e.g. 30 seconds for voice
+    /// command) measured from the start of streaming call (any streaming_recognize variant
+    /// or stream API, e.g. streaming_recognize_async_stream). Once reached, the stream is closed
+    /// and the call returns (stream ends) normally with results received so far,
+    /// StreamSummary.deadline_reached
+    /// (or deadline_reached) is then set. Unlike set_max_total_duration, budget is renewed
+    /// with every streaming call. Disabled (None) by default.
+    pub fn set_session_deadline(&mut self, session_deadline: Option<Duration>) {
+        self.session_deadline = session_deadline;
+    }
+
+    /// Returns true if last streaming call was closed because session deadline was reached.
+    pub fn deadline_reached(&self) -> bool {
+        self.deadline_reached
+    }
+
+    /// Starts session deadline (if set) of streaming call.
+    fn start_session_deadline(&mut self) {
+        self.deadline_reached = false;
+        self.session_deadline_at = self
+            .session_deadline
+            .map(|session_deadline| Instant::now() + session_deadline);
+    }
 
-        let speech_client =
-            SpeechClient::with_interceptor(channel, new_interceptor(token.into()));
+    /// Sets validation of LINEAR16 audio chunk alignment (disabled by default).
+    /// Applies to all audio sent by streaming calls (any streaming_recognize variant
+    /// or stream API, e.g. streaming_recognize_async_stream).
+    pub fn set_frame_validation(&mut self, frame_validation: FrameValidation) {
+        self.frame_validation = frame_validation;
+    }
 
-        let (audio_sender, audio_receiver) =
-            mpsc::channel::<StreamingRecognizeRequest>(buffer_size.unwrap_or(1000));
+    /// Sets limiter of concurrent streams shared by recognizers using the same channel
+    /// (see common::PooledChannel::with_max_concurrent_streams). All streaming calls
+    /// (streaming_recognize and its variants, streaming_recognize_async_stream, stream of
+    /// into_sink_and_stream) then wait for (or fail with STREAM_LIMIT_EXCEEDED without)
+    /// free stream slot.
+    pub fn set_stream_limiter(&mut self, stream_limiter: StreamLimiter) {
+        self.stream_limiter = Some(stream_limiter);
+    }
 
-        let streaming_config = StreamingRecognizeRequest {
-            recognizer,
-            streaming_request: Some(StreamingRequest::StreamingConfig(config)),
-        };
+    /// Registers processor applied to transcripts of final streaming results (e.g.
+    /// processing::MaskWords). Processors run in order of registration before results
+    /// are forwarded to result receiver, streams and subscribers (subscribe_raw included).
+    pub fn add_transcript_processor(&mut self, processor: impl TranscriptProcessor + 'static) {
+        self.transcript_processors.push(Box::new(processor));
+    }
 
-        audio_sender.send(streaming_config).await?;
+    /// Enables caching of recognize_bytes responses in provided cache (e.g.
+    /// cache::LruRecognitionCache), None disables caching. Cache can be shared
+    /// by multiple recognizers.
+    pub fn set_recognition_cache(&mut self, recognition_cache: Option<Arc<dyn RecognitionCache>>) {
+        self.recognition_cache = recognition_cache;
+    }
 
-        Ok(Recognizer {
-            speech_client,
-            operations_client: None,
-            audio_sender: Some(audio_sender),
-            audio_receiver: Some(audio_receiver),
-            result_sender: None
-        })
+    /// Returns number of recognize_bytes calls answered from recognition cache (hits)
+    /// and sent to Google (misses) while cache was set.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache_counters.stats()
     }
 
-    /// Creates new speech recognizer from provided
-    /// Google credentials. This kind of recognizer can be used
-    /// for long running recognition.
-    pub async fn create_asynchronous_recognizer(
-        google_credentials: impl AsRef<str>
-    ) -> Result<Self> {
-        let channel = new_grpc_channel(GRPC_API_DOMAIN, GRPC_API_URL, None).await?;
+    /// Records all outgoing streaming requests (streaming config included) into file
+    /// (created or truncated) as length-delimited protobuf messages. Recording can be
+    /// replayed with recording::replay_requests. Applies to all requests sent by streaming
+    /// calls (any streaming_recognize variant or stream API).
+    pub fn record_requests(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.request_recorder = Some(Arc::new(Mutex::new(fs::File::create(path)?)));
+        Ok(())
+    }
 
-        let token_header_val = get_token(google_credentials)?;
+    /// Records all received streaming responses into file (created or truncated) as
+    /// length-delimited protobuf messages, e.g. to capture golden outputs for tests.
+    /// Recording can be loaded with recording::read_responses. Applies to all responses
+    /// received by streaming calls (any streaming_recognize variant or stream API).
+    pub fn record_responses(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        self.response_recorder = Some(fs::File::create(path)?);
+        Ok(())
+    }
 
-        let speech_client = SpeechClient::with_interceptor(
-            channel.clone(),
-            new_interceptor(token_header_val.clone()),
-        );
+    /// Sets fill level of audio sink (0.0 - 1.0, e.g. 0.8 = 80% full) above which warning
+    /// is logged (at most once per 5 seconds) before audio producers start to block (or
+    /// AudioCallbackSink starts to drop audio). Checked on every send of AudioProducer
+    /// and AudioCallbackSink created afterwards. Disabled by default.
+    pub fn set_sink_high_water_mark(&mut self, high_water_mark: Option<f32>) {
+        self.sink_high_water_mark = high_water_mark;
+    }
 
-        let operations_client =
-            OperationsClient::with_interceptor(channel, new_interceptor(token_header_val));
+    /// Returns fill level of audio sink (0.0 - 1.0) computed from channel capacity and
+    /// number of queued requests, i.e. early sign of backpressure. Recognizer must hold
+    /// audio sink (see get_audio_sink), otherwise 0.0 is returned, use sink_pressure
+    /// of AudioProducer or AudioCallbackSink instead.
+    pub fn sink_pressure(&self) -> f32 {
+        match &self.audio_sender {
+            Some(audio_sender) => self.sink_gauge().pressure(audio_sender),
+            None => 0.0,
+        }
+    }
 
-        Ok(Recognizer {
-            speech_client,
-            operations_client: Some(operations_client),
-            audio_sender: None,
-            audio_receiver: None,
-            result_sender: None
-        })
+    /// Limits total size of audio buffered in audio sink (enqueued but not yet sent) to
+    /// max_buffered_bytes, None (default) disables the limit. Limit applies to audio sent
+    /// by AudioProducer (send_bytes waits) and AudioCallbackSink (chunk is dropped) created
+    /// by this recognizer, senders obtained by get_audio_sink/take_audio_sink bypass it.
+    /// Limit is independent of buffer_size (message count), i.e. producers are blocked
+    /// by whichever of both limits is reached first. Single chunk larger than the limit
+    /// is accepted when nothing is buffered.
+    pub fn set_max_buffered_bytes(&mut self, max_buffered_bytes: Option<usize>) {
+        self.audio_bytes
+            .max
+            .store(max_buffered_bytes.unwrap_or(0), Ordering::Relaxed);
     }
 
-    /// Creates new speech recognizer from provided
-    /// Google credentials. This kind of recognizer can be used
-    /// for synchronous recognition.
-    pub async fn create_synchronous_recognizer(
-        google_credentials: impl AsRef<str>
-    ) -> Result<Self> {
-        let channel = new_grpc_channel(GRPC_API_DOMAIN, GRPC_API_URL, None).await?;
+    /// Returns number of audio bytes buffered in audio sink by AudioProducer/AudioCallbackSink
+    /// (see set_max_buffered_bytes), not yet sent to Google.
+    pub fn buffered_bytes(&self) -> usize {
+        self.audio_bytes.buffered.load(Ordering::Acquire)
+    }
 
-        let token_header_val = get_token(google_credentials)?;
+    /// Returns future which resolves once the first audio chunk of current stream is
+    /// actually sent, i.e. pulled from audio channel by GRPC request stream (not just
+    /// enqueued into audio sink). Resolves immediately if it was already sent. Can be used
+    /// to measure enqueue-to-wire latency or to detect audio stuck in the channel.
+    /// Signal is reset by reconfigure (futures created before keep waiting for old stream).
+    pub fn first_audio_sent(&self) -> impl Future<Output = ()> + Send + 'static {
+        let first_audio = self.first_audio.clone();
+        async move { first_audio.wait().await }
+    }
 
-        let speech_client =
-            SpeechClient::with_interceptor(channel, new_interceptor(token_header_val));
+    fn sink_gauge(&self) -> SinkGauge {
+        SinkGauge {
+            capacity: self.audio_buffer_size.unwrap_or(0),
+            high_water_mark: self.sink_high_water_mark,
+            last_warning: self.last_sink_warning.clone(),
+            log_tag: self.log_tag(),
+        }
+    }
 
-        Ok(Recognizer {
-            speech_client,
-            operations_client: None,
-            audio_sender: None,
-            audio_receiver: None,
-            result_sender: None
-        })
+    /// Enables logging of outgoing requests at debug level, e.g. to diagnose why Google
+    /// rejects config. Audio bytes are always redacted (replaced by their length, see
+    /// redact_streaming_request and redact_recognize_request). Applies to recognize,
+    /// batch_recognize and all streaming requests (streaming config included).
+    pub fn set_log_requests(&mut self, log_requests: bool) {
+        self.log_requests = log_requests;
+    }
+
+    /// Sets prefix (e.g. session id) which is prepended to all log lines
+    /// of this recognizer. Allows to correlate logs of many concurrently
+    /// running recognizers.
+    pub fn set_log_prefix(&mut self, log_prefix: impl Into<String>) {
+        self.log_prefix = Some(log_prefix.into());
+    }
+
+    /// Enables gzip compression of GRPC requests and decompression of gzip compressed
+    /// responses. Compression is disabled by default. Might be useful for high volume
+    /// transcription over metered links, raw audio (LINEAR16) usually compresses well.
+    /// Must be called before recognition / streaming is started.
+    pub fn enable_gzip_compression(&mut self) {
+        self.speech_client = self
+            .speech_client
+            .clone()
+            .send_compressed(CompressionEncoding::Gzip)
+            .accept_compressed(CompressionEncoding::Gzip);
+    }
+
+    /// Returns first request id (generated by Google) seen in streaming responses.
+    pub fn request_id(&self) -> Option<String> {
+        self.request_id.clone()
+    }
+
+    /// Acquires auth token ahead of time, e.g. during application startup, so that first
+    /// recognize call (or first utterance) is not slowed down by token acquisition. Token
+    /// is cached by credential provider, calling this method again refreshes the cached
+    /// token if expired. Provider is called on blocking thread pool, i.e. this method does
+    /// not block the executor. Recognizers created from static token have nothing to acquire.
+    ///
+    /// Connectivity check is out of scope: default channels are already connected when
+    /// recognizer is created (see connection_info), readiness of custom (lazy) channels
+    /// does not prove that endpoint is reachable and Speech API has no cheap RPC which
+    /// would not require additional permissions or a recognizer resource.
+    pub async fn warm_up(&mut self) -> Result<()> {
+        self.token_interceptor.refresh().await
+    }
+
+    /// Returns information about endpoint this recognizer is connected to.
+    /// Useful for diagnostics, e.g. to check whether global or regional endpoint is used.
+    pub fn connection_info(&self) -> ConnectionInfo {
+        self.connection_info.clone()
     }
 
     /// Returns sender than can be used to stream in audio bytes. This method can be called
-    /// multiple times to retrieve multiple senders.
+    /// multiple times to retrieve multiple senders. Returns None if streaming config
+    /// was not sent yet (see create_streaming_recognizer_deferred).
     pub fn get_audio_sink(&mut self) -> Option<mpsc::Sender<StreamingRecognizeRequest>> {
+        if self.ensure_streaming_config_sent().is_err() {
+            return None;
+        }
         if let Some(audio_sender) = &self.audio_sender {
             Some(audio_sender.clone())
         } else {
@@ -207,8 +2237,11 @@ impl Recognizer {
     /// Returns sender than can be used to stream in audio bytes. This method will take
     /// the sender out of the option leaving None in its place. No additional sender
     /// can be retrieved from recognizer after this call. When sender is dropped respective
-    /// stream will be closed.
+    /// stream will be closed. Returns None if streaming config was not sent yet.
     pub fn take_audio_sink(&mut self) -> Option<mpsc::Sender<StreamingRecognizeRequest>> {
+        if self.ensure_streaming_config_sent().is_err() {
+            return None;
+        }
         if let Some(audio_sender) = self.audio_sender.take() {
             Some(audio_sender)
         } else {
@@ -216,11 +2249,55 @@ impl Recognizer {
         }
     }
 
+    /// Returns audio producer handle which can be cloned into multiple producer tasks.
+    /// Audio sink is taken out of recognizer (see take_audio_sink), i.e. audio stream is
+    /// closed once all producers are closed or dropped. Returns None if audio sink was
+    /// already taken or streaming config was not sent yet.
+    pub fn audio_producer_handle(&mut self) -> Option<AudioProducer> {
+        let sink_gauge = self.sink_gauge();
+        let audio_bytes = self.audio_bytes.clone();
+        self.take_audio_sink().map(|sender| AudioProducer {
+            sender,
+            producers: Arc::new(AtomicUsize::new(1)),
+            sink_gauge,
+            audio_bytes,
+        })
+    }
+
     /// Drops audio sender so that respective stream can be closed.
     pub fn drop_audio_sink(&mut self) {
         self.audio_sender.take();
     }
 
+    /// Signals end of audio. v2 API has no explicit end-of-audio message, end of audio
+    /// is signalled by half-closing request stream, i.e. this method drops audio sink
+    /// held by recognizer (same as drop_audio_sink). Stream is half-closed once all
+    /// senders obtained via get_audio_sink/audio_callback_sink are dropped as well;
+    /// responses for already sent audio are still received afterwards.
+    /// Returns error (code STREAMING_CONFIG_MISSING) if streaming config was not sent yet.
+    pub fn send_end_of_audio(&mut self) -> Result<()> {
+        self.ensure_streaming_config_sent()?;
+        self.drop_audio_sink();
+        Ok(())
+    }
+
+    /// Requests finalization of pending interim results, e.g. when application's own
+    /// VAD detected end of speech. v2 API has no message forcing finalization within open
+    /// stream, the only supported signal is end of audio. This method therefore falls back
+    /// to send_end_of_audio: request stream is half-closed (once all senders obtained via
+    /// get_audio_sink/audio_callback_sink are dropped), Google finalizes pending results
+    /// and closes the stream. Use reconfigure to continue with new stream. To have Google
+    /// finalize sooner within open stream set streaming_features.voice_activity_timeout
+    /// (speech_end_timeout) or endpointing_sensitivity in streaming config instead.
+    /// Returns error (code STREAMING_CONFIG_MISSING) if streaming config was not sent yet.
+    pub fn request_finalize(&mut self) -> Result<()> {
+        debug!(
+            "{}request_finalize: finalization within stream is not supported, ending audio",
+            self.log_tag()
+        );
+        self.send_end_of_audio()
+    }
+
     /// Returns receiver that can be used to receive speech-to-text results
     /// used with streaming_recognize function.
     pub fn get_streaming_result_receiver(
@@ -228,84 +2305,570 @@ impl Recognizer {
         // buffer size for tokio channel. If not provided defaults to 1000.
         buffer_size: Option<usize>,
     ) -> mpsc::Receiver<StreamingRecognizeResponse> {
-        let (result_sender, result_receiver) =
-            mpsc::channel::<StreamingRecognizeResponse>(buffer_size.unwrap_or(1000));
+        self.get_streaming_result_receiver_with_overflow(buffer_size, ResultOverflowPolicy::Block)
+    }
+
+    /// Same as get_streaming_result_receiver, but overflow_policy specifies what happens
+    /// when channel is full because consumer is slow. With ResultOverflowPolicy::Block reception
+    /// from Google waits for the consumer (and stream can eventually time out), other
+    /// policies drop results instead, see dropped_results.
+    pub fn get_streaming_result_receiver_with_overflow(
+        &mut self,
+        // buffer size for tokio channel. If not provided defaults to 1000.
+        buffer_size: Option<usize>,
+        overflow_policy: ResultOverflowPolicy,
+    ) -> mpsc::Receiver<StreamingRecognizeResponse> {
+        let (result_sender, result_receiver) = result_channel(
+            buffer_size.unwrap_or(1000),
+            overflow_policy,
+            self.dropped_results.clone(),
+        );
         self.result_sender = Some(result_sender);
         result_receiver
     }
 
+    /// Returns number of results dropped so far because result channel was full
+    /// (see get_streaming_result_receiver_with_overflow).
+    pub fn dropped_results(&self) -> u64 {
+        self.dropped_results.load(Ordering::Relaxed)
+    }
+
+    /// Sets custom destination of results produced by streaming_recognize, e.g. flume
+    /// channel (with feature flume). Replaces channel returned by get_streaming_result_receiver.
+    pub fn set_result_sink(&mut self, result_sink: impl ResultSink + 'static) {
+        self.result_sender = Some(Arc::new(result_sink));
+    }
+
+    /// Subscribes to raw responses received during streaming calls (any streaming_recognize
+    /// variant or stream API). Can be called multiple times,
+    /// each subscriber receives all responses sent after it subscribed.
+    /// Subscribers lagging more than 1000 responses behind lose oldest responses.
+    pub fn subscribe_raw(&mut self) -> broadcast::Receiver<StreamingRecognizeResponse> {
+        self.raw_broadcast
+            .get_or_insert_with(|| broadcast::channel(1000).0)
+            .subscribe()
+    }
+
+    /// Same as subscribe_raw, but responses are parsed into transcripts
+    /// (one transcript per recognition result).
+    pub fn subscribe_transcripts(&mut self) -> broadcast::Receiver<StreamingTranscript> {
+        self.transcript_broadcast
+            .get_or_insert_with(|| broadcast::channel(1000).0)
+            .subscribe()
+    }
+
+    /// Returns receiver of transcripts (see subscribe_transcripts) suitable for rendering
+    /// in UI. Rapid interim updates are coalesced, i.e. at most one interim transcript
+    /// (the latest one) is forwarded per window. Final transcripts are never debounced,
+    /// they are forwarded immediately. Must be called within tokio runtime
+    /// (debouncing runs in spawned task which ends once recognizer is dropped).
+    pub fn get_streaming_transcript_receiver_debounced(
+        &mut self,
+        window: Duration,
+    ) -> mpsc::Receiver<StreamingTranscript> {
+        let (sender, receiver) = mpsc::channel(1000);
+        tokio::spawn(debounce_transcripts(
+            self.subscribe_transcripts(),
+            sender,
+            window,
+        ));
+        receiver
+    }
+
+    /// Returns adapter which can be used to feed audio samples from synchronous (non-async)
+    /// callbacks, e.g. live microphone capture. Audio must be configured as LINEAR16.
+    /// Adapter holds clone of audio sink, i.e. stream is closed only after adapter
+    /// (and respective callback) is dropped and drop_audio_sink is called.
+    /// Returns None if audio sink is not available.
+    pub fn audio_callback_sink(&mut self) -> Option<AudioCallbackSink> {
+        let sink_gauge = self.sink_gauge();
+        let audio_bytes = self.audio_bytes.clone();
+        self.get_audio_sink().map(|audio_sender| AudioCallbackSink {
+            audio_sender,
+            dropped_chunks: Arc::new(AtomicU64::new(0)),
+            sink_gauge,
+            audio_bytes,
+        })
+    }
+
+    /// Returns adapter accepting LINEAR16 buffers pushed by media framework threads
+    /// (see PacedAudioSink). Buffers are forwarded at real-time speed multiplied by pace
+    /// (e.g. 1.0 for real time), up to queue_capacity buffers are queued before
+    /// overflow_policy applies. Adapter holds clone of audio sink (limit of
+    /// set_max_buffered_bytes does not apply), i.e. stream is closed only after adapter
+    /// is dropped and drop_audio_sink is called. Must be called within tokio runtime.
+    /// Returns error if pace is not positive number or audio sink is not available.
+    pub fn paced_audio_sink(
+        &mut self,
+        sample_rate: u32,
+        channels: u16,
+        pace: f64,
+        queue_capacity: usize,
+        overflow_policy: PushOverflowPolicy,
+    ) -> Result<PacedAudioSink> {
+        if !(pace > 0.0 && pace.is_finite()) {
+            return Err(Error::new(format!(
+                "Pace must be positive number, got {}",
+                pace
+            )));
+        }
+        let audio_sender = self
+            .get_audio_sink()
+            .ok_or_else(|| Error::new("Audio sink is not available".to_string()))?;
+        let queue = Arc::new(OverflowQueue::new(
+            queue_capacity,
+            overflow_policy == PushOverflowPolicy::DropOldest,
+        ));
+        tokio::spawn(forward_paced(
+            queue.clone(),
+            audio_sender,
+            sample_rate,
+            channels,
+            pace,
+        ));
+        Ok(PacedAudioSink {
+            queue,
+            dropped_buffers: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Same as audio_callback_sink, but returned callback accepts normalized f32 samples
+    /// (-1.0 - 1.0) which are clamped and converted to LINEAR16. Use audio_callback_sink
+    /// and AudioCallbackSink::push_f32 if drop counter is needed.
+    pub fn audio_callback_sink_f32(&mut self) -> Option<impl FnMut(&[f32]) + Send + 'static> {
+        self.audio_callback_sink()
+            .map(AudioCallbackSink::into_callback_f32)
+    }
+
     /// Convenience function so that client does not have to create full StreamingRecognizeRequest
     /// and can just pass audio bytes vector instead.
-    pub fn streaming_request_from_bytes(audio_bytes: Vec<u8>, recognizer: String) -> StreamingRecognizeRequest {
+    pub fn streaming_request_from_bytes(
+        audio_bytes: Vec<u8>,
+        recognizer: String,
+    ) -> StreamingRecognizeRequest {
         StreamingRecognizeRequest {
             recognizer,
             streaming_request: Some(StreamingRequest::Audio(audio_bytes)),
         }
     }
 
+    /// Reads audio data from reader in chunks of chunk_size bytes and sends them
+    /// into audio sink (see take_audio_sink). Returns total number of bytes sent.
+    /// Sink is dropped once reader is exhausted, i.e. if it was the only sender
+    /// respective stream will be closed. Since reader is blocking this should be
+    /// used with files or in-memory buffers and spawned into separate tokio task.
+    pub async fn pump_audio_from_reader(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        reader: impl Read,
+        chunk_size: usize,
+    ) -> Result<u64> {
+        Recognizer::pump_audio_from_reader_with_progress(audio_sender, reader, chunk_size, |_| {})
+            .await
+    }
+
+    /// Same as pump_audio_from_reader. Additionally invokes progress callback
+    /// with cumulative number of bytes sent after each chunk. When EOF is reached
+    /// callback is invoked once more with the final total.
+    pub async fn pump_audio_from_reader_with_progress(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        reader: impl Read,
+        chunk_size: usize,
+        progress: impl FnMut(u64),
+    ) -> Result<u64> {
+        Recognizer::pump_audio(
+            audio_sender,
+            reader,
+            chunk_size,
+            &PumpOptions::default(),
+            progress,
+        )
+        .await
+    }
+
+    /// Same as pump_audio_from_reader, additionally LINEAR16 audio is transformed according
+    /// to options (e.g. converted from big-endian) before it is sent. Chunk size is rounded
+    /// up to whole 16-bit samples.
+    pub async fn pump_audio_from_reader_with_options(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        reader: impl Read,
+        chunk_size: usize,
+        options: PumpOptions,
+    ) -> Result<u64> {
+        Recognizer::pump_audio(audio_sender, reader, chunk_size, &options, |_| {}).await
+    }
+
+    /// Streams OGG_OPUS audio (see RecognitionConfigBuilder::ogg_opus) from reader into audio
+    /// sink in chunks of chunk_size bytes without decoding. First Ogg page is validated
+    /// before anything is sent, returns error with code INVALID_OGG_OPUS if audio does not
+    /// start with OpusHead page. Returns number of bytes sent.
+    pub async fn pump_ogg_opus_from_reader(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        mut reader: impl Read,
+        chunk_size: usize,
+    ) -> Result<u64> {
+        // short audio is reported by validation
+        let mut header = vec![];
+        reader
+            .by_ref()
+            .take(OGG_PAGE_HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+        if let Some(segment_count) = header.get(OGG_PAGE_HEADER_LEN - 1) {
+            // segment table followed by OpusHead magic
+            let rest_len = *segment_count as u64 + 8;
+            reader.by_ref().take(rest_len).read_to_end(&mut header)?;
+        }
+        validate_ogg_opus_header(&header)?;
+
+        Recognizer::pump_audio(
+            audio_sender,
+            Cursor::new(header).chain(reader),
+            chunk_size,
+            &PumpOptions::default(),
+            |_| {},
+        )
+        .await
+    }
+
+    async fn pump_audio(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        mut reader: impl Read,
+        chunk_size: usize,
+        options: &PumpOptions,
+        mut progress: impl FnMut(u64),
+    ) -> Result<u64> {
+        let chunk_size = if options.transforms() {
+            chunk_size.max(2).next_multiple_of(2)
+        } else {
+            chunk_size
+        };
+        let mut total_bytes: u64 = 0;
+
+        loop {
+            let mut chunk = Vec::with_capacity(chunk_size);
+            let n = reader
+                .by_ref()
+                .take(chunk_size as u64)
+                .read_to_end(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+
+            // recognizer name is needed only in the first (config) message
+            audio_sender
+                .send(Recognizer::streaming_request_from_bytes(
+                    options.apply(chunk)?,
+                    String::new(),
+                ))
+                .await?;
+            total_bytes += n as u64;
+            progress(total_bytes);
+
+            if n < chunk_size {
+                break;
+            }
+        }
+
+        progress(total_bytes);
+        Ok(total_bytes)
+    }
+
+    /// Same as pump_audio_realtime_with_pace with pace 1.0 (real-time speed).
+    pub async fn pump_audio_realtime(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        reader: impl Read,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<u64> {
+        Recognizer::pump_audio_realtime_with_pace(audio_sender, reader, sample_rate, channels, 1.0)
+            .await
+    }
+
+    /// Reads LINEAR16 audio from reader and sends it into audio sink in chunks of
+    /// REALTIME_CHUNK_DURATION, pacing chunks so that audio is sent at real-time speed
+    /// multiplied by pace (e.g. 2.0 sends 1s of audio every 500ms). Mirrors live capture
+    /// when replaying files and avoids 'audio should be sent close to real time' errors.
+    /// Returns total number of bytes sent. Sink is dropped once reader is exhausted.
+    pub async fn pump_audio_realtime_with_pace(
+        audio_sender: mpsc::Sender<StreamingRecognizeRequest>,
+        mut reader: impl Read,
+        sample_rate: u32,
+        channels: u16,
+        pace: f64,
+    ) -> Result<u64> {
+        if !(pace > 0.0 && pace.is_finite()) {
+            return Err(Error::new(format!(
+                "Pace must be positive number, got {}",
+                pace
+            )));
+        }
+        let chunk_size = pcm_byte_len(REALTIME_CHUNK_DURATION, sample_rate, channels, 16).max(1);
+        let started = tokio::time::Instant::now();
+        let mut total_bytes: u64 = 0;
+
+        loop {
+            let mut chunk = Vec::with_capacity(chunk_size);
+            let n = reader
+                .by_ref()
+                .take(chunk_size as u64)
+                .read_to_end(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+
+            audio_sender
+                .send(Recognizer::streaming_request_from_bytes(
+                    chunk,
+                    String::new(),
+                ))
+                .await?;
+            total_bytes += n as u64;
+
+            // sleep until sent audio would be captured live (no drift accumulates)
+            let sent_duration = pcm_duration(total_bytes as usize, sample_rate, channels, 16);
+            tokio::time::sleep_until(started + sent_duration.div_f64(pace)).await;
+
+            if n < chunk_size {
+                break;
+            }
+        }
+
+        Ok(total_bytes)
+    }
+
     /// Initiates bidirectional streaming. Returns
     /// asynchronous stream of streaming recognition results
     /// Audio data must be fed into recognizer via channel sender
-    /// returned by function get_audio_sink.
+    /// returned by function get_audio_sink. Stream is set up and guarded the same way
+    /// as streaming_recognize (stream limiter, session deadline, frame validation,
+    /// request logging and recording), responses are also sent to broadcast subscribers.
     #[allow(unreachable_code)]
     pub async fn streaming_recognize_async_stream(
         &mut self,
     ) -> impl Stream<Item = Result<StreamingRecognizeResponse>> + '_ {
         try_stream! {
-                // yank self.audio_receiver so that we can consume it
-                if let Some(audio_receiver) = self.audio_receiver.take() {
-                    let streaming_recognize_result: StdResult<
-                        TonicResponse<Streaming<StreamingRecognizeResponse>>,
-                        TonicStatus,
-                    > = self.speech_client.streaming_recognize(ReceiverStream::new(audio_receiver)).await;
-
-                    let mut response_stream: Streaming<StreamingRecognizeResponse> =
-                        streaming_recognize_result?.into_inner();
-
-                    trace!("streaming_recognize: entering loop");
-                    while let Some(streaming_recognize_response) = response_stream.message().await? {
-                        yield streaming_recognize_response;
-                    }
-                    trace!("streaming_recognize: leaving loop");
+            if let Some(mut stream) = self.open_stream(None).await? {
+                while let Some(response) = self.next_stream_response(&mut stream).await? {
+                    yield response;
                 }
+            }
         }
     }
 
+    /// Converts recognizer into futures Sink of audio bytes and Stream of streaming results
+    /// so that it can be composed with futures combinators (e.g. audio.forward(sink)).
+    /// Backpressure: sink accepts next chunk only when audio sink (buffer_size chunks) has
+    /// free capacity, i.e. fast producer waits for chunks to be sent to Google. Results
+    /// are received only while the stream is polled. Audio sink is taken out of recognizer
+    /// (see take_audio_sink), audio stream ends when sink is closed or dropped. If audio
+    /// sink is not available (already taken or streaming config not sent) sink is closed.
+    pub fn into_sink_and_stream(
+        mut self,
+    ) -> (
+        AudioStreamSink,
+        impl Stream<Item = Result<StreamingRecognizeResponse>>,
+    ) {
+        let sender = self.take_audio_sink().unwrap_or_else(|| mpsc::channel(1).0);
+        // recognizer must not keep audio stream open
+        self.drop_audio_sink();
+        let sink = AudioStreamSink {
+            sender: PollSender::new(sender),
+        };
+        let stream = async_stream::stream! {
+            let mut recognizer = self;
+            let response_stream = recognizer.streaming_recognize_async_stream().await;
+            tokio::pin!(response_stream);
+            while let Some(response) = response_stream.next().await {
+                yield response;
+            }
+        };
+        (sink, stream)
+    }
+
+    /// Same as streaming_recognize_async_stream, additionally returns handle which can be
+    /// used (e.g. from another task) to stop streaming. Once aborted, audio stream is closed,
+    /// GRPC call is cancelled (pending responses are discarded) and returned stream ends.
+    /// Finals received before abort can be retrieved by StreamHandle::abort_and_collect.
+    #[allow(unreachable_code)]
+    pub async fn streaming_recognize_async_stream_with_handle(
+        &mut self,
+    ) -> (
+        StreamHandle,
+        impl Stream<Item = Result<StreamingRecognizeResponse>> + '_,
+    ) {
+        let handle = StreamHandle {
+            abort_sender: Arc::new(watch::channel(false).0),
+            finals: Arc::new(Mutex::new(vec![])),
+        };
+        // stream keeps its own handle so that dropped handles do not abort the stream
+        let stream_handle = handle.clone();
+        let abort_sender = handle.abort_sender.clone();
+
+        let stream = try_stream! {
+            if let Some(mut stream) = self.open_stream(Some(&abort_sender)).await? {
+                while let Some(response) = self.next_stream_response(&mut stream).await? {
+                    stream_handle.collect_finals(&response);
+                    yield response;
+                }
+            }
+        };
+
+        (handle, stream)
+    }
+
     /// Initiates bidirectional streaming. This call should be spawned
     /// into separate tokio task. Results can be then retrieved via
     /// channel receiver returned by method get_streaming_result_receiver.
+    /// Returns error with code NO_AUDIO_SENT (without calling Google) if audio
+    /// stream was closed before any audio was sent.
     pub async fn streaming_recognize(&mut self) -> Result<()> {
-        // yank self.audio_receiver so that we can consume it
-        if let Some(audio_receiver) = self.audio_receiver.take() {
-            let streaming_recognize_result: StdResult<
-                tonic::Response<Streaming<StreamingRecognizeResponse>>,
-                tonic::Status,
-            > = self
-                .speech_client
-                .streaming_recognize(ReceiverStream::new(audio_receiver))
-                .await;
-
-            let mut response_stream: Streaming<StreamingRecognizeResponse> =
-                streaming_recognize_result?.into_inner();
-
-            while let Some(streaming_recognize_response) = response_stream.message().await? {
-                if let Some(result_sender) = &self.result_sender {
-                    result_sender.send(streaming_recognize_response).await?;
-                }
+        let result_sender = self.result_sender.clone();
+        self.run_stream(|response| send_result(result_sender.clone(), response))
+            .await
+    }
+
+    /// Same as streaming_recognize but every response is wrapped into ContextualResponse
+    /// carrying (clone of) provided context and sent to result_sender. Channel returned by
+    /// get_streaming_result_receiver is not used. Context is not sent to Google.
+    pub async fn streaming_recognize_with_context<C: Clone>(
+        &mut self,
+        context: C,
+        result_sender: mpsc::Sender<ContextualResponse<C, StreamingRecognizeResponse>>,
+    ) -> Result<()> {
+        self.run_stream(|response| {
+            let result_sender = result_sender.clone();
+            let context = context.clone();
+            async move {
+                result_sender
+                    .send(ContextualResponse { context, response })
+                    .await?;
+                Ok(())
             }
+        })
+        .await
+    }
+
+    /// Same as streaming_recognize, i.e. drives bidirectional streaming to completion
+    /// (results are sent to channel returned by get_streaming_result_receiver if requested).
+    /// When stream is closed returns StreamSummary with aggregated statistics.
+    pub async fn streaming_recognize_with_summary(&mut self) -> Result<StreamSummary> {
+        let started = Instant::now();
+        let audio_bytes_sent = self.audio_bytes_sent.load(Ordering::Relaxed);
+        let mut summary = StreamSummary::default();
+        let result_sender = self.result_sender.clone();
+        self.run_stream(|response| {
+            summary.record_response(&response);
+            send_result(result_sender.clone(), response)
+        })
+        .await?;
+
+        summary.audio_bytes_sent = self.audio_bytes_sent.load(Ordering::Relaxed) - audio_bytes_sent;
+        summary.request_id = self.request_id.clone();
+        summary.deadline_reached = self.deadline_reached;
+        summary.elapsed = started.elapsed();
+        Ok(summary)
+    }
+
+    /// Explicitly ends audio streaming. Drops audio sink held by recognizer, ends audio
+    /// stream and waits until response stream signals completion, see
+    /// StreamCloser::close_audio_and_flush. Streaming call borrows the recognizer, i.e. to
+    /// close stream driven by another task use handle returned by stream_closer.
+    /// If stream was not started yet returns immediately (nothing is sent to Google).
+    /// Calling this method repeatedly (or after streaming_recognize has finished) is a no-op.
+    pub async fn close_audio_and_flush(&mut self) -> Result<()> {
+        self.drop_audio_sink();
+        self.stream_closer().close_audio_and_flush().await
+    }
+
+    /// Returns handle which can end audio of running stream and wait for its completion
+    /// (see StreamCloser::close_audio_and_flush) while streaming_recognize (or stream
+    /// returned by streaming_recognize_async_stream) is driven by another task.
+    /// Handle stays valid for streams opened by reconfigure.
+    pub fn stream_closer(&self) -> StreamCloser {
+        StreamCloser {
+            lifecycle: self.stream_lifecycle.clone(),
         }
+    }
 
-        Ok(())
+    /// Switches streaming recognizer to new config, e.g. to change language or model
+    /// in the middle of session. Current stream is closed gracefully (see
+    /// close_audio_and_flush, audio queued so far is recognized with previous config)
+    /// and new audio sink with new streaming config
+    /// is prepared. Results of new stream are sent to the same result receiver (and
+    /// broadcast subscribers). Caller then obtains new audio sink and calls
+    /// streaming_recognize again, i.e. audio is briefly interrupted while switching.
+    /// If no audio is queued, no stream is opened to close the session. Audio sent via
+    /// previously obtained audio sinks after this call is rejected. New audio sink is
+    /// prepared even if recognition of queued audio fails (error is returned).
+    /// Returns error (code STREAMING_CONFIG_MISSING) if recognizer is not streaming one.
+    pub async fn reconfigure(&mut self, new_config: StreamingRecognitionConfig) -> Result<()> {
+        let buffer_size = match self.audio_buffer_size {
+            Some(buffer_size) => buffer_size,
+            None => {
+                return Err(Error::new_with_code(
+                    "Only streaming recognizers can be reconfigured".to_string(),
+                    "STREAMING_CONFIG_MISSING".to_string(),
+                ))
+            }
+        };
+        let (audio_sender, audio_receiver) = mpsc::channel(buffer_size);
+        // deferred recognizer without config has no stream to close
+        let flushed = match self.pending_recognizer {
+            None => self.recognize_queued_audio().await,
+            Some(_) => Ok(()),
+        };
+        trace!("{}reconfigure: opening new stream", self.log_tag());
+
+        self.audio_sender = Some(audio_sender);
+        self.audio_receiver = Some(audio_receiver);
+        self.first_audio = Arc::new(FirstAudioSignal::default());
+        self.stream_lifecycle.close_requested.send_replace(false);
+        self.pending_recognizer = Some(self.recognizer_name.clone());
+        self.send_streaming_config(new_config).await?;
+        flushed
+    }
+
+    /// Ends audio of current stream (see close_audio_and_flush) and drives stream not started
+    /// yet to completion, i.e. audio queued so far is recognized with current config.
+    /// Nothing is sent to Google if no audio is queued.
+    async fn recognize_queued_audio(&mut self) -> Result<()> {
+        self.close_audio_and_flush().await?;
+        let mut audio_receiver = match self.audio_receiver.take() {
+            Some(audio_receiver) => audio_receiver,
+            None => return Ok(()),
+        };
+        let mut queued = vec![];
+        while let Ok(request) = audio_receiver.try_recv() {
+            queued.push(request);
+        }
+        let has_audio = queued
+            .iter()
+            .any(|request| matches!(request.streaming_request, Some(StreamingRequest::Audio(_))));
+        if !has_audio {
+            return Ok(());
+        }
+        // queued requests are moved into closed channel, i.e. stream ends with them
+        // even if audio sinks obtained by caller are still alive
+        let (queued_sender, queued_receiver) = mpsc::channel(queued.len());
+        for request in queued {
+            let _ = queued_sender.try_send(request);
+        }
+        self.audio_receiver = Some(queued_receiver);
+        self.streaming_recognize().await
     }
 
     /// Initiates asynchronous recognition.
     /// Returns batch operation representing
     /// asynchronous computation performed by Google Cloud Platform.
-    /// Use batch_wait to wait until operation is done.
+    /// Use batch_wait to wait until operation is done. Operation name
+    /// can be used to cancel the operation (e.g. from another task) via cancel_operation.
     pub async fn batch_recognize(
         &mut self,
         request: BatchRecognizeRequest,
     ) -> Result<GrpcResponse<Operation>> {
+        if self.log_requests {
+            debug!("{}batch_recognize: sending {:?}", self.log_tag(), request);
+        }
+        self.token_interceptor.refresh().await?;
         Ok(self.speech_client.batch_recognize(request).await?)
     }
 
@@ -325,6 +2888,7 @@ impl Recognizer {
         };
 
         loop {
+            self.token_interceptor.refresh().await?;
             if let Some(oper_client) = &mut self.operations_client {
                 let tonic_response: TonicResponse<Operation> =
                     oper_client.get_operation(operation_req.clone()).await?;
@@ -333,7 +2897,11 @@ impl Recognizer {
                     return if let Some(operation_result) = operation.result {
                         match operation_result {
                             OperationResult::Error(rpc_status) => {
-                                error!("Recognizer.long_running_wait rpc error {:?}", rpc_status);
+                                error!(
+                                    "{}Recognizer.long_running_wait rpc error {:?}",
+                                    self.log_tag(),
+                                    rpc_status
+                                );
                                 Err(Error::new_with_code(
                                     rpc_status.message,
                                     rpc_status.code.to_string(),
@@ -357,10 +2925,353 @@ impl Recognizer {
         }
     }
 
+    /// Requests cancellation of long running (batch) operation. Cancellation
+    /// is best effort, once cancelled operation is done with CANCELLED error,
+    /// i.e. batch_wait returns error with code 1. Available for asynchronous recognizers only.
+    pub async fn cancel_operation(&mut self, operation_name: &str) -> Result<()> {
+        match &mut self.operations_client {
+            Some(oper_client) => {
+                self.token_interceptor.refresh().await?;
+                oper_client
+                    .cancel_operation(CancelOperationRequest {
+                        name: operation_name.to_string(),
+                    })
+                    .await?;
+                Ok(())
+            }
+            None => Err(Error::new_with_code(
+                "cancel_operation requires asynchronous recognizer".to_string(),
+                "OPERATIONS_CLIENT_MISSING".to_string(),
+            )),
+        }
+    }
+
+    /// Lists long running (batch) operations in collection name (projects/{project}/locations/{location}),
+    /// optionally restricted by filter (e.g. done=false, see google.longrunning filter syntax).
+    /// All pages are fetched. Available for asynchronous recognizers only.
+    pub async fn list_operations(
+        &mut self,
+        name: &str,
+        filter: Option<String>,
+    ) -> Result<Vec<Operation>> {
+        let oper_client = match &mut self.operations_client {
+            Some(oper_client) => oper_client,
+            None => {
+                return Err(Error::new_with_code(
+                    "list_operations requires asynchronous recognizer".to_string(),
+                    "OPERATIONS_CLIENT_MISSING".to_string(),
+                ))
+            }
+        };
+        let filter = filter.unwrap_or_default();
+        let mut operations = vec![];
+        let mut page_token = String::new();
+        loop {
+            self.token_interceptor.refresh().await?;
+            let response = oper_client
+                .list_operations(ListOperationsRequest {
+                    name: name.to_string(),
+                    filter: filter.clone(),
+                    page_size: 0,
+                    page_token,
+                })
+                .await?
+                .into_inner();
+            operations.extend(response.operations);
+            if response.next_page_token.is_empty() {
+                return Ok(operations);
+            }
+            page_token = response.next_page_token;
+        }
+    }
+
+    /// Cancels all operations returned by list_operations which are not done yet
+    /// and returns names of cancelled operations. All cancellations are attempted,
+    /// if some of them fail error with code CANCEL_PARTIALLY_FAILED listing failed
+    /// operations (and names of cancelled ones) is returned.
+    pub async fn cancel_all_operations(
+        &mut self,
+        name: &str,
+        filter: Option<String>,
+    ) -> Result<Vec<String>> {
+        let operations = self.list_operations(name, filter).await?;
+        let mut cancelled = vec![];
+        let mut failed = vec![];
+        for operation in operations.into_iter().filter(|operation| !operation.done) {
+            match self.cancel_operation(&operation.name).await {
+                Ok(()) => cancelled.push(operation.name),
+                Err(error) => failed.push(format!("{}: {}", operation.name, error.message)),
+            }
+        }
+        if failed.is_empty() {
+            Ok(cancelled)
+        } else {
+            Err(Error::new_with_code(
+                format!(
+                    "Failed to cancel {} operation(s): {}. Cancelled: [{}]",
+                    failed.len(),
+                    failed.join("; "),
+                    cancelled.join(", ")
+                ),
+                "CANCEL_PARTIALLY_FAILED".to_string(),
+            ))
+        }
+    }
+
+    /// Returns current state (done flag, metadata, error or response) of long running
+    /// (batch) operation with given name. Name can be persisted and operation polled
+    /// from another process. Available for asynchronous recognizers only.
+    pub async fn get_operation(&mut self, operation_name: &str) -> Result<Operation> {
+        match &mut self.operations_client {
+            Some(oper_client) => {
+                self.token_interceptor.refresh().await?;
+                Ok(oper_client
+                    .get_operation(GetOperationRequest {
+                        name: operation_name.to_string(),
+                    })
+                    .await?
+                    .into_inner())
+            }
+            None => Err(Error::new_with_code(
+                "get_operation requires asynchronous recognizer".to_string(),
+                "OPERATIONS_CLIENT_MISSING".to_string(),
+            )),
+        }
+    }
+
+    /// Polls long running (batch) operation with given name every check_interval
+    /// until it is done and returns done operation. Unlike batch_wait operation
+    /// result is not decoded, i.e. operation error is returned as part of operation.
+    /// Available for asynchronous recognizers only.
+    pub async fn poll_until_done(
+        &mut self,
+        operation_name: &str,
+        check_interval: Duration,
+    ) -> Result<Operation> {
+        loop {
+            let operation = self.get_operation(operation_name).await?;
+            if operation.done {
+                return Ok(operation);
+            }
+            sleep(check_interval).await;
+        }
+    }
+
     /// Performs synchronous speech recognition.
+    /// Request must contain exactly one non-empty audio source, see validate_audio_source.
     pub async fn recognize(&mut self, request: RecognizeRequest) -> Result<RecognizeResponse> {
+        self.send_recognize_request(TonicRequest::new(request))
+            .await
+    }
+
+    /// Common path of recognize and recognize_with_headers: validates, logs and sends
+    /// request (with metadata already attached).
+    async fn send_recognize_request(
+        &mut self,
+        request: TonicRequest<RecognizeRequest>,
+    ) -> Result<RecognizeResponse> {
+        Recognizer::validate_audio_source(request.get_ref())?;
+        if self.log_requests {
+            debug!(
+                "{}recognize: sending {}",
+                self.log_tag(),
+                redact_recognize_request(request.get_ref())
+            );
+        }
+        self.token_interceptor.refresh().await?;
         let tonic_response: TonicResponse<RecognizeResponse> =
             self.speech_client.recognize(request).await?;
         Ok(tonic_response.into_inner())
     }
-}
\ No newline at end of file
+
+    /// Performs synchronous recognition of audio bytes. If recognition cache is set
+    /// (see set_recognition_cache) response of identical audio recognized with identical
+    /// config and recognizer is returned from cache without calling Google.
+    pub async fn recognize_bytes(
+        &mut self,
+        audio: Vec<u8>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+        config: RecognitionConfig,
+    ) -> Result<RecognizeResponse> {
+        let cache_entry = self.recognition_cache.clone().map(|cache| {
+            let key = CacheKey::new(&audio, &config, &recognizer);
+            (cache, key)
+        });
+        if let Some((cache, key)) = &cache_entry {
+            if let Some(response) = cache.get(*key) {
+                self.cache_counters.hit();
+                debug!("{}recognize_bytes: cache hit", self.log_tag());
+                return Ok(response);
+            }
+            self.cache_counters.miss();
+        }
+
+        let response = self
+            .recognize(RecognizeRequest {
+                recognizer,
+                config: Some(config),
+                config_mask: None,
+                audio_source: Some(AudioSource::Content(audio)),
+            })
+            .await?;
+        if let Some((cache, key)) = cache_entry {
+            cache.put(key, response.clone());
+        }
+        Ok(response)
+    }
+
+    /// Checks that exactly one audio source (content or uri) is set. Since audio_source
+    /// is GRPC oneof, setting both is prevented by the type, but the source may be
+    /// missing or empty. Returns error with code AUDIO_SOURCE_CONFLICT otherwise.
+    pub fn validate_audio_source(request: &RecognizeRequest) -> Result<()> {
+        let valid = match &request.audio_source {
+            Some(AudioSource::Content(content)) => !content.is_empty(),
+            Some(AudioSource::Uri(uri)) => !uri.trim().is_empty(),
+            None => false,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::new_with_code(
+                "Exactly one of audio_source content or uri must be set (and non-empty)"
+                    .to_string(),
+                "AUDIO_SOURCE_CONFLICT".to_string(),
+            ))
+        }
+    }
+
+    /// Reads audio file and transcribes it using synchronous recognition.
+    /// Synchronous recognition is limited to SYNC_RECOGNIZE_MAX_BYTES of audio content
+    /// and SYNC_RECOGNIZE_MAX_DURATION of audio. Size limit is checked for all files, duration
+    /// limit only for headerless audio with explicit decoding config (LINEAR16, MULAW, ALAW)
+    /// where duration can be computed from file size. If any limit is exceeded error
+    /// with code AUDIO_TOO_LONG is returned, such audio must be uploaded to Google Cloud Storage
+    /// and transcribed with batch_recognize.
+    pub async fn transcribe_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+        config: RecognitionConfig,
+    ) -> Result<RecognizeResponse> {
+        let path = path.as_ref();
+        let content = fs::read(path)?;
+
+        let duration = match &config.decoding_config {
+            Some(DecodingConfig::ExplicitDecodingConfig(explicit)) => {
+                explicit_audio_duration(explicit, content.len())
+            }
+            _ => None,
+        };
+
+        if content.len() > SYNC_RECOGNIZE_MAX_BYTES
+            || duration.is_some_and(|duration| duration > SYNC_RECOGNIZE_MAX_DURATION)
+        {
+            return Err(Error::new_with_code(
+                format!(
+                    "Audio file {} ({} bytes, duration {:?}) exceeds synchronous recognition limits \
+                     ({} bytes, {:?}). Upload it to Google Cloud Storage and use batch_recognize with GCS URI.",
+                    path.display(),
+                    content.len(),
+                    duration,
+                    SYNC_RECOGNIZE_MAX_BYTES,
+                    SYNC_RECOGNIZE_MAX_DURATION
+                ),
+                "AUDIO_TOO_LONG".to_string(),
+            ));
+        }
+
+        self.recognize(RecognizeRequest {
+            recognizer,
+            config: Some(config),
+            config_mask: None,
+            audio_source: Some(AudioSource::Content(content)),
+        })
+        .await
+    }
+
+    /// Same as transcribe_file, recognition config is derived from file extension
+    /// (see config::config_for_file). Returns error with code UNSUPPORTED_FORMAT
+    /// if audio format cannot be inferred.
+    pub async fn transcribe_file_with_language(
+        &mut self,
+        path: impl AsRef<Path>,
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+        language: &str,
+    ) -> Result<RecognizeResponse> {
+        let config = config_for_file(path.as_ref(), language)?;
+        self.transcribe_file(path, recognizer, config).await
+    }
+
+    /// Detects spoken language of short audio sample (e.g. first few seconds of a call).
+    /// Runs synchronous recognition with candidate_languages as language_codes of provided
+    /// config and returns language code of detected speech (language of results with the
+    /// longest total transcript if results differ, codes are returned as reported by Google,
+    /// e.g. en-us). Returns error with code NO_SPEECH if no speech was recognized.
+    pub async fn detect_language(
+        &mut self,
+        audio: &[u8],
+        // Required. The name of the Recognizer to use during recognition.
+        recognizer: String,
+        candidate_languages: &[&str],
+        mut config: RecognitionConfig,
+    ) -> Result<String> {
+        config.language_codes = candidate_languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect();
+
+        let response = self
+            .recognize(RecognizeRequest {
+                recognizer,
+                config: Some(config),
+                config_mask: None,
+                audio_source: Some(AudioSource::Content(audio.to_vec())),
+            })
+            .await?;
+
+        let mut transcript_lengths: HashMap<String, usize> = HashMap::new();
+        for result in &response.results {
+            let transcript_len = result
+                .alternatives
+                .first()
+                .map_or(0, |alternative| alternative.transcript.trim().len());
+            if transcript_len > 0 && !result.language_code.is_empty() {
+                *transcript_lengths
+                    .entry(result.language_code.clone())
+                    .or_default() += transcript_len;
+            }
+        }
+
+        transcript_lengths
+            .into_iter()
+            .max_by_key(|(_, transcript_len)| *transcript_len)
+            .map(|(language_code, _)| language_code)
+            .ok_or_else(|| {
+                Error::new_with_code(
+                    "No speech recognized in audio sample".to_string(),
+                    "NO_SPEECH".to_string(),
+                )
+            })
+    }
+
+    /// Same as recognize, but additionally attaches provided headers (e.g. trace id)
+    /// as GRPC metadata to this single request. Invalid header names (e.g. containing
+    /// spaces or upper case letters) or values (e.g. containing new lines) are reported
+    /// as error of kind ErrorKind::InvalidArgument and request is not sent.
+    pub async fn recognize_with_headers(
+        &mut self,
+        request: RecognizeRequest,
+        headers: HashMap<String, String>,
+    ) -> Result<RecognizeResponse> {
+        let mut tonic_request = TonicRequest::new(request);
+        for (name, value) in headers {
+            let key = MetadataKey::<Ascii>::from_bytes(name.as_bytes())?;
+            let value: MetadataValue<Ascii> = value.parse()?;
+            tonic_request.metadata_mut().insert(key, value);
+        }
+        self.send_recognize_request(tonic_request).await
+    }
+}
@@ -0,0 +1,98 @@
+//! Recording of streaming sessions into files of length-delimited protobuf messages
+//! (see Recognizer::record_requests and Recognizer::record_responses) and their replay,
+//! e.g. for deterministic regression tests against mock server (see testing::MockSpeech)
+//! or for diffing of transcriptions across library versions.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    streaming_recognize_request::StreamingRequest, StreamingRecognizeRequest,
+    StreamingRecognizeResponse,
+};
+use crate::errors::{Error, Result};
+use futures_core::stream::Stream;
+use prost::bytes::Buf;
+use prost::Message;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Writes message prefixed by its length (varint), i.e. prost length-delimited encoding.
+pub fn write_delimited<M: Message>(writer: &mut impl Write, message: &M) -> Result<()> {
+    writer.write_all(&message.encode_length_delimited_to_vec())?;
+    Ok(())
+}
+
+/// Reads all length-delimited messages from file.
+pub fn read_delimited<M: Message + Default>(path: impl AsRef<Path>) -> Result<Vec<M>> {
+    let content = fs::read(path)?;
+    let mut buf = content.as_slice();
+    let mut messages = vec![];
+    while buf.has_remaining() {
+        messages.push(M::decode_length_delimited(&mut buf)?);
+    }
+    Ok(messages)
+}
+
+/// Returns stream of requests recorded by Recognizer::record_requests. First request
+/// carries streaming config (recorded sessions always start with it), i.e. requests
+/// should be replayed into raw streaming recognizer (see create_streaming_recognizer_raw)
+/// which does not send config on its own. Returns error with code REPLAY_CONFIG_MISSING
+/// if recording does not start with streaming config.
+pub fn replay_requests(
+    path: impl AsRef<Path>,
+) -> Result<impl Stream<Item = StreamingRecognizeRequest>> {
+    let requests: Vec<StreamingRecognizeRequest> = read_delimited(path)?;
+    match requests.first().map(|request| &request.streaming_request) {
+        Some(Some(StreamingRequest::StreamingConfig(_))) => {}
+        _ => {
+            return Err(Error::new_with_code(
+                "Recorded session does not start with streaming config".to_string(),
+                "REPLAY_CONFIG_MISSING".to_string(),
+            ))
+        }
+    }
+    Ok(tokio_stream::iter(requests))
+}
+
+/// Loads all responses recorded by Recognizer::record_responses.
+pub fn read_responses(path: impl AsRef<Path>) -> Result<Vec<StreamingRecognizeResponse>> {
+    read_delimited(path)
+}
+
+/// Returns stream of responses recorded by Recognizer::record_responses.
+pub fn replay_responses(
+    path: impl AsRef<Path>,
+) -> Result<impl Stream<Item = StreamingRecognizeResponse>> {
+    Ok(tokio_stream::iter(read_responses(path)?))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_replay_requests
+    #[test]
+    fn test_replay_requests() {
+        let path = std::env::temp_dir().join("test_replay_requests.pb");
+        let audio = StreamingRecognizeRequest {
+            recognizer: String::new(),
+            streaming_request: Some(StreamingRequest::Audio(vec![1, 2, 3])),
+        };
+
+        let mut file = fs::File::create(&path).unwrap();
+        write_delimited(&mut file, &audio).unwrap();
+        let error = replay_requests(&path).err().unwrap();
+        assert_eq!(error.code.as_deref(), Some("REPLAY_CONFIG_MISSING"));
+
+        let config = StreamingRecognizeRequest {
+            recognizer: "projects/p/locations/global/recognizers/_".to_string(),
+            streaming_request: Some(StreamingRequest::StreamingConfig(Default::default())),
+        };
+        let mut file = fs::File::create(&path).unwrap();
+        write_delimited(&mut file, &config).unwrap();
+        write_delimited(&mut file, &audio).unwrap();
+        let requests: Vec<StreamingRecognizeRequest> = read_delimited(&path).unwrap();
+        assert_eq!(requests, vec![config, audio]);
+        assert!(replay_requests(&path).is_ok());
+
+        let _ = fs::remove_file(&path);
+    }
+}
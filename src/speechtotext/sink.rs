@@ -0,0 +1,303 @@
+//! Pluggable delivery of v2 streaming recognition results.
+//! Results are delivered to tokio mpsc channel by default (see
+//! Recognizer::get_streaming_result_receiver). Implement ResultSink
+//! (or enable feature flume for flume::Sender adapter) to deliver results
+//! to other channels, see Recognizer::set_result_sink.
+//! Behavior of default channel when consumer is slow is controlled by ResultOverflowPolicy,
+//! see Recognizer::get_streaming_result_receiver_with_overflow.
+use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognizeResponse;
+use crate::errors::{Error, Result};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::error::{SendError, TrySendError};
+use tokio::sync::{mpsc, Notify};
+
+/// Destination of streaming recognition results.
+#[tonic::async_trait]
+pub trait ResultSink: Send + Sync + Debug {
+    /// Delivers single response. Returning error terminates streaming_recognize.
+    async fn send(&self, response: StreamingRecognizeResponse) -> Result<()>;
+}
+
+#[tonic::async_trait]
+impl ResultSink for mpsc::Sender<StreamingRecognizeResponse> {
+    async fn send(&self, response: StreamingRecognizeResponse) -> Result<()> {
+        Ok(mpsc::Sender::send(self, response).await?)
+    }
+}
+
+/// Adapter for flume channels which can be consumed both from sync and async code.
+#[cfg(feature = "flume")]
+#[tonic::async_trait]
+impl ResultSink for flume::Sender<StreamingRecognizeResponse> {
+    async fn send(&self, response: StreamingRecognizeResponse) -> Result<()> {
+        self.send_async(response)
+            .await
+            .map_err(|error| crate::errors::Error::new(format!("{}", error)))
+    }
+}
+
+/// What happens with new result when result channel is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultOverflowPolicy {
+    /// waits until consumer makes room, i.e. reception from Google is stalled (default)
+    #[default]
+    Block,
+    /// oldest buffered result is dropped to make room for new one
+    DropOldest,
+    /// new result is dropped
+    DropNewest,
+}
+
+/// Creates result channel with given capacity and overflow policy. Results dropped
+/// because of full channel are counted in dropped.
+pub(crate) fn result_channel(
+    buffer_size: usize,
+    policy: ResultOverflowPolicy,
+    dropped: Arc<AtomicU64>,
+) -> (
+    Arc<dyn ResultSink>,
+    mpsc::Receiver<StreamingRecognizeResponse>,
+) {
+    match policy {
+        ResultOverflowPolicy::Block => {
+            let (sender, receiver) = mpsc::channel(buffer_size);
+            (Arc::new(sender), receiver)
+        }
+        ResultOverflowPolicy::DropNewest => {
+            let (sender, receiver) = mpsc::channel(buffer_size);
+            (Arc::new(DropNewestSink { sender, dropped }), receiver)
+        }
+        ResultOverflowPolicy::DropOldest => {
+            // results are queued in the sink and forwarded one by one by spawned task,
+            // i.e. one more result can be buffered in the channel and one in the task
+            let (sender, receiver) = mpsc::channel(1);
+            let queue = Arc::new(OverflowQueue::new(buffer_size, true));
+            tokio::spawn(forward_queued(queue.clone(), sender));
+            let sink = DropOldestSink { queue, dropped };
+            (Arc::new(sink), receiver)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DropNewestSink {
+    sender: mpsc::Sender<StreamingRecognizeResponse>,
+    dropped: Arc<AtomicU64>,
+}
+
+#[tonic::async_trait]
+impl ResultSink for DropNewestSink {
+    async fn send(&self, response: StreamingRecognizeResponse) -> Result<()> {
+        match self.sender.try_send(response) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Closed(response)) => Err(SendError(response).into()),
+        }
+    }
+}
+
+/// Bounded queue dropping items when full, used where producer must never wait
+/// (result channel with drop-oldest policy, PacedAudioSink). Items are taken by
+/// single consumer task.
+#[derive(Debug)]
+pub(crate) struct OverflowQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    drop_oldest: bool,
+    notify: Notify,
+    // no more items will be queued
+    producer_closed: AtomicBool,
+    // consumer is gone
+    consumer_closed: AtomicBool,
+}
+
+impl<T> OverflowQueue<T> {
+    /// Creates queue holding up to capacity (at least 1) items. When queue is full
+    /// oldest item is dropped (drop_oldest) or new item is dropped.
+    pub(crate) fn new(capacity: usize, drop_oldest: bool) -> Self {
+        OverflowQueue {
+            items: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            drop_oldest,
+            notify: Notify::new(),
+            producer_closed: AtomicBool::new(false),
+            consumer_closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Queues item without waiting. Returns false if some item was dropped.
+    pub(crate) fn push(&self, item: T) -> bool {
+        let mut dropped = false;
+        {
+            let mut items = self.items.lock().unwrap();
+            if items.len() >= self.capacity {
+                if !self.drop_oldest {
+                    return false;
+                }
+                items.pop_front();
+                dropped = true;
+            }
+            items.push_back(item);
+        }
+        self.notify.notify_one();
+        !dropped
+    }
+
+    /// Signals that no more items will be pushed, consumer drains remaining items.
+    pub(crate) fn close(&self) {
+        self.producer_closed.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+
+    /// Marks consumer as gone, see is_consumer_closed.
+    pub(crate) fn close_consumer(&self) {
+        self.consumer_closed.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_consumer_closed(&self) -> bool {
+        self.consumer_closed.load(Ordering::Acquire)
+    }
+
+    /// Waits for next item. Returns None once queue is closed and drained.
+    pub(crate) async fn pop(&self) -> Option<T> {
+        loop {
+            if let Some(item) = self.items.lock().unwrap().pop_front() {
+                return Some(item);
+            }
+            if self.producer_closed.load(Ordering::Acquire) {
+                return None;
+            }
+            // notify_one stores permit, i.e. notification sent before this point is not lost
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DropOldestSink {
+    queue: Arc<OverflowQueue<StreamingRecognizeResponse>>,
+    dropped: Arc<AtomicU64>,
+}
+
+#[tonic::async_trait]
+impl ResultSink for DropOldestSink {
+    async fn send(&self, response: StreamingRecognizeResponse) -> Result<()> {
+        if self.queue.is_consumer_closed() {
+            return Err(Error::new("result receiver closed".to_string()));
+        }
+        if !self.queue.push(response) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DropOldestSink {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+/// Forwards queued responses into channel until sink is dropped (and queue is drained)
+/// or receiver is dropped.
+async fn forward_queued(
+    queue: Arc<OverflowQueue<StreamingRecognizeResponse>>,
+    sender: mpsc::Sender<StreamingRecognizeResponse>,
+) {
+    while let Some(response) = queue.pop().await {
+        if sender.send(response).await.is_err() {
+            queue.close_consumer();
+            return;
+        }
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    // cargo test -- --show-output test_mpsc_result_sink
+    #[tokio::test]
+    async fn test_mpsc_result_sink() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let sink: Box<dyn ResultSink> = Box::new(sender);
+        sink.send(StreamingRecognizeResponse::default())
+            .await
+            .unwrap();
+        assert!(receiver.recv().await.is_some());
+
+        drop(receiver);
+        assert!(sink
+            .send(StreamingRecognizeResponse::default())
+            .await
+            .is_err());
+    }
+
+    // cargo test --features flume -- --show-output test_flume_result_sink
+    #[cfg(feature = "flume")]
+    #[tokio::test]
+    async fn test_flume_result_sink() {
+        let (sender, receiver) = flume::unbounded();
+        let sink: Box<dyn ResultSink> = Box::new(sender);
+        sink.send(StreamingRecognizeResponse::default())
+            .await
+            .unwrap();
+        // consumed from sync code
+        assert!(receiver.recv().is_ok());
+    }
+
+    // responses are told apart by speech_event_type
+    #[allow(dead_code)]
+    fn response(id: i32) -> StreamingRecognizeResponse {
+        StreamingRecognizeResponse {
+            speech_event_type: id,
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_drop_newest_result_channel
+    #[tokio::test]
+    async fn test_drop_newest_result_channel() {
+        let dropped = Arc::new(AtomicU64::new(0));
+        let (sink, mut receiver) =
+            result_channel(2, ResultOverflowPolicy::DropNewest, dropped.clone());
+        for id in 1..=4 {
+            sink.send(response(id)).await.unwrap();
+        }
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+        assert_eq!(receiver.recv().await.unwrap().speech_event_type, 1);
+        assert_eq!(receiver.recv().await.unwrap().speech_event_type, 2);
+
+        drop(receiver);
+        assert!(sink.send(response(5)).await.is_err());
+    }
+
+    // cargo test -- --show-output test_drop_oldest_result_channel
+    #[tokio::test]
+    async fn test_drop_oldest_result_channel() {
+        let dropped = Arc::new(AtomicU64::new(0));
+        let (sink, mut receiver) =
+            result_channel(2, ResultOverflowPolicy::DropOldest, dropped.clone());
+        sink.send(response(1)).await.unwrap();
+        // let forwarding task move first response into the channel
+        assert_eq!(receiver.recv().await.unwrap().speech_event_type, 1);
+
+        for id in 2..=10 {
+            sink.send(response(id)).await.unwrap();
+        }
+        drop(sink);
+        let mut received = vec![];
+        while let Some(response) = receiver.recv().await {
+            received.push(response.speech_event_type);
+        }
+        // newest responses are always delivered
+        assert!(received.ends_with(&[9, 10]));
+        assert_eq!(dropped.load(Ordering::Relaxed) as usize + received.len(), 9);
+    }
+}
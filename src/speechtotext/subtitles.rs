@@ -0,0 +1,220 @@
+//! Generation of subtitles (SRT, WebVTT) from v2 streaming recognition results.
+//! Cues are built from word timings of final results, i.e. streaming config must
+//! have enable_word_time_offsets set.
+use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognizeResponse;
+use crate::errors::{Error, Result};
+use crate::speechtotext::words::{extract_words, WordTiming};
+use std::time::Duration;
+
+/// Max number of characters per subtitle line.
+pub const MAX_LINE_CHARS: usize = 42;
+
+/// Max number of lines per cue.
+pub const MAX_CUE_LINES: usize = 2;
+
+/// Single timed subtitle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    /// offset of the start of the cue relative to the beginning of the audio
+    pub start: Duration,
+
+    /// offset of the end of the cue relative to the beginning of the audio
+    pub end: Duration,
+
+    /// text lines, each at most MAX_LINE_CHARS long (unless single word is longer)
+    pub lines: Vec<String>,
+}
+
+/// Builds cues from words of top alternatives of final results. Cue never spans
+/// multiple results. Returns error with code WORD_TIMINGS_MISSING if any final result
+/// with non-empty transcript contains no words (enable_word_time_offsets is not set).
+pub fn build_cues(responses: &[StreamingRecognizeResponse]) -> Result<Vec<Cue>> {
+    let mut cues = vec![];
+
+    let alternatives = responses
+        .iter()
+        .flat_map(|response| response.results.iter())
+        .filter(|result| result.is_final)
+        .filter_map(|result| result.alternatives.first());
+
+    for alternative in alternatives {
+        if alternative.words.is_empty() {
+            if alternative.transcript.trim().is_empty() {
+                continue;
+            }
+            return Err(Error::new_with_code(
+                format!(
+                    "Word timings missing in result '{}', enable_word_time_offsets must be set",
+                    alternative.transcript.trim()
+                ),
+                "WORD_TIMINGS_MISSING".to_string(),
+            ));
+        }
+
+        let mut cue: Option<Cue> = None;
+        for word in extract_words(alternative) {
+            if let Some(current) = &mut cue {
+                if append_word(current, &word) {
+                    continue;
+                }
+            }
+            cues.extend(cue.replace(new_cue(&word)));
+        }
+        cues.extend(cue);
+    }
+
+    Ok(cues)
+}
+
+fn new_cue(word: &WordTiming) -> Cue {
+    Cue {
+        start: word.start_offset,
+        end: word.end_offset.max(word.start_offset),
+        lines: vec![word.word.clone()],
+    }
+}
+
+/// Appends word to cue. Returns false (cue is not changed) if word does not fit.
+fn append_word(cue: &mut Cue, word: &WordTiming) -> bool {
+    let fits_line = cue
+        .lines
+        .last()
+        .is_some_and(|line| line.chars().count() + 1 + word.word.chars().count() <= MAX_LINE_CHARS);
+    if fits_line {
+        if let Some(line) = cue.lines.last_mut() {
+            line.push(' ');
+            line.push_str(&word.word);
+        }
+    } else if cue.lines.len() < MAX_CUE_LINES {
+        cue.lines.push(word.word.clone());
+    } else {
+        return false;
+    }
+    cue.end = cue.end.max(word.end_offset);
+    true
+}
+
+/// Formats duration as HH:MM:SS followed by separator and milliseconds.
+fn format_timestamp(duration: Duration, millis_separator: char) -> String {
+    let millis = duration.as_millis();
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis_separator,
+        millis % 1000
+    )
+}
+
+/// Converts final results into SubRip (SRT) subtitles, see build_cues.
+pub fn to_srt(responses: &[StreamingRecognizeResponse]) -> Result<String> {
+    Ok(build_cues(responses)?
+        .iter()
+        .enumerate()
+        .map(|(index, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_timestamp(cue.start, ','),
+                format_timestamp(cue.end, ','),
+                cue.lines.join("\n")
+            )
+        })
+        .collect())
+}
+
+/// Converts final results into WebVTT subtitles, see build_cues.
+pub fn to_webvtt(responses: &[StreamingRecognizeResponse]) -> Result<String> {
+    let cues: String = build_cues(responses)?
+        .iter()
+        .map(|cue| {
+            format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(cue.start, '.'),
+                format_timestamp(cue.end, '.'),
+                cue.lines.join("\n")
+            )
+        })
+        .collect();
+    Ok(format!("WEBVTT\n\n{}", cues))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        SpeechRecognitionAlternative, StreamingRecognitionResult, WordInfo,
+    };
+
+    #[allow(dead_code)]
+    fn millis(millis: u64) -> Option<prost_types::Duration> {
+        Some(prost_types::Duration {
+            seconds: (millis / 1000) as i64,
+            nanos: ((millis % 1000) * 1_000_000) as i32,
+        })
+    }
+
+    /// final result where every word takes 500ms
+    #[allow(dead_code)]
+    fn final_result(transcript: &str, start_millis: u64) -> StreamingRecognizeResponse {
+        let words = transcript
+            .split_whitespace()
+            .enumerate()
+            .map(|(i, word)| WordInfo {
+                word: word.to_string(),
+                start_offset: millis(start_millis + i as u64 * 500),
+                end_offset: millis(start_millis + (i as u64 + 1) * 500),
+                ..Default::default()
+            })
+            .collect();
+        StreamingRecognizeResponse {
+            results: vec![StreamingRecognitionResult {
+                alternatives: vec![SpeechRecognitionAlternative {
+                    transcript: transcript.to_string(),
+                    words,
+                    ..Default::default()
+                }],
+                is_final: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_subtitles
+    #[test]
+    fn test_subtitles() {
+        let responses = vec![
+            final_result("hello world", 0),
+            final_result("bye", 3_600_000),
+        ];
+        assert_eq!(
+            to_srt(&responses).unwrap(),
+            "1\n00:00:00,000 --> 00:00:01,000\nhello world\n\n\
+             2\n01:00:00,000 --> 01:00:00,500\nbye\n\n"
+        );
+        assert_eq!(
+            to_webvtt(&responses).unwrap(),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.000\nhello world\n\n\
+             01:00:00.000 --> 01:00:00.500\nbye\n\n"
+        );
+
+        // long result is split into lines and cues
+        let long = "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor incididunt ut labore et dolore magna aliqua";
+        let cues = build_cues(&[final_result(long, 0)]).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].lines.len(), MAX_CUE_LINES);
+        assert!(cues
+            .iter()
+            .flat_map(|cue| cue.lines.iter())
+            .all(|line| line.len() <= MAX_LINE_CHARS));
+        assert_eq!(cues[1].start, cues[0].end);
+
+        let mut no_words = final_result("hello", 0);
+        no_words.results[0].alternatives[0].words.clear();
+        let error = to_srt(&[no_words]).unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("WORD_TIMINGS_MISSING"));
+    }
+}
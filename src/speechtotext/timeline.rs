@@ -0,0 +1,118 @@
+//! Rebasing of streaming result offsets across stream restarts.
+//! Result offsets returned by the API are relative to the start of audio of the current
+//! streaming session, i.e. they reset when stream is re-established. TimelineTracker
+//! converts them to offsets relative to the very first byte of audio so that
+//! timestamps (e.g. of subtitles) remain monotonic through reconnects.
+use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognizeResponse;
+use crate::common::convert::to_std_duration;
+use std::time::Duration;
+
+/// Tracks offset of the current streaming session within the whole audio.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineTracker {
+    /// offset (from the very beginning of audio) where current session started
+    session_start: Duration,
+    /// latest rebased end time returned so far
+    last_end_time: Duration,
+}
+
+impl TimelineTracker {
+    /// Creates tracker for the first streaming session.
+    pub fn new() -> Self {
+        TimelineTracker::default()
+    }
+
+    /// To be called when new streaming session is started with audio following
+    /// previous session audio seamlessly (nothing re-sent, nothing skipped).
+    /// New session is assumed to start at latest rebased end time.
+    pub fn restart(&mut self) {
+        self.session_start = self.last_end_time;
+    }
+
+    /// To be called when new streaming session is started with audio starting at given
+    /// offset (from the very beginning of audio), e.g. when audio after last acknowledged
+    /// result is re-sent (see AudioCheckpointBuffer::checkpoint_offset).
+    pub fn restart_at(&mut self, offset: Duration) {
+        self.session_start = offset;
+    }
+
+    /// Returns offset where current session started.
+    pub fn session_start(&self) -> Duration {
+        self.session_start
+    }
+
+    /// Converts offset relative to current session into offset relative to the very
+    /// beginning of audio.
+    pub fn rebase(&self, session_offset: Duration) -> Duration {
+        self.session_start + session_offset
+    }
+
+    /// Returns latest result_end_offset of response rebased to the very beginning of audio.
+    /// Returned values never decrease, i.e. response without results (or with results
+    /// ending before already returned time) yields previously returned value.
+    pub fn rebased_end_time(&mut self, response: &StreamingRecognizeResponse) -> Duration {
+        let end_time = response
+            .results
+            .iter()
+            .filter_map(|result| result.result_end_offset.as_ref())
+            .map(|offset| self.rebase(to_std_duration(offset)))
+            .max()
+            .unwrap_or_default();
+        self.last_end_time = self.last_end_time.max(end_time);
+        self.last_end_time
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::StreamingRecognitionResult;
+
+    #[allow(dead_code)]
+    fn response(end_offset_millis: i32) -> StreamingRecognizeResponse {
+        StreamingRecognizeResponse {
+            results: vec![StreamingRecognitionResult {
+                result_end_offset: Some(prost_types::Duration {
+                    seconds: 0,
+                    nanos: end_offset_millis * 1_000_000,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_timeline_tracker
+    #[test]
+    fn test_timeline_tracker() {
+        let mut tracker = TimelineTracker::new();
+        assert_eq!(
+            tracker.rebased_end_time(&response(800)),
+            Duration::from_millis(800)
+        );
+
+        // seamless restart, offsets reset
+        tracker.restart();
+        assert_eq!(
+            tracker.rebased_end_time(&response(300)),
+            Duration::from_millis(1100)
+        );
+
+        // restart with audio re-sent from checkpoint at 1000ms
+        tracker.restart_at(Duration::from_millis(1000));
+        // result ending before already returned time keeps timeline monotonic
+        assert_eq!(
+            tracker.rebased_end_time(&response(50)),
+            Duration::from_millis(1100)
+        );
+        assert_eq!(
+            tracker.rebased_end_time(&response(500)),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            tracker.rebased_end_time(&StreamingRecognizeResponse::default()),
+            Duration::from_millis(1500)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Reconstruction of utterance boundaries from v2 streaming recognition results.
+//! Google may split one continuous utterance into several final results,
+//! adjacent finals separated by less than given gap are merged into one utterance.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    StreamingRecognitionResult, StreamingRecognizeResponse,
+};
+use crate::common::convert::to_std_duration;
+use std::time::Duration;
+
+/// Default max gap between final results merged into one utterance.
+pub const DEFAULT_UTTERANCE_GAP: Duration = Duration::from_millis(500);
+
+/// Continuous piece of speech made of one or more final results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Utterance {
+    /// joined transcripts (top alternative) of merged final results
+    pub text: String,
+
+    /// offset of the start of the utterance relative to the beginning of the audio
+    pub start: Duration,
+
+    /// offset of the end of the utterance relative to the beginning of the audio
+    pub end: Duration,
+}
+
+/// Same as merge_finals_with_gap with DEFAULT_UTTERANCE_GAP.
+pub fn merge_finals(responses: &[StreamingRecognizeResponse]) -> Vec<Utterance> {
+    merge_finals_with_gap(responses, DEFAULT_UTTERANCE_GAP)
+}
+
+/// Merges adjacent final results (interim results are ignored) separated by less than
+/// max_gap into utterances. Start of the result is taken from the first word offset,
+/// i.e. enable_word_time_offsets must be set, otherwise start is unknown and the result
+/// is treated as starting right after previous one (i.e. it is always merged).
+pub fn merge_finals_with_gap(
+    responses: &[StreamingRecognizeResponse],
+    max_gap: Duration,
+) -> Vec<Utterance> {
+    let mut utterances: Vec<Utterance> = vec![];
+    let mut previous_end = Duration::ZERO;
+
+    let finals = responses
+        .iter()
+        .flat_map(|response| response.results.iter())
+        .filter(|result| result.is_final);
+
+    for result in finals {
+        let text = match result.alternatives.first() {
+            Some(alternative) if !alternative.transcript.trim().is_empty() => {
+                alternative.transcript.trim()
+            }
+            _ => continue,
+        };
+        let end = result
+            .result_end_offset
+            .as_ref()
+            .map(to_std_duration)
+            .unwrap_or(previous_end);
+        let start = result_start(result).unwrap_or(previous_end).min(end);
+
+        match utterances.last_mut() {
+            Some(utterance) if start.saturating_sub(utterance.end) < max_gap => {
+                utterance.text.push(' ');
+                utterance.text.push_str(text);
+                utterance.end = end.max(utterance.end);
+            }
+            _ => utterances.push(Utterance {
+                text: text.to_string(),
+                start,
+                end,
+            }),
+        }
+        previous_end = end;
+    }
+
+    utterances
+}
+
+/// Returns start offset of the first word of the top alternative (if available).
+fn result_start(result: &StreamingRecognitionResult) -> Option<Duration> {
+    result
+        .alternatives
+        .first()?
+        .words
+        .first()?
+        .start_offset
+        .as_ref()
+        .map(to_std_duration)
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::{
+        SpeechRecognitionAlternative, WordInfo,
+    };
+
+    #[allow(dead_code)]
+    fn millis(millis: u64) -> prost_types::Duration {
+        prost_types::Duration {
+            seconds: (millis / 1000) as i64,
+            nanos: ((millis % 1000) * 1_000_000) as i32,
+        }
+    }
+
+    #[allow(dead_code)]
+    fn final_result(transcript: &str, start: u64, end: u64) -> StreamingRecognizeResponse {
+        StreamingRecognizeResponse {
+            results: vec![StreamingRecognitionResult {
+                alternatives: vec![SpeechRecognitionAlternative {
+                    transcript: transcript.to_string(),
+                    words: vec![WordInfo {
+                        start_offset: Some(millis(start)),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                is_final: true,
+                result_end_offset: Some(millis(end)),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_merge_finals
+    #[test]
+    fn test_merge_finals() {
+        let mut interim = final_result("ignored", 0, 100);
+        interim.results[0].is_final = false;
+
+        let responses = vec![
+            final_result("hello", 0, 800),
+            interim,
+            final_result(" world ", 900, 1500),
+            final_result("next sentence", 3000, 4200),
+        ];
+
+        assert_eq!(
+            merge_finals(&responses),
+            vec![
+                Utterance {
+                    text: "hello world".to_string(),
+                    start: Duration::ZERO,
+                    end: Duration::from_millis(1500),
+                },
+                Utterance {
+                    text: "next sentence".to_string(),
+                    start: Duration::from_secs(3),
+                    end: Duration::from_millis(4200),
+                },
+            ]
+        );
+
+        // smaller gap splits first utterance
+        let utterances = merge_finals_with_gap(&responses, Duration::from_millis(50));
+        assert_eq!(utterances.len(), 3);
+        assert_eq!(utterances[1].start, Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,286 @@
+//! Borrowed, typed view of v2 recognition responses. The same API navigates results of
+//! synchronous (RecognizeResponse) and streaming (StreamingRecognizeResponse) recognition
+//! without copying data and without importing generated types.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    RecognizeResponse, SpeechRecognitionAlternative, SpeechRecognitionResult,
+    StreamingRecognitionResult, StreamingRecognizeResponse,
+};
+use crate::common::convert::to_std_duration;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+enum Results<'a> {
+    Sync(&'a [SpeechRecognitionResult]),
+    Streaming(&'a [StreamingRecognitionResult]),
+}
+
+/// View of recognition response. Created from &RecognizeResponse
+/// or &StreamingRecognizeResponse, iterates over results.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseView<'a> {
+    results: Results<'a>,
+}
+
+/// View of single recognition result.
+#[derive(Debug, Clone, Copy)]
+pub struct ResultView<'a> {
+    alternatives: &'a [SpeechRecognitionAlternative],
+    is_final: bool,
+    channel_tag: i32,
+    language_code: &'a str,
+    end_offset: Option<&'a prost_types::Duration>,
+}
+
+/// View of single recognition alternative.
+#[derive(Debug, Clone, Copy)]
+pub struct AlternativeView<'a> {
+    alternative: &'a SpeechRecognitionAlternative,
+}
+
+impl<'a> From<&'a RecognizeResponse> for ResponseView<'a> {
+    fn from(response: &'a RecognizeResponse) -> Self {
+        ResponseView {
+            results: Results::Sync(&response.results),
+        }
+    }
+}
+
+impl<'a> From<&'a StreamingRecognizeResponse> for ResponseView<'a> {
+    fn from(response: &'a StreamingRecognizeResponse) -> Self {
+        ResponseView {
+            results: Results::Streaming(&response.results),
+        }
+    }
+}
+
+impl<'a> ResponseView<'a> {
+    /// Returns number of results.
+    pub fn len(self) -> usize {
+        match self.results {
+            Results::Sync(results) => results.len(),
+            Results::Streaming(results) => results.len(),
+        }
+    }
+
+    /// Returns true if response contains no results.
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns result at given index.
+    pub fn get(self, index: usize) -> Option<ResultView<'a>> {
+        match self.results {
+            Results::Sync(results) => results.get(index).map(|result| ResultView {
+                alternatives: &result.alternatives,
+                is_final: true,
+                channel_tag: result.channel_tag,
+                language_code: &result.language_code,
+                end_offset: result.result_end_offset.as_ref(),
+            }),
+            Results::Streaming(results) => results.get(index).map(|result| ResultView {
+                alternatives: &result.alternatives,
+                is_final: result.is_final,
+                channel_tag: result.channel_tag,
+                language_code: &result.language_code,
+                end_offset: result.result_end_offset.as_ref(),
+            }),
+        }
+    }
+
+    /// Iterates over all results.
+    pub fn results(self) -> ResultIter<'a> {
+        self.into_iter()
+    }
+
+    /// Iterates over final results (all results of synchronous recognition are final).
+    pub fn finals(self) -> impl Iterator<Item = ResultView<'a>> + 'a {
+        self.results().filter(|result| result.is_final())
+    }
+
+    /// Iterates over top alternatives of all results.
+    pub fn best(self) -> impl Iterator<Item = AlternativeView<'a>> + 'a {
+        self.results().filter_map(ResultView::best)
+    }
+
+    /// Iterates over all alternatives of all results.
+    pub fn alternatives(self) -> impl Iterator<Item = AlternativeView<'a>> + 'a {
+        self.results().flat_map(ResultView::alternatives)
+    }
+
+    /// Returns transcripts of top alternatives of final results joined by space.
+    pub fn transcript(self) -> String {
+        self.finals()
+            .filter_map(ResultView::best)
+            .map(|alternative| alternative.transcript().trim())
+            .filter(|transcript| !transcript.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Iterator over results of ResponseView.
+#[derive(Debug, Clone)]
+pub struct ResultIter<'a> {
+    view: ResponseView<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for ResultIter<'a> {
+    type Item = ResultView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.view.get(self.index)?;
+        self.index += 1;
+        Some(result)
+    }
+}
+
+impl<'a> IntoIterator for ResponseView<'a> {
+    type Item = ResultView<'a>;
+    type IntoIter = ResultIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ResultIter {
+            view: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> ResultView<'a> {
+    /// Returns true for final results (always true for synchronous recognition).
+    pub fn is_final(self) -> bool {
+        self.is_final
+    }
+
+    /// Returns channel number for multi-channel audio.
+    pub fn channel_tag(self) -> i32 {
+        self.channel_tag
+    }
+
+    /// Returns language detected in this result.
+    pub fn language_code(self) -> &'a str {
+        self.language_code
+    }
+
+    /// Returns offset of the end of this result relative to the beginning of the audio.
+    pub fn end_offset(self) -> Duration {
+        self.end_offset.map(to_std_duration).unwrap_or_default()
+    }
+
+    /// Returns top alternative.
+    pub fn best(self) -> Option<AlternativeView<'a>> {
+        self.alternatives
+            .first()
+            .map(|alternative| AlternativeView { alternative })
+    }
+
+    /// Iterates over alternatives (most probable first).
+    pub fn alternatives(self) -> impl Iterator<Item = AlternativeView<'a>> + 'a {
+        self.alternatives
+            .iter()
+            .map(|alternative| AlternativeView { alternative })
+    }
+}
+
+impl<'a> AlternativeView<'a> {
+    /// Returns transcript of the alternative.
+    pub fn transcript(self) -> &'a str {
+        &self.alternative.transcript
+    }
+
+    /// Returns confidence estimate (0.0 - 1.0), None if not provided by the API.
+    pub fn confidence(self) -> Option<f32> {
+        if self.alternative.confidence > 0.0 {
+            Some(self.alternative.confidence)
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over words of the alternative (populated only if word
+    /// time offsets or word confidence were requested).
+    pub fn words(self) -> impl Iterator<Item = &'a str> + 'a {
+        self.alternative
+            .words
+            .iter()
+            .map(|word_info| word_info.word.as_str())
+    }
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[allow(dead_code)]
+    fn alternative(transcript: &str, confidence: f32) -> SpeechRecognitionAlternative {
+        SpeechRecognitionAlternative {
+            transcript: transcript.to_string(),
+            confidence,
+            ..Default::default()
+        }
+    }
+
+    // cargo test -- --show-output test_response_view
+    #[test]
+    fn test_response_view() {
+        let response = RecognizeResponse {
+            results: vec![
+                SpeechRecognitionResult {
+                    alternatives: vec![alternative("hello", 0.9), alternative("yellow", 0.0)],
+                    language_code: "en-us".to_string(),
+                    ..Default::default()
+                },
+                SpeechRecognitionResult {
+                    alternatives: vec![alternative("world", 0.8)],
+                    channel_tag: 2,
+                    ..Default::default()
+                },
+            ],
+            metadata: None,
+        };
+        let view = ResponseView::from(&response);
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.finals().count(), 2);
+        assert_eq!(
+            view.best()
+                .map(AlternativeView::transcript)
+                .collect::<Vec<_>>(),
+            vec!["hello", "world"]
+        );
+        assert_eq!(view.alternatives().count(), 3);
+        assert_eq!(view.transcript(), "hello world");
+        let first = view.into_iter().next().unwrap();
+        assert_eq!(first.language_code(), "en-us");
+        assert_eq!(first.best().unwrap().confidence(), Some(0.9));
+        assert_eq!(view.get(1).unwrap().channel_tag(), 2);
+
+        let streaming = StreamingRecognizeResponse {
+            results: vec![
+                StreamingRecognitionResult {
+                    alternatives: vec![alternative("good morning", 0.0)],
+                    is_final: true,
+                    result_end_offset: Some(prost_types::Duration {
+                        seconds: 2,
+                        nanos: 0,
+                    }),
+                    ..Default::default()
+                },
+                StreamingRecognitionResult {
+                    alternatives: vec![alternative("every", 0.0)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let view = ResponseView::from(&streaming);
+        assert_eq!(view.finals().count(), 1);
+        assert_eq!(view.best().count(), 2);
+        assert_eq!(view.transcript(), "good morning");
+        assert_eq!(
+            view.finals().next().unwrap().end_offset(),
+            Duration::from_secs(2)
+        );
+        assert_eq!(view.get(0).unwrap().best().unwrap().confidence(), None);
+    }
+}
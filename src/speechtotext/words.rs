@@ -0,0 +1,269 @@
+//! Helpers for extracting word level information from v2 recognition results.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    RecognizeResponse, SpeechRecognitionAlternative, WordInfo,
+};
+use crate::common::convert::to_std_duration;
+use std::time::Duration;
+
+/// Word of the top alternative together with its timing and confidence.
+/// Timing is populated only if enable_word_time_offsets is set,
+/// confidence only if enable_word_confidence is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTiming {
+    /// recognized word
+    pub word: String,
+
+    /// offset of the start of the word relative to the beginning of the audio
+    pub start_offset: Duration,
+
+    /// offset of the end of the word relative to the beginning of the audio
+    pub end_offset: Duration,
+
+    /// confidence estimate (0.0 - 1.0). None if confidence was not provided by the API
+    /// (API uses 0.0 as sentinel value for not set confidence)
+    pub confidence: Option<f32>,
+
+    /// speaker label (if diarization is enabled)
+    pub speaker_label: Option<String>,
+}
+
+impl From<&WordInfo> for WordTiming {
+    fn from(word_info: &WordInfo) -> Self {
+        WordTiming {
+            word: word_info.word.clone(),
+            start_offset: word_info
+                .start_offset
+                .as_ref()
+                .map(to_std_duration)
+                .unwrap_or_default(),
+            end_offset: word_info
+                .end_offset
+                .as_ref()
+                .map(to_std_duration)
+                .unwrap_or_default(),
+            confidence: if word_info.confidence > 0.0 {
+                Some(word_info.confidence)
+            } else {
+                None
+            },
+            speaker_label: if word_info.speaker_label.is_empty() {
+                None
+            } else {
+                Some(word_info.speaker_label.clone())
+            },
+        }
+    }
+}
+
+/// Extracts words of single recognition alternative.
+pub fn extract_words(alternative: &SpeechRecognitionAlternative) -> Vec<WordTiming> {
+    alternative.words.iter().map(WordTiming::from).collect()
+}
+
+/// Extracts words of top alternatives of all results of synchronous recognition.
+pub fn extract_response_words(response: &RecognizeResponse) -> Vec<WordTiming> {
+    response
+        .results
+        .iter()
+        .filter_map(|result| result.alternatives.first())
+        .flat_map(extract_words)
+        .collect()
+}
+
+/// Candidate word at position of a word of the top alternative (see extract_word_alternatives).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordAlt {
+    /// candidate word
+    pub word: String,
+
+    /// confidence of the alternative the word comes from. None if not provided by the API
+    pub confidence: Option<f32>,
+
+    /// index of the alternative (within its result) the word comes from, 0 = top alternative
+    pub alternative_index: usize,
+}
+
+/// Returns per-word alternatives (confusion network) of all results of synchronous recognition.
+/// Every item corresponds to one word of the top alternative and contains that word first,
+/// followed by distinct words other alternatives have at the same position.
+///
+/// Google API (v2) does not provide per-word alternatives, this is client-side approximation:
+/// N-best alternatives (max_alternatives > 1 must be requested) are aligned with the top
+/// alternative word by word (minimal edit distance). Only substituted words are collected,
+/// words inserted or deleted by other alternatives are ignored. Confidence is available
+/// for whole alternatives only (typically for the top one).
+pub fn extract_word_alternatives(response: &RecognizeResponse) -> Vec<Vec<WordAlt>> {
+    let mut word_alternatives = vec![];
+    for result in &response.results {
+        let mut alternatives = result.alternatives.iter().enumerate();
+        let top = match alternatives.next() {
+            Some((_, top)) => top,
+            None => continue,
+        };
+        let top_words: Vec<&str> = top.transcript.split_whitespace().collect();
+        let mut slots: Vec<Vec<WordAlt>> = top_words
+            .iter()
+            .map(|word| {
+                vec![WordAlt {
+                    word: word.to_string(),
+                    confidence: alternative_confidence(top),
+                    alternative_index: 0,
+                }]
+            })
+            .collect();
+
+        for (alternative_index, alternative) in alternatives {
+            let words: Vec<&str> = alternative.transcript.split_whitespace().collect();
+            for (position, word) in align_words(&top_words, &words) {
+                let slot = &mut slots[position];
+                if !slot
+                    .iter()
+                    .any(|candidate| candidate.word.to_lowercase() == word.to_lowercase())
+                {
+                    slot.push(WordAlt {
+                        word: word.to_string(),
+                        confidence: alternative_confidence(alternative),
+                        alternative_index,
+                    });
+                }
+            }
+        }
+        word_alternatives.extend(slots);
+    }
+    word_alternatives
+}
+
+fn alternative_confidence(alternative: &SpeechRecognitionAlternative) -> Option<f32> {
+    if alternative.confidence > 0.0 {
+        Some(alternative.confidence)
+    } else {
+        None
+    }
+}
+
+/// Aligns words with top words (minimal edit distance, case insensitive)
+/// and returns (position in top words, word) of matched and substituted words.
+fn align_words<'a>(top: &[&str], words: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let same = |i: usize, j: usize| top[i].to_lowercase() == words[j].to_lowercase();
+
+    // distance[i][j] = edit distance of top[i..] and words[j..]
+    let mut distance = vec![vec![0usize; words.len() + 1]; top.len() + 1];
+    for i in (0..=top.len()).rev() {
+        for j in (0..=words.len()).rev() {
+            distance[i][j] = if i == top.len() {
+                words.len() - j
+            } else if j == words.len() {
+                top.len() - i
+            } else {
+                let substitution = distance[i + 1][j + 1] + usize::from(!same(i, j));
+                substitution
+                    .min(distance[i + 1][j] + 1)
+                    .min(distance[i][j + 1] + 1)
+            };
+        }
+    }
+
+    let mut aligned = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < top.len() && j < words.len() {
+        if distance[i][j] == distance[i + 1][j + 1] + usize::from(!same(i, j)) {
+            aligned.push((i, words[j]));
+            i += 1;
+            j += 1;
+        } else if distance[i][j] == distance[i + 1][j] + 1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    aligned
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::api::grpc::google::cloud::speechtotext::v2::SpeechRecognitionResult;
+
+    // cargo test -- --show-output test_extract_word_alternatives
+    #[test]
+    fn test_extract_word_alternatives() {
+        let alternative = |transcript: &str, confidence: f32| SpeechRecognitionAlternative {
+            transcript: transcript.to_string(),
+            confidence,
+            ..Default::default()
+        };
+        let response = RecognizeResponse {
+            results: vec![
+                SpeechRecognitionResult {
+                    alternatives: vec![
+                        alternative("call bob now", 0.9),
+                        alternative("call rob now", 0.0),
+                        alternative("call Bob right now", 0.0),
+                        alternative("fall bob", 0.0),
+                    ],
+                    ..Default::default()
+                },
+                SpeechRecognitionResult {
+                    alternatives: vec![alternative("please", 0.0)],
+                    ..Default::default()
+                },
+            ],
+            metadata: None,
+        };
+
+        let slots = extract_word_alternatives(&response);
+        let words = |slot: &Vec<WordAlt>| -> Vec<String> {
+            slot.iter()
+                .map(|candidate| candidate.word.clone())
+                .collect()
+        };
+        assert_eq!(slots.len(), 4);
+        assert_eq!(words(&slots[0]), vec!["call", "fall"]);
+        assert_eq!(words(&slots[1]), vec!["bob", "rob"]);
+        // inserted "right" is ignored
+        assert_eq!(words(&slots[2]), vec!["now"]);
+        assert_eq!(words(&slots[3]), vec!["please"]);
+        assert_eq!(slots[0][0].confidence, Some(0.9));
+        assert_eq!(slots[1][1].alternative_index, 1);
+        assert_eq!(slots[0][1].alternative_index, 3);
+    }
+
+    // cargo test -- --show-output test_extract_words
+    #[test]
+    fn test_extract_words() {
+        let alternative = SpeechRecognitionAlternative {
+            transcript: "hello world".to_string(),
+            confidence: 0.9,
+            words: vec![
+                WordInfo {
+                    start_offset: Some(prost_types::Duration {
+                        seconds: 1,
+                        nanos: 0,
+                    }),
+                    end_offset: Some(prost_types::Duration {
+                        seconds: 1,
+                        nanos: 500_000_000,
+                    }),
+                    word: "hello".to_string(),
+                    confidence: 0.8,
+                    speaker_label: "1".to_string(),
+                },
+                WordInfo {
+                    word: "world".to_string(),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let words = extract_words(&alternative);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].start_offset, Duration::from_secs(1));
+        assert_eq!(words[0].end_offset, Duration::from_millis(1500));
+        assert_eq!(words[0].confidence, Some(0.8));
+        assert_eq!(words[0].speaker_label, Some("1".to_string()));
+        assert_eq!(words[1].word, "world");
+        assert_eq!(words[1].confidence, None);
+        assert_eq!(words[1].speaker_label, None);
+    }
+}
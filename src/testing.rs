@@ -0,0 +1,392 @@
+//! In-process mock of Google Speech v2 API. Intended for testing of code built
+//! on top of this library (reconnects, stream finalization, error mapping, ...)
+//! without calling Google. Available only with feature testing.
+//!
+//! Mock is scripted with canned responses/errors, started with MockSpeech::serve
+//! and returned channel is passed to Recognizer::create_*_recognizer_with_channel.
+use crate::api::grpc::google::cloud::speechtotext::v2::{
+    speech_server::{Speech, SpeechServer},
+    *,
+};
+use crate::api::grpc::google::longrunning::{
+    operation::Result as OperationResult,
+    operations_server::{Operations, OperationsServer},
+    CancelOperationRequest, DeleteOperationRequest, GetOperationRequest, ListOperationsRequest,
+    ListOperationsResponse, Operation, WaitOperationRequest,
+};
+use crate::api::grpc::google::rpc::Status as RpcStatus;
+use crate::errors::Result;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::codec::CompressionEncoding;
+use tonic::metadata::MetadataMap;
+use tonic::transport::{Channel, Server};
+use tonic::{Request, Response, Status, Streaming};
+
+/// Number of operations per page returned by list_operations when page size is not set.
+const MOCK_OPERATIONS_PAGE_SIZE: usize = 2;
+
+/// Scripted event of mocked streaming_recognize call.
+#[derive(Debug)]
+pub enum MockStreamEvent {
+    /// response sent to the client
+    Response(StreamingRecognizeResponse),
+    /// error sent to the client, stream is terminated afterwards
+    Error(Status),
+    /// response stream is closed immediately (without waiting for end of audio)
+    Close,
+}
+
+/// Request received by mock server.
+#[derive(Debug, Clone)]
+pub struct Received<T> {
+    /// request metadata (headers), e.g. authorization or grpc-encoding
+    pub metadata: MetadataMap,
+    /// request message
+    pub message: T,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    recognize_results: VecDeque<std::result::Result<RecognizeResponse, Status>>,
+    streaming_scripts: VecDeque<Vec<MockStreamEvent>>,
+    recognize_requests: Vec<Received<RecognizeRequest>>,
+    streaming_requests: Vec<Vec<StreamingRecognizeRequest>>,
+    streaming_request_times: Vec<Vec<Instant>>,
+    batch_operations: VecDeque<Operation>,
+    operations: HashMap<String, Operation>,
+    cancelled_operations: Vec<String>,
+    cancel_errors: HashMap<String, Status>,
+    recognize_delay: Duration,
+    max_concurrent_streams: Option<u32>,
+}
+
+/// Scriptable mock of Speech service. Clones share the same state, i.e. script
+/// can be extended and received requests inspected while server is running.
+#[derive(Debug, Clone, Default)]
+pub struct MockSpeech {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockSpeech {
+    /// Creates new mock with empty script.
+    pub fn new() -> Self {
+        MockSpeech::default()
+    }
+
+    /// Queues response returned by next recognize call.
+    /// Calls with empty queue return default (empty) response.
+    pub fn push_recognize_response(&self, response: RecognizeResponse) {
+        self.lock().recognize_results.push_back(Ok(response));
+    }
+
+    /// Queues error returned by next recognize call.
+    pub fn push_recognize_error(&self, status: Status) {
+        self.lock().recognize_results.push_back(Err(status));
+    }
+
+    /// Delays response of every recognize call, e.g. to simulate processing time of Google.
+    pub fn set_recognize_delay(&self, delay: Duration) {
+        self.lock().recognize_delay = delay;
+    }
+
+    /// Limits number of concurrent calls (HTTP/2 streams) per connection of servers
+    /// started afterwards, e.g. to simulate per connection limit of Google frontends.
+    pub fn set_max_concurrent_streams(&self, max_concurrent_streams: u32) {
+        self.lock().max_concurrent_streams = Some(max_concurrent_streams);
+    }
+
+    /// Queues script of next streaming_recognize call. Events are sent right after stream
+    /// is opened. Unless script ends with Error or Close, response stream is closed once
+    /// client closes audio stream. Calls with empty queue just consume audio.
+    pub fn push_streaming_script(&self, events: Vec<MockStreamEvent>) {
+        self.lock().streaming_scripts.push_back(events);
+    }
+
+    /// Queues operation returned by next batch_recognize call. Operation is also
+    /// registered for get_operation/cancel_operation calls of Operations service.
+    pub fn push_batch_operation(&self, operation: Operation) {
+        self.set_operation(operation.clone());
+        self.lock().batch_operations.push_back(operation);
+    }
+
+    /// Registers (or replaces) operation returned by get_operation and list_operations calls,
+    /// e.g. to mark operation done. list_operations returns pages of 2 operations
+    /// (unless page size is set) ordered by name.
+    pub fn set_operation(&self, operation: Operation) {
+        self.lock()
+            .operations
+            .insert(operation.name.clone(), operation);
+    }
+
+    /// Makes cancel_operation of operation with given name fail with status.
+    pub fn set_cancel_error(&self, operation_name: &str, status: Status) {
+        self.lock()
+            .cancel_errors
+            .insert(operation_name.to_string(), status);
+    }
+
+    /// Returns names of operations cancelled so far.
+    pub fn cancelled_operations(&self) -> Vec<String> {
+        self.lock().cancelled_operations.clone()
+    }
+
+    /// Returns all recognize requests received so far.
+    pub fn recognize_requests(&self) -> Vec<Received<RecognizeRequest>> {
+        self.lock().recognize_requests.clone()
+    }
+
+    /// Returns requests received by finished streaming_recognize calls (one vector per call).
+    pub fn streaming_requests(&self) -> Vec<Vec<StreamingRecognizeRequest>> {
+        self.lock().streaming_requests.clone()
+    }
+
+    /// Returns times when requests of finished streaming_recognize calls were received
+    /// (same layout as streaming_requests), e.g. to measure latency of audio chunks.
+    pub fn streaming_request_times(&self) -> Vec<Vec<Instant>> {
+        self.lock().streaming_request_times.clone()
+    }
+
+    /// Starts mock server on random local port and returns channel connected to it.
+    /// Server provides Speech and Operations services.
+    /// Server accepts and sends gzip compressed messages. Server runs until
+    /// the tokio runtime is shut down.
+    pub async fn serve(&self) -> Result<Channel> {
+        let addr = self.serve_addr().await?;
+        Ok(Channel::from_shared(format!("http://{}", addr))
+            .expect("valid mock server uri")
+            .connect()
+            .await?)
+    }
+
+    /// Same as serve, but only address of started server is returned,
+    /// e.g. to build custom channels (see common::PooledChannel).
+    pub async fn serve_addr(&self) -> Result<SocketAddr> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let service = SpeechServer::new(self.clone())
+            .accept_compressed(CompressionEncoding::Gzip)
+            .send_compressed(CompressionEncoding::Gzip);
+        let operations_service = OperationsServer::new(self.clone());
+        let max_concurrent_streams = self.lock().max_concurrent_streams;
+        tokio::spawn(async move {
+            let _ = Server::builder()
+                .max_concurrent_streams(max_concurrent_streams)
+                .add_service(service)
+                .add_service(operations_service)
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await;
+        });
+
+        Ok(addr)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().expect("mock state lock poisoned")
+    }
+}
+
+/// Generates Speech implementation for MockSpeech. Only recognize and streaming_recognize
+/// are scripted, all other methods return UNIMPLEMENTED. Whole impl block must be generated
+/// at once since async_trait does not expand macros nested in impl block.
+macro_rules! mock_speech_impl {
+    ($( $name:ident ( $req:ty ) -> $resp:ty ),* $(,)?) => {
+        #[tonic::async_trait]
+        impl Speech for MockSpeech {
+            async fn recognize(
+                &self,
+                request: Request<RecognizeRequest>,
+            ) -> std::result::Result<Response<RecognizeResponse>, Status> {
+                let (result, delay) = {
+                    let mut state = self.lock();
+                    state.recognize_requests.push(Received {
+                        metadata: request.metadata().clone(),
+                        message: request.into_inner(),
+                    });
+                    (state.recognize_results.pop_front(), state.recognize_delay)
+                };
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                match result {
+                    Some(result) => result.map(Response::new),
+                    None => Ok(Response::new(RecognizeResponse::default())),
+                }
+            }
+
+            async fn batch_recognize(
+                &self,
+                _request: Request<BatchRecognizeRequest>,
+            ) -> std::result::Result<Response<Operation>, Status> {
+                match self.lock().batch_operations.pop_front() {
+                    Some(operation) => Ok(Response::new(operation)),
+                    None => Err(Status::unimplemented("no batch operation scripted")),
+                }
+            }
+
+            type StreamingRecognizeStream =
+                ReceiverStream<std::result::Result<StreamingRecognizeResponse, Status>>;
+
+            async fn streaming_recognize(
+                &self,
+                request: Request<Streaming<StreamingRecognizeRequest>>,
+            ) -> std::result::Result<Response<Self::StreamingRecognizeStream>, Status> {
+                let script = self.lock().streaming_scripts.pop_front().unwrap_or_default();
+                let mut inbound = request.into_inner();
+                let (response_sender, response_receiver) = mpsc::channel(100);
+                let (drained_sender, drained_receiver) = oneshot::channel();
+
+                // records audio until client closes the stream
+                let mock = self.clone();
+                tokio::spawn(async move {
+                    let mut requests = vec![];
+                    let mut request_times = vec![];
+                    while let Ok(Some(request)) = inbound.message().await {
+                        requests.push(request);
+                        request_times.push(Instant::now());
+                    }
+                    let mut state = mock.lock();
+                    state.streaming_requests.push(requests);
+                    state.streaming_request_times.push(request_times);
+                    let _ = drained_sender.send(());
+                });
+
+                tokio::spawn(async move {
+                    for event in script {
+                        match event {
+                            MockStreamEvent::Response(response) => {
+                                if response_sender.send(Ok(response)).await.is_err() {
+                                    return;
+                                }
+                            }
+                            MockStreamEvent::Error(status) => {
+                                let _ = response_sender.send(Err(status)).await;
+                                return;
+                            }
+                            MockStreamEvent::Close => return,
+                        }
+                    }
+                    let _ = drained_receiver.await;
+                });
+
+                Ok(Response::new(ReceiverStream::new(response_receiver)))
+            }
+
+            $(
+                async fn $name(
+                    &self,
+                    _request: Request<$req>,
+                ) -> std::result::Result<Response<$resp>, Status> {
+                    Err(Status::unimplemented(stringify!($name)))
+                }
+            )*
+        }
+    };
+}
+
+mock_speech_impl!(
+    create_recognizer(CreateRecognizerRequest) -> Operation,
+    list_recognizers(ListRecognizersRequest) -> ListRecognizersResponse,
+    get_recognizer(GetRecognizerRequest) -> Recognizer,
+    update_recognizer(UpdateRecognizerRequest) -> Operation,
+    delete_recognizer(DeleteRecognizerRequest) -> Operation,
+    undelete_recognizer(UndeleteRecognizerRequest) -> Operation,
+    get_config(GetConfigRequest) -> Config,
+    update_config(UpdateConfigRequest) -> Config,
+    create_custom_class(CreateCustomClassRequest) -> Operation,
+    list_custom_classes(ListCustomClassesRequest) -> ListCustomClassesResponse,
+    get_custom_class(GetCustomClassRequest) -> CustomClass,
+    update_custom_class(UpdateCustomClassRequest) -> Operation,
+    delete_custom_class(DeleteCustomClassRequest) -> Operation,
+    undelete_custom_class(UndeleteCustomClassRequest) -> Operation,
+    create_phrase_set(CreatePhraseSetRequest) -> Operation,
+    list_phrase_sets(ListPhraseSetsRequest) -> ListPhraseSetsResponse,
+    get_phrase_set(GetPhraseSetRequest) -> PhraseSet,
+    update_phrase_set(UpdatePhraseSetRequest) -> Operation,
+    delete_phrase_set(DeletePhraseSetRequest) -> Operation,
+    undelete_phrase_set(UndeletePhraseSetRequest) -> Operation,
+);
+
+/// Operations service backed by operations registered via push_batch_operation/set_operation.
+/// Cancelled operations are marked done with CANCELLED error.
+#[tonic::async_trait]
+impl Operations for MockSpeech {
+    async fn list_operations(
+        &self,
+        request: Request<ListOperationsRequest>,
+    ) -> std::result::Result<Response<ListOperationsResponse>, Status> {
+        // operations ordered by name, page token is offset of the page, filter is ignored
+        let request = request.into_inner();
+        let mut operations: Vec<Operation> = self.lock().operations.values().cloned().collect();
+        operations.sort_by(|a, b| a.name.cmp(&b.name));
+        let offset = request.page_token.parse::<usize>().unwrap_or(0);
+        let page_size = match request.page_size {
+            size if size > 0 => size as usize,
+            _ => MOCK_OPERATIONS_PAGE_SIZE,
+        };
+        let end = (offset + page_size).min(operations.len());
+        let next_page_token = if end < operations.len() {
+            end.to_string()
+        } else {
+            String::new()
+        };
+        Ok(Response::new(ListOperationsResponse {
+            operations: operations.get(offset..end).unwrap_or_default().to_vec(),
+            next_page_token,
+        }))
+    }
+
+    async fn get_operation(
+        &self,
+        request: Request<GetOperationRequest>,
+    ) -> std::result::Result<Response<Operation>, Status> {
+        let name = request.into_inner().name;
+        match self.lock().operations.get(&name) {
+            Some(operation) => Ok(Response::new(operation.clone())),
+            None => Err(Status::not_found(name)),
+        }
+    }
+
+    async fn delete_operation(
+        &self,
+        _request: Request<DeleteOperationRequest>,
+    ) -> std::result::Result<Response<()>, Status> {
+        Err(Status::unimplemented("delete_operation"))
+    }
+
+    async fn cancel_operation(
+        &self,
+        request: Request<CancelOperationRequest>,
+    ) -> std::result::Result<Response<()>, Status> {
+        let name = request.into_inner().name;
+        let mut state = self.lock();
+        if let Some(status) = state.cancel_errors.get(&name) {
+            return Err(Status::new(status.code(), status.message()));
+        }
+        match state.operations.get_mut(&name) {
+            Some(operation) => {
+                operation.done = true;
+                operation.result = Some(OperationResult::Error(RpcStatus {
+                    code: tonic::Code::Cancelled as i32,
+                    message: "operation cancelled".to_string(),
+                    details: vec![],
+                }));
+            }
+            None => return Err(Status::not_found(name)),
+        }
+        state.cancelled_operations.push(name);
+        Ok(Response::new(()))
+    }
+
+    async fn wait_operation(
+        &self,
+        _request: Request<WaitOperationRequest>,
+    ) -> std::result::Result<Response<Operation>, Status> {
+        Err(Status::unimplemented("wait_operation"))
+    }
+}
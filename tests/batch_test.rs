@@ -0,0 +1,130 @@
+#![cfg(feature = "testing")]
+//! Tests of v2 batch recognition and long running operations against mock speech server.
+//! cargo test --features testing --test batch_test -- --show-output
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::BatchRecognizeRequest;
+use google_cognitive_apis::api::grpc::google::longrunning::Operation;
+use google_cognitive_apis::speechtotext::recognizer_v2::Recognizer;
+use google_cognitive_apis::testing::MockSpeech;
+use std::time::Duration;
+use tonic::Status;
+
+#[tokio::test]
+async fn test_cancel_operation() {
+    let mock = MockSpeech::new();
+    mock.push_batch_operation(Operation {
+        name: "operations/batch-1".to_string(),
+        ..Default::default()
+    });
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer =
+        Recognizer::create_asynchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+
+    let operation = recognizer
+        .batch_recognize(BatchRecognizeRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+    recognizer.cancel_operation(&operation.name).await.unwrap();
+    assert_eq!(mock.cancelled_operations(), vec!["operations/batch-1"]);
+
+    let error = recognizer
+        .batch_wait(operation, Some(10))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("1"));
+}
+
+#[tokio::test]
+async fn test_get_operation_and_poll_until_done() {
+    let mock = MockSpeech::new();
+    mock.push_batch_operation(Operation {
+        name: "operations/batch-2".to_string(),
+        ..Default::default()
+    });
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer =
+        Recognizer::create_asynchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+
+    let name = recognizer
+        .batch_recognize(BatchRecognizeRequest::default())
+        .await
+        .unwrap()
+        .into_inner()
+        .name;
+    let operation = recognizer.get_operation(&name).await.unwrap();
+    assert!(!operation.done);
+
+    let poll_mock = mock.clone();
+    let done_name = name.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        poll_mock.set_operation(Operation {
+            name: done_name,
+            done: true,
+            ..Default::default()
+        });
+    });
+    let operation = recognizer
+        .poll_until_done(&name, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert!(operation.done);
+
+    let error = recognizer
+        .get_operation("operations/unknown")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.as_tonic_status().map(|status| status.code()),
+        Some(tonic::Code::NotFound)
+    );
+}
+
+#[tokio::test]
+async fn test_list_and_cancel_all_operations() {
+    let mock = MockSpeech::new();
+    for (name, done) in [
+        ("operations/a", false),
+        ("operations/b", true),
+        ("operations/c", false),
+        ("operations/d", false),
+        ("operations/e", false),
+    ] {
+        mock.set_operation(Operation {
+            name: name.to_string(),
+            done,
+            ..Default::default()
+        });
+    }
+    mock.set_cancel_error("operations/d", Status::permission_denied("denied"));
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer =
+        Recognizer::create_asynchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+
+    // mock returns pages of two operations
+    let operations = recognizer
+        .list_operations("projects/p/locations/global", None)
+        .await
+        .unwrap();
+    assert_eq!(operations.len(), 5);
+
+    let error = recognizer
+        .cancel_all_operations(
+            "projects/p/locations/global",
+            Some("done=false".to_string()),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("CANCEL_PARTIALLY_FAILED"));
+    assert!(error.message.contains("operations/d: "));
+    assert_eq!(
+        mock.cancelled_operations(),
+        vec!["operations/a", "operations/c", "operations/e"]
+    );
+}
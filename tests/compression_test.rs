@@ -0,0 +1,53 @@
+#![cfg(feature = "testing")]
+//! Verifies gzip compressed GRPC calls against mock v2 speech server.
+//! cargo test --features testing --test compression_test -- --show-output
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::{
+    recognize_request::AudioSource, RecognizeRequest, RecognizeResponse,
+    SpeechRecognitionAlternative, SpeechRecognitionResult,
+};
+use google_cognitive_apis::speechtotext::recognizer_v2::Recognizer;
+use google_cognitive_apis::testing::MockSpeech;
+
+#[tokio::test]
+async fn test_gzip_compression() {
+    let mock = MockSpeech::new();
+    mock.push_recognize_response(RecognizeResponse {
+        results: vec![SpeechRecognitionResult {
+            alternatives: vec![SpeechRecognitionAlternative {
+                transcript: "hello".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        metadata: None,
+    });
+    let channel = mock.serve().await.unwrap();
+
+    let mut recognizer =
+        Recognizer::create_synchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+    recognizer.enable_gzip_compression();
+
+    let response = recognizer
+        .recognize(RecognizeRequest {
+            recognizer: "projects/p/locations/global/recognizers/_".to_string(),
+            audio_source: Some(AudioSource::Content(vec![0u8; 4096])),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.results[0].alternatives[0].transcript, "hello");
+
+    let requests = mock.recognize_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].metadata.get("grpc-encoding").unwrap(), "gzip");
+    assert_eq!(
+        requests[0].metadata.get("authorization").unwrap(),
+        "Bearer test"
+    );
+    match &requests[0].message.audio_source {
+        Some(AudioSource::Content(content)) => assert_eq!(content.len(), 4096),
+        _ => panic!("audio content expected"),
+    }
+}
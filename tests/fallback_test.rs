@@ -0,0 +1,95 @@
+#![cfg(feature = "testing")]
+//! Tests of v2 to v1 fallback against mock v2 speech server.
+//! cargo test --features testing --test fallback_test -- --show-output
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::{
+    recognize_request::AudioSource, RecognizeRequest, RecognizeResponse,
+    SpeechRecognitionAlternative, SpeechRecognitionResult,
+};
+use google_cognitive_apis::errors::ErrorKind;
+use google_cognitive_apis::speechtotext::fallback::{
+    recognize_with_fallback_using, recognize_with_model_fallbacks, SpeechApi,
+};
+use google_cognitive_apis::speechtotext::recognizer_v2::Recognizer;
+use google_cognitive_apis::testing::MockSpeech;
+use tonic::Status;
+
+fn request() -> RecognizeRequest {
+    RecognizeRequest {
+        recognizer: "projects/p/locations/global/recognizers/_".to_string(),
+        audio_source: Some(AudioSource::Content(vec![0u8; 1024])),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_fallback_not_needed() {
+    let mock = MockSpeech::new();
+    mock.push_recognize_response(RecognizeResponse {
+        results: vec![SpeechRecognitionResult {
+            alternatives: vec![SpeechRecognitionAlternative {
+                transcript: "hello".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        metadata: None,
+    });
+    mock.push_recognize_error(Status::invalid_argument("Invalid sample rate"));
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer =
+        Recognizer::create_synchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+
+    let transcript = recognize_with_fallback_using(&mut recognizer, "{}", request())
+        .await
+        .unwrap();
+    assert_eq!(transcript.api, SpeechApi::V2);
+    assert_eq!(transcript.segments[0].text, "hello");
+
+    // errors not related to v2-only features are returned as is
+    let error = recognize_with_fallback_using(&mut recognizer, "{}", request())
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+    assert!(error.message.contains("Invalid sample rate"));
+    assert_eq!(mock.recognize_requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_model_fallbacks() {
+    let mock = MockSpeech::new();
+    mock.push_recognize_error(Status::invalid_argument(
+        "The model \"chirp_2\" is not supported in location \"europe-west4\"",
+    ));
+    mock.push_recognize_error(Status::not_found("Model \"chirp\" does not exist"));
+    mock.push_recognize_response(RecognizeResponse::default());
+    mock.push_recognize_error(Status::invalid_argument("Invalid sample rate"));
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer =
+        Recognizer::create_synchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+            .await
+            .unwrap();
+
+    let mut primary = request();
+    primary.config = Some(Default::default());
+    primary.config.as_mut().unwrap().model = "chirp_2".to_string();
+    let fallbacks = vec!["chirp".to_string(), "long".to_string()];
+    let response = recognize_with_model_fallbacks(&mut recognizer, primary.clone(), fallbacks)
+        .await
+        .unwrap();
+    assert_eq!(response.model, "long");
+    let models: Vec<String> = mock
+        .recognize_requests()
+        .iter()
+        .map(|request| request.message.config.clone().unwrap().model)
+        .collect();
+    assert_eq!(models, vec!["chirp_2", "chirp", "long"]);
+
+    // other errors are returned without trying fallbacks
+    let error = recognize_with_model_fallbacks(&mut recognizer, primary, vec!["long".to_string()])
+        .await
+        .unwrap_err();
+    assert!(error.message.contains("Invalid sample rate"));
+    assert_eq!(mock.recognize_requests().len(), 4);
+}
@@ -0,0 +1,1347 @@
+#![cfg(feature = "testing")]
+//! Tests of v2 recognizer streaming against scripted mock speech server.
+//! cargo test --features testing --test mock_server_test -- --show-output
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::{
+    recognition_config::DecodingConfig, streaming_recognize_request::StreamingRequest,
+    RecognitionConfig, RecognitionResponseMetadata, SpeechRecognitionAlternative,
+    StreamingRecognitionConfig, StreamingRecognitionResult, StreamingRecognizeRequest,
+    StreamingRecognizeResponse,
+};
+use google_cognitive_apis::common::{new_channel_with_connector, OverflowPolicy, StreamLimiter};
+use google_cognitive_apis::credentials::CredentialProvider;
+use google_cognitive_apis::errors::{Error, Result};
+use google_cognitive_apis::speechtotext::audio::PumpOptions;
+use google_cognitive_apis::speechtotext::compare::DualRecognizer;
+use google_cognitive_apis::speechtotext::config::{
+    telephony_linear16, RecognitionConfigBuilder, StreamingConfigBuilder,
+};
+use google_cognitive_apis::speechtotext::recognizer_v2::{
+    FrameValidation, PushOverflowPolicy, Recognizer,
+};
+use google_cognitive_apis::speechtotext::recording::{read_responses, replay_requests};
+use google_cognitive_apis::testing::{MockSpeech, MockStreamEvent};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tonic::Status;
+
+fn final_response(transcript: &str) -> StreamingRecognizeResponse {
+    StreamingRecognizeResponse {
+        results: vec![StreamingRecognitionResult {
+            alternatives: vec![SpeechRecognitionAlternative {
+                transcript: transcript.to_string(),
+                ..Default::default()
+            }],
+            is_final: true,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Sends single audio chunk and closes audio sink (streams without audio are rejected).
+async fn send_chunk_and_close(audio_sink: mpsc::Sender<StreamingRecognizeRequest>) {
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![0u8; 320],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+}
+
+async fn streaming_recognizer(mock: &MockSpeech) -> Recognizer {
+    let channel = mock.serve().await.unwrap();
+    Recognizer::create_streaming_recognizer_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        StreamingRecognitionConfig::default(),
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_close_audio_and_flush() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+
+    let audio_sink = recognizer.get_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2, 3],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    drop(audio_sink);
+
+    // stream was not started yet, nothing is sent
+    recognizer.close_audio_and_flush().await.unwrap();
+    assert!(mock.streaming_requests().is_empty());
+
+    // stream started later sends audio queued before close
+    recognizer.streaming_recognize().await.unwrap();
+    let response = result_receiver.recv().await.unwrap();
+    assert_eq!(response.results[0].alternatives[0].transcript, "hello");
+    // streaming config + one audio chunk
+    assert_eq!(mock.streaming_requests()[0].len(), 2);
+
+    // calls after stream finished are no-op
+    recognizer.close_audio_and_flush().await.unwrap();
+    recognizer.close_audio_and_flush().await.unwrap();
+    assert_eq!(mock.streaming_requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_close_audio_and_flush_active_stream() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Response(final_response("world")),
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+    let closer = recognizer.stream_closer();
+
+    // sink is kept open, closing does not depend on senders being dropped
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2, 3],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    let streaming = tokio::spawn(async move {
+        let result = recognizer.streaming_recognize().await;
+        (recognizer, result)
+    });
+
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+    assert!(closer.is_streaming());
+
+    // both calls wait for the same completion
+    let (first, second) = tokio::join!(
+        closer.close_audio_and_flush(),
+        closer.close_audio_and_flush()
+    );
+    first.unwrap();
+    second.unwrap();
+    assert!(!closer.is_streaming());
+
+    // remaining responses were delivered before close returned
+    assert_eq!(
+        result_receiver.try_recv().unwrap().results[0].alternatives[0].transcript,
+        "world"
+    );
+    let (mut recognizer, result) = tokio::time::timeout(Duration::from_secs(1), streaming)
+        .await
+        .unwrap()
+        .unwrap();
+    result.unwrap();
+    recognizer.close_audio_and_flush().await.unwrap();
+
+    // streaming config + one audio chunk, audio sent after close is rejected
+    assert_eq!(mock.streaming_requests()[0].len(), 2);
+    assert!(audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![4, 5, 6],
+            String::new(),
+        ))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_streaming_error() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Error(Status::resource_exhausted("quota exceeded")),
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+
+    let error = recognizer.streaming_recognize().await.unwrap_err();
+    assert!(error.message.contains("quota exceeded"));
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+}
+
+/// Provider failing every token request, e.g. unreachable metadata server.
+struct FailingProvider(Arc<AtomicUsize>);
+
+impl CredentialProvider for FailingProvider {
+    fn token(&self) -> Result<String> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Err(Error::new("metadata server unavailable".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_streaming_auth_refresh_failure() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut recognizer = Recognizer::create_streaming_recognizer_with_provider_and_channel(
+        mock.serve().await.unwrap(),
+        FailingProvider(calls.clone()),
+        StreamingRecognitionConfig::default(),
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+    send_chunk_and_close(recognizer.take_audio_sink().unwrap()).await;
+
+    // AUTH_REFRESH is returned by streaming_recognize, not sent through result channel
+    let error = recognizer.streaming_recognize().await.unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("AUTH_REFRESH"));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert!(mock.streaming_requests().is_empty());
+    drop(recognizer);
+    assert!(result_receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn test_token_clock_validation() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let expired = jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &serde_json::json!({"iat": now - 7200, "exp": now - 3600}),
+        &jsonwebtoken::EncodingKey::from_secret(b"secret"),
+    )
+    .unwrap();
+
+    // rejected before connecting to Google
+    let error = Recognizer::create_streaming_recognizer_from_token_with_validation(
+        format!("Bearer {}", expired),
+        StreamingRecognitionConfig::default(),
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+        true,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("CLOCK_SKEW"));
+}
+
+#[tokio::test]
+async fn test_streaming_close_early() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Close]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+
+    // audio sink is still held by recognizer, stream ends because server closed it
+    let summary = recognizer.streaming_recognize_with_summary().await.unwrap();
+    assert_eq!(summary.final_results, 0);
+}
+
+#[tokio::test]
+async fn test_streaming_config_sent_once() {
+    let mock = MockSpeech::new();
+    let mut recognizer = streaming_recognizer(&mock).await;
+
+    let error = recognizer
+        .send_streaming_config(StreamingRecognitionConfig::default())
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, Some("STREAMING_CONFIG_SENT".to_string()));
+    assert!(recognizer.get_audio_sink().is_some());
+}
+
+#[tokio::test]
+async fn test_deferred_streaming_config_guard() {
+    let mock = MockSpeech::new();
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer = Recognizer::create_streaming_recognizer_deferred_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+
+    // stream API refuses to send audio without config
+    let responses: Vec<_> = recognizer
+        .streaming_recognize_async_stream()
+        .await
+        .collect()
+        .await;
+    assert_eq!(responses.len(), 1);
+    let error = responses[0].as_ref().unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("STREAMING_CONFIG_MISSING"));
+
+    // config cannot be sent once audio sink is dropped, recognizer stays without config
+    recognizer.drop_audio_sink();
+    let error = recognizer
+        .send_streaming_config(StreamingRecognitionConfig::default())
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("AUDIO_SINK_CLOSED"));
+    let error = recognizer.streaming_recognize().await.unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("STREAMING_CONFIG_MISSING"));
+    assert!(mock.streaming_requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_audio_callback_sink() {
+    let mock = MockSpeech::new();
+    let mut recognizer = streaming_recognizer(&mock).await;
+
+    let sink = recognizer.audio_callback_sink().unwrap();
+    let dropped = sink.dropped_chunks_counter();
+    let mut callback = sink.into_callback();
+    callback(&[1, 2, 3]);
+    callback(&[4, 5]);
+    drop(callback);
+
+    recognizer.drop_audio_sink();
+    recognizer.streaming_recognize().await.unwrap();
+    assert_eq!(dropped.load(std::sync::atomic::Ordering::Relaxed), 0);
+    // streaming config + two audio chunks
+    assert_eq!(mock.streaming_requests()[0].len(), 3);
+}
+
+#[tokio::test]
+async fn test_streaming_request_id() {
+    let mock = MockSpeech::new();
+    let mut response = final_response("hello");
+    response.metadata = Some(RecognitionResponseMetadata {
+        request_id: "req-123".to_string(),
+        ..Default::default()
+    });
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("no metadata")),
+        MockStreamEvent::Response(response),
+        MockStreamEvent::Close,
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+
+    let summary = recognizer.streaming_recognize_with_summary().await.unwrap();
+    assert_eq!(summary.request_id.as_deref(), Some("req-123"));
+    assert_eq!(recognizer.request_id().as_deref(), Some("req-123"));
+}
+
+#[tokio::test]
+async fn test_streaming_with_context() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Close,
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let (sender, mut receiver) = mpsc::channel(10);
+
+    recognizer
+        .streaming_recognize_with_context("session-1".to_string(), sender)
+        .await
+        .unwrap();
+
+    let contextual = receiver.recv().await.unwrap();
+    assert_eq!(contextual.context, "session-1");
+    assert_eq!(
+        contextual.response.results[0].alternatives[0].transcript,
+        "hello"
+    );
+    assert!(receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn test_debounced_transcripts() {
+    let interim = |transcript: &str| {
+        let mut response = final_response(transcript);
+        response.results[0].is_final = false;
+        MockStreamEvent::Response(response)
+    };
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![
+        interim("hel"),
+        interim("hell"),
+        interim("hello"),
+        MockStreamEvent::Response(final_response("hello world")),
+        MockStreamEvent::Close,
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut receiver =
+        recognizer.get_streaming_transcript_receiver_debounced(Duration::from_secs(60));
+
+    recognizer.streaming_recognize().await.unwrap();
+    drop(recognizer);
+
+    // first interim is forwarded immediately, others are superseded by final
+    let first = receiver.recv().await.unwrap();
+    assert_eq!((first.transcript.as_str(), first.is_final), ("hel", false));
+    let last = receiver.recv().await.unwrap();
+    assert_eq!(
+        (last.transcript.as_str(), last.is_final),
+        ("hello world", true)
+    );
+    assert!(receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn test_send_end_of_audio() {
+    let mock = MockSpeech::new();
+    // response stream is closed only once client half-closes audio stream
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    send_chunk_and_close(recognizer.get_audio_sink().unwrap()).await;
+
+    recognizer.send_end_of_audio().unwrap();
+    let summary = recognizer.streaming_recognize_with_summary().await.unwrap();
+    assert_eq!(summary.final_results, 1);
+    // streaming config and audio chunk
+    assert_eq!(mock.streaming_requests()[0].len(), 2);
+}
+
+#[tokio::test]
+async fn test_no_audio_sent() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+
+    recognizer.send_end_of_audio().unwrap();
+    let error = recognizer.streaming_recognize().await.unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("NO_AUDIO_SENT"));
+    // GRPC call was not opened
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(mock.streaming_requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_raw_stream_replay() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer = Recognizer::create_streaming_recognizer_raw_with_channel(
+        channel,
+        "Bearer test".into(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // captured stream already contains config message
+    let captured = vec![
+        StreamingRecognizeRequest {
+            recognizer: "projects/p/locations/global/recognizers/_".to_string(),
+            streaming_request: Some(StreamingRequest::StreamingConfig(
+                StreamingRecognitionConfig::default(),
+            )),
+        },
+        Recognizer::streaming_request_from_bytes(vec![1, 2, 3], String::new()),
+    ];
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    for request in captured.clone() {
+        audio_sink.send(request).await.unwrap();
+    }
+    drop(audio_sink);
+
+    let summary = recognizer.streaming_recognize_with_summary().await.unwrap();
+    assert_eq!(summary.final_results, 1);
+    assert_eq!(mock.streaming_requests()[0], captured);
+}
+
+#[tokio::test]
+async fn test_strict_frame_validation() {
+    let mock = MockSpeech::new();
+    let channel = mock.serve().await.unwrap();
+    let config = StreamingRecognitionConfig {
+        config: Some(RecognitionConfig {
+            decoding_config: Some(DecodingConfig::ExplicitDecodingConfig(telephony_linear16())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut recognizer = Recognizer::create_streaming_recognizer_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        config,
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    recognizer.set_frame_validation(FrameValidation::Strict);
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    for chunk in [vec![0u8; 320], vec![0u8; 321], vec![0u8; 320]] {
+        audio_sink
+            .send(Recognizer::streaming_request_from_bytes(
+                chunk,
+                String::new(),
+            ))
+            .await
+            .unwrap();
+    }
+    drop(audio_sink);
+
+    let error = recognizer.streaming_recognize().await.unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("FRAME_MISALIGNED"));
+    // config and first (aligned) chunk only
+    assert_eq!(mock.streaming_requests()[0].len(), 2);
+}
+
+#[tokio::test]
+async fn test_stream_api_request_pipeline() {
+    let mock = MockSpeech::new();
+    let mut response = final_response("hello");
+    response.metadata = Some(RecognitionResponseMetadata {
+        request_id: "req-456".to_string(),
+        ..Default::default()
+    });
+    mock.push_streaming_script(vec![MockStreamEvent::Response(response)]);
+    let channel = mock.serve().await.unwrap();
+    let config = StreamingRecognitionConfig {
+        config: Some(RecognitionConfig {
+            decoding_config: Some(DecodingConfig::ExplicitDecodingConfig(telephony_linear16())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut recognizer = Recognizer::create_streaming_recognizer_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        config,
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    recognizer.set_frame_validation(FrameValidation::Strict);
+    let mut raw_receiver = recognizer.subscribe_raw();
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    for chunk in [vec![0u8; 320], vec![0u8; 321], vec![0u8; 320]] {
+        audio_sink
+            .send(Recognizer::streaming_request_from_bytes(
+                chunk,
+                String::new(),
+            ))
+            .await
+            .unwrap();
+    }
+    drop(audio_sink);
+
+    // stream API validates frames, publishes responses and records request id
+    let responses: Vec<_> = recognizer
+        .streaming_recognize_async_stream()
+        .await
+        .collect()
+        .await;
+    assert_eq!(responses.len(), 2);
+    assert!(responses[0].is_ok());
+    let error = responses[1].as_ref().unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("FRAME_MISALIGNED"));
+    assert_eq!(mock.streaming_requests()[0].len(), 2);
+    assert_eq!(
+        raw_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+    assert_eq!(recognizer.request_id().as_deref(), Some("req-456"));
+}
+
+#[tokio::test]
+async fn test_stream_handle_abort() {
+    let mock = MockSpeech::new();
+    // mock keeps response stream open until audio stream is closed
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    // audio sink is kept open, i.e. stream would never end without abort
+    let _audio_sink = recognizer.get_audio_sink().unwrap();
+
+    let (handle, stream) = recognizer
+        .streaming_recognize_async_stream_with_handle()
+        .await;
+    let mut stream = Box::pin(stream);
+
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.results[0].alternatives[0].transcript, "hello");
+
+    handle.abort();
+    assert!(handle.is_aborted());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_stream_handle_abort_and_collect() {
+    let mock = MockSpeech::new();
+    let mut interim = final_response("wor");
+    interim.results[0].is_final = false;
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Response(interim),
+    ]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let _audio_sink = recognizer.get_audio_sink().unwrap();
+
+    let (handle, stream) = recognizer
+        .streaming_recognize_async_stream_with_handle()
+        .await;
+    let mut stream = Box::pin(stream);
+    stream.next().await.unwrap().unwrap();
+    stream.next().await.unwrap().unwrap();
+
+    // interim results are not collected
+    assert_eq!(handle.clone().abort_and_collect().unwrap(), "hello");
+    assert!(handle.is_aborted());
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_interim_results_disabled() {
+    let mock = MockSpeech::new();
+    let mut interim = final_response("hel");
+    interim.results[0].is_final = false;
+    let mut mixed = final_response("hello");
+    mixed.results.insert(0, interim.results[0].clone());
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(interim),
+        MockStreamEvent::Response(mixed),
+    ]);
+    let channel = mock.serve().await.unwrap();
+    let config = StreamingConfigBuilder::new(RecognitionConfigBuilder::new().build())
+        .interim_results(false)
+        .build();
+    let mut recognizer = Recognizer::create_streaming_recognizer_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        config,
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    send_chunk_and_close(recognizer.take_audio_sink().unwrap()).await;
+
+    let stream = recognizer.streaming_recognize_async_stream().await;
+    let responses: Vec<_> = Box::pin(stream).collect().await;
+    // response with interim result only is dropped, interim result of mixed response stripped
+    assert_eq!(responses.len(), 1);
+    let response = responses[0].as_ref().unwrap();
+    assert_eq!(response.results.len(), 1);
+    assert!(response.results[0].is_final);
+}
+
+#[tokio::test]
+async fn test_dual_recognizer() {
+    let mock_a = MockSpeech::new();
+    mock_a.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Response(final_response("world")),
+    ]);
+    let mock_b = MockSpeech::new();
+    mock_b.push_streaming_script(vec![MockStreamEvent::Response(final_response("yellow"))]);
+
+    let mut dual = DualRecognizer::new(
+        streaming_recognizer(&mock_a).await,
+        streaming_recognizer(&mock_b).await,
+    )
+    .unwrap();
+    let audio_sink = dual.take_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2, 3],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    drop(audio_sink);
+
+    let (result_sender, mut result_receiver) = mpsc::channel(10);
+    dual.streaming_recognize(result_sender).await.unwrap();
+
+    let transcript = |response: &Option<StreamingRecognizeResponse>| {
+        response
+            .as_ref()
+            .map(|response| response.results[0].alternatives[0].transcript.clone())
+    };
+    let first = result_receiver.recv().await.unwrap();
+    assert_eq!(first.index, 0);
+    assert_eq!(transcript(&first.a).as_deref(), Some("hello"));
+    assert_eq!(transcript(&first.b).as_deref(), Some("yellow"));
+    let second = result_receiver.recv().await.unwrap();
+    assert_eq!(transcript(&second.a).as_deref(), Some("world"));
+    assert_eq!(second.b, None);
+    assert!(result_receiver.recv().await.is_none());
+
+    // both recognizers received the same audio
+    assert_eq!(
+        mock_a.streaming_requests()[0],
+        mock_b.streaming_requests()[0]
+    );
+}
+
+#[tokio::test]
+async fn test_low_latency_streaming() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer = Recognizer::create_streaming_recognizer_low_latency_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        StreamingRecognitionConfig::default(),
+        true,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    // streaming config occupies the only slot until streaming starts
+    assert_eq!(audio_sink.capacity(), 0);
+    let sender = tokio::spawn(async move {
+        for chunk in 0..10u8 {
+            audio_sink
+                .send(Recognizer::streaming_request_from_bytes(
+                    vec![chunk; 160],
+                    String::new(),
+                ))
+                .await
+                .unwrap();
+        }
+    });
+
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+    recognizer.streaming_recognize().await.unwrap();
+    sender.await.unwrap();
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+    // every chunk sent as separate message
+    assert_eq!(mock.streaming_requests()[0].len(), 11);
+}
+
+#[tokio::test]
+async fn test_record_and_replay_requests() {
+    let path = std::env::temp_dir().join("test_record_and_replay_requests.pb");
+
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    recognizer.record_requests(&path).unwrap();
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    for chunk in [vec![1u8; 10], vec![2u8; 10]] {
+        audio_sink
+            .send(Recognizer::streaming_request_from_bytes(
+                chunk,
+                String::new(),
+            ))
+            .await
+            .unwrap();
+    }
+    drop(audio_sink);
+    recognizer.streaming_recognize().await.unwrap();
+    let recorded = mock.streaming_requests()[0].clone();
+
+    // replay recorded session into raw recognizer (config is part of recording)
+    let channel = mock.serve().await.unwrap();
+    let mut replaying = Recognizer::create_streaming_recognizer_raw_with_channel(
+        channel,
+        "Bearer test".into(),
+        None,
+    )
+    .await
+    .unwrap();
+    let audio_sink = replaying.take_audio_sink().unwrap();
+    let mut requests = Box::pin(replay_requests(&path).unwrap());
+    while let Some(request) = requests.next().await {
+        audio_sink.send(request).await.unwrap();
+    }
+    drop(audio_sink);
+    replaying.streaming_recognize().await.unwrap();
+
+    assert_eq!(recorded.len(), 3);
+    assert_eq!(mock.streaming_requests()[1], recorded);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_record_responses() {
+    let path = std::env::temp_dir().join("test_record_responses.pb");
+    let mock = MockSpeech::new();
+    let responses = vec![final_response("hello"), final_response("world")];
+    mock.push_streaming_script(
+        responses
+            .iter()
+            .cloned()
+            .map(MockStreamEvent::Response)
+            .collect(),
+    );
+    let mut recognizer = streaming_recognizer(&mock).await;
+    recognizer.record_responses(&path).unwrap();
+    send_chunk_and_close(recognizer.take_audio_sink().unwrap()).await;
+    recognizer.streaming_recognize().await.unwrap();
+
+    assert_eq!(read_responses(&path).unwrap(), responses);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_custom_connector() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let addr = mock.serve_addr().await.unwrap();
+    // connector ignores uri, e.g. as unix socket connector would
+    let connector =
+        tower::service_fn(move |_: tonic::transport::Uri| tokio::net::TcpStream::connect(addr));
+    let channel = new_channel_with_connector("http://localhost", connector)
+        .await
+        .unwrap();
+
+    let mut recognizer = Recognizer::create_streaming_recognizer_deferred_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        None,
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    recognizer
+        .send_streaming_config(StreamingRecognitionConfig::default())
+        .await
+        .unwrap();
+    send_chunk_and_close(recognizer.take_audio_sink().unwrap()).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+    recognizer.streaming_recognize().await.unwrap();
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+}
+
+#[tokio::test]
+async fn test_pump_audio_realtime() {
+    let (audio_sender, mut audio_receiver) = mpsc::channel(100);
+    // 400ms of 8kHz mono LINEAR16 audio sent at double speed
+    let audio = vec![0u8; 6400];
+    let started = std::time::Instant::now();
+    let sent =
+        Recognizer::pump_audio_realtime_with_pace(audio_sender, audio.as_slice(), 8000, 1, 2.0)
+            .await
+            .unwrap();
+    assert_eq!(sent, 6400);
+    assert!(started.elapsed() >= Duration::from_millis(190));
+
+    let mut chunks = 0;
+    while let Some(request) = audio_receiver.recv().await {
+        match request.streaming_request {
+            Some(StreamingRequest::Audio(audio)) => assert_eq!(audio.len(), 1600),
+            _ => panic!("audio request expected"),
+        }
+        chunks += 1;
+    }
+    assert_eq!(chunks, 4);
+
+    let (audio_sender, _audio_receiver) = mpsc::channel(1);
+    assert!(Recognizer::pump_audio_realtime_with_pace(
+        audio_sender,
+        audio.as_slice(),
+        8000,
+        1,
+        0.0
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_audio_producers() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let producer = recognizer.audio_producer_handle().unwrap();
+    assert!(recognizer.audio_producer_handle().is_none());
+
+    let mut producers = vec![];
+    for source in 0..3u8 {
+        let producer = producer.clone();
+        producers.push(tokio::spawn(async move {
+            producer.send_bytes(vec![source; 10]).await.unwrap();
+            producer.send_bytes(vec![source; 10]).await.unwrap();
+            producer.close();
+        }));
+    }
+    assert!(producer.producer_count() >= 1);
+    producer.close();
+
+    // stream is finalized only after all producers are closed
+    recognizer.streaming_recognize().await.unwrap();
+    for producer in producers {
+        producer.await.unwrap();
+    }
+    // streaming config + 2 chunks from each producer
+    assert_eq!(mock.streaming_requests()[0].len(), 7);
+}
+
+#[tokio::test]
+async fn test_stream_limiter() {
+    let mock = MockSpeech::new();
+    let limiter = StreamLimiter::new(1, OverflowPolicy::Reject);
+
+    let mut first = streaming_recognizer(&mock).await;
+    first.set_stream_limiter(limiter.clone());
+    // audio sink is kept open, i.e. first stream stays in flight
+    let first_sink = first.take_audio_sink().unwrap();
+    let first_stream = tokio::spawn(async move { first.streaming_recognize().await });
+    while limiter.in_flight() == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let mut second = streaming_recognizer(&mock).await;
+    second.set_stream_limiter(limiter.clone());
+    let error = second.streaming_recognize().await.unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("STREAM_LIMIT_EXCEEDED"));
+
+    drop(first_sink);
+    first_stream.await.unwrap().unwrap();
+    assert_eq!(limiter.in_flight(), 0);
+
+    // recognizer rejected before streaming started can be retried
+    send_chunk_and_close(second.take_audio_sink().unwrap()).await;
+    second.streaming_recognize().await.unwrap();
+
+    // streams driven via stream API are limited as well
+    let mut streamed = streaming_recognizer(&mock).await;
+    streamed.set_stream_limiter(limiter.clone());
+    let streamed_sink = streamed.take_audio_sink().unwrap();
+    let streamed_task = tokio::spawn(async move {
+        let stream = streamed.streaming_recognize_async_stream().await;
+        tokio::pin!(stream);
+        while let Some(response) = stream.next().await {
+            response?;
+        }
+        Ok::<_, Error>(())
+    });
+    while limiter.in_flight() == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let mut rejected = streaming_recognizer(&mock).await;
+    rejected.set_stream_limiter(limiter.clone());
+    let (_sink, stream) = rejected.into_sink_and_stream();
+    tokio::pin!(stream);
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("STREAM_LIMIT_EXCEEDED"));
+
+    send_chunk_and_close(streamed_sink).await;
+    streamed_task.await.unwrap().unwrap();
+    assert_eq!(limiter.in_flight(), 0);
+}
+
+#[tokio::test]
+async fn test_pump_big_endian_audio() {
+    let (audio_sender, mut audio_receiver) = mpsc::channel(100);
+    let audio: Vec<u8> = vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+    let options = PumpOptions {
+        big_endian: true,
+        ..Default::default()
+    };
+    // odd chunk size is rounded up to whole samples
+    let sent =
+        Recognizer::pump_audio_from_reader_with_options(audio_sender, audio.as_slice(), 3, options)
+            .await
+            .unwrap();
+    assert_eq!(sent, 6);
+
+    let mut converted = vec![];
+    while let Some(request) = audio_receiver.recv().await {
+        if let Some(StreamingRequest::Audio(audio)) = request.streaming_request {
+            converted.extend(audio);
+        }
+    }
+    assert_eq!(converted, vec![0x02, 0x01, 0x04, 0x03, 0x06, 0x05]);
+}
+
+#[tokio::test]
+async fn test_reconfigure() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hallo"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    drop(audio_sink);
+
+    let german = StreamingRecognitionConfig {
+        config: Some(RecognitionConfig {
+            language_codes: vec!["de-DE".to_string()],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    // closes first stream, results are still delivered
+    recognizer.reconfigure(german).await.unwrap();
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hello"
+    );
+
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![3, 4],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    drop(audio_sink);
+    recognizer.streaming_recognize().await.unwrap();
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hallo"
+    );
+
+    let requests = mock.streaming_requests();
+    assert_eq!(requests.len(), 2);
+    match &requests[1][0].streaming_request {
+        Some(StreamingRequest::StreamingConfig(config)) => {
+            assert_eq!(
+                config.config.as_ref().unwrap().language_codes,
+                vec!["de-DE".to_string()]
+            );
+        }
+        _ => panic!("streaming config expected"),
+    }
+    assert_eq!(
+        requests[1][0].recognizer,
+        "projects/p/locations/global/recognizers/_"
+    );
+}
+
+#[tokio::test]
+async fn test_reconfigure_without_audio() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hallo"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+    // clone of audio sink held by caller does not keep old stream open
+    let stale_sink = recognizer.get_audio_sink().unwrap();
+
+    for language_code in ["de-DE", "fr-FR"] {
+        let config = StreamingRecognitionConfig {
+            config: Some(RecognitionConfig {
+                language_codes: vec![language_code.to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        tokio::time::timeout(Duration::from_secs(5), recognizer.reconfigure(config))
+            .await
+            .expect("reconfigure blocked")
+            .unwrap();
+    }
+    // no stream was opened for empty sessions
+    assert!(mock.streaming_requests().is_empty());
+    assert!(stale_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2],
+            String::new(),
+        ))
+        .await
+        .is_err());
+
+    send_chunk_and_close(recognizer.take_audio_sink().unwrap()).await;
+    recognizer.streaming_recognize().await.unwrap();
+    assert_eq!(
+        result_receiver.recv().await.unwrap().results[0].alternatives[0].transcript,
+        "hallo"
+    );
+    let requests = mock.streaming_requests();
+    assert_eq!(requests.len(), 1);
+    match &requests[0][0].streaming_request {
+        Some(StreamingRequest::StreamingConfig(config)) => {
+            assert_eq!(
+                config.config.as_ref().unwrap().language_codes,
+                vec!["fr-FR".to_string()]
+            );
+        }
+        _ => panic!("streaming config expected"),
+    }
+}
+
+#[tokio::test]
+async fn test_request_finalize() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("hello"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let mut result_receiver = recognizer.get_streaming_result_receiver(None);
+
+    let audio_sink = recognizer.get_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![1, 2],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+    drop(audio_sink);
+
+    // stream ends once pending results are finalized
+    recognizer.request_finalize().unwrap();
+    recognizer.streaming_recognize().await.unwrap();
+    assert!(result_receiver.recv().await.unwrap().results[0].is_final);
+    assert!(recognizer.get_audio_sink().is_none());
+}
+
+#[tokio::test]
+async fn test_sink_pressure() {
+    let mock = MockSpeech::new();
+    let channel = mock.serve().await.unwrap();
+    let mut recognizer = Recognizer::create_streaming_recognizer_with_channel(
+        channel,
+        "Bearer test".to_string(),
+        StreamingRecognitionConfig::default(),
+        Some(4),
+        "projects/p/locations/global/recognizers/_".to_string(),
+    )
+    .await
+    .unwrap();
+    recognizer.set_sink_high_water_mark(Some(0.5));
+
+    // streaming config is queued
+    assert_eq!(recognizer.sink_pressure(), 0.25);
+    let audio_callback_sink = recognizer.audio_callback_sink().unwrap();
+    assert!(audio_callback_sink.push(&[1, 2]));
+    assert!(audio_callback_sink.push(&[3, 4]));
+    assert_eq!(audio_callback_sink.sink_pressure(), 0.75);
+    assert!(audio_callback_sink.push(&[5, 6]));
+    assert!(!audio_callback_sink.push(&[7, 8]));
+    assert_eq!(recognizer.sink_pressure(), 1.0);
+}
+
+#[tokio::test]
+async fn test_pump_ogg_opus() {
+    let mut ogg_opus = b"OggS".to_vec();
+    ogg_opus.extend([0u8; 22]);
+    ogg_opus.extend([1, 19]);
+    ogg_opus.extend(b"OpusHead");
+    ogg_opus.extend([0u8; 111]);
+
+    let (audio_sender, mut audio_receiver) = mpsc::channel(100);
+    let sent = Recognizer::pump_ogg_opus_from_reader(audio_sender, ogg_opus.as_slice(), 64)
+        .await
+        .unwrap();
+    assert_eq!(sent, ogg_opus.len() as u64);
+    let mut received = vec![];
+    while let Some(request) = audio_receiver.recv().await {
+        if let Some(StreamingRequest::Audio(audio)) = request.streaming_request {
+            received.extend(audio);
+        }
+    }
+    assert_eq!(received, ogg_opus);
+
+    let (audio_sender, mut audio_receiver) = mpsc::channel(100);
+    let error = Recognizer::pump_ogg_opus_from_reader(audio_sender, &b"RIFF"[..], 64)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("INVALID_OGG_OPUS"));
+    assert!(audio_receiver.recv().await.is_none());
+
+    let config = RecognitionConfigBuilder::new().ogg_opus().build();
+    assert!(matches!(
+        config.decoding_config,
+        Some(DecodingConfig::AutoDecodingConfig(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_session_deadline() {
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("turn on"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    recognizer.set_session_deadline(Some(Duration::from_millis(200)));
+    // audio sink is held, i.e. stream would never be closed by the client
+    let _audio_sink = recognizer.take_audio_sink().unwrap();
+
+    let summary = tokio::time::timeout(
+        Duration::from_secs(5),
+        recognizer.streaming_recognize_with_summary(),
+    )
+    .await
+    .expect("session deadline did not fire")
+    .unwrap();
+    assert!(summary.deadline_reached);
+    assert!(recognizer.deadline_reached());
+    assert_eq!(summary.final_results, 1);
+    assert!(summary.elapsed >= Duration::from_millis(200));
+
+    // stream API is closed by deadline as well
+    mock.push_streaming_script(vec![MockStreamEvent::Response(final_response("turn off"))]);
+    let mut recognizer = streaming_recognizer(&mock).await;
+    recognizer.set_session_deadline(Some(Duration::from_millis(200)));
+    let _audio_sink = recognizer.take_audio_sink().unwrap();
+    let responses: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(5),
+        recognizer
+            .streaming_recognize_async_stream()
+            .await
+            .collect(),
+    )
+    .await
+    .expect("session deadline of stream API did not fire");
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].is_ok());
+    assert!(recognizer.deadline_reached());
+}
+
+#[tokio::test]
+async fn test_first_audio_sent() {
+    let mock = MockSpeech::new();
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let first_audio_sent = recognizer.first_audio_sent();
+    let audio_sink = recognizer.take_audio_sink().unwrap();
+    audio_sink
+        .send(Recognizer::streaming_request_from_bytes(
+            vec![0u8; 320],
+            String::new(),
+        ))
+        .await
+        .unwrap();
+
+    // audio is only enqueued, stream was not started yet
+    let mut first_audio_sent = Box::pin(first_audio_sent);
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), &mut first_audio_sent)
+            .await
+            .is_err()
+    );
+
+    drop(audio_sink);
+    recognizer.streaming_recognize().await.unwrap();
+    tokio::time::timeout(Duration::from_secs(1), first_audio_sent)
+        .await
+        .expect("first audio was not signalled");
+    // already sent audio resolves immediately
+    tokio::time::timeout(Duration::from_secs(1), recognizer.first_audio_sent())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_max_buffered_bytes() {
+    let mock = MockSpeech::new();
+    let mut recognizer = streaming_recognizer(&mock).await;
+    recognizer.set_max_buffered_bytes(Some(1000));
+    let producer = recognizer.audio_producer_handle().unwrap();
+
+    producer.send_bytes(vec![0u8; 600]).await.unwrap();
+    assert_eq!(recognizer.buffered_bytes(), 600);
+    // limit is reached although audio sink has free capacity
+    assert!(tokio::time::timeout(
+        Duration::from_millis(50),
+        producer.send_bytes(vec![0u8; 600])
+    )
+    .await
+    .is_err());
+
+    let sender = producer.clone();
+    let streaming = tokio::spawn(async move {
+        recognizer.streaming_recognize().await.unwrap();
+        recognizer
+    });
+    // first chunk was sent to Google, room for the second one
+    tokio::time::timeout(Duration::from_secs(1), sender.send_bytes(vec![0u8; 600]))
+        .await
+        .expect("limit of buffered bytes was not released")
+        .unwrap();
+    drop(sender);
+    drop(producer);
+
+    let recognizer = streaming.await.unwrap();
+    assert_eq!(recognizer.buffered_bytes(), 0);
+    // streaming config + two audio chunks
+    assert_eq!(mock.streaming_requests()[0].len(), 3);
+}
+
+#[tokio::test]
+async fn test_into_sink_and_stream() {
+    use futures_util::SinkExt;
+
+    let mock = MockSpeech::new();
+    mock.push_streaming_script(vec![
+        MockStreamEvent::Response(final_response("hello")),
+        MockStreamEvent::Response(final_response("world")),
+    ]);
+    let recognizer = streaming_recognizer(&mock).await;
+    let (mut audio_sink, results) = recognizer.into_sink_and_stream();
+
+    let mut audio = tokio_stream::iter(vec![Ok(vec![0u8; 320]), Ok(vec![0u8; 320])]);
+    audio_sink.send_all(&mut audio).await.unwrap();
+    audio_sink.close().await.unwrap();
+
+    let transcripts: Vec<String> = results
+        .map(|response| {
+            response.unwrap().results[0].alternatives[0]
+                .transcript
+                .clone()
+        })
+        .collect()
+        .await;
+    assert_eq!(transcripts, vec!["hello", "world"]);
+    // streaming config + two audio chunks
+    assert_eq!(mock.streaming_requests()[0].len(), 3);
+}
+
+#[tokio::test]
+async fn test_paced_audio_sink() {
+    let mock = MockSpeech::new();
+    let mut recognizer = streaming_recognizer(&mock).await;
+    let paced_sink = recognizer
+        .paced_audio_sink(16000, 1, 100.0, 2, PushOverflowPolicy::DropOldest)
+        .unwrap();
+    recognizer.drop_audio_sink();
+
+    // forwarding task does not run before the test yields, i.e. queue overflows
+    for chunk in 1..=4u8 {
+        paced_sink.push(&[chunk; 320]);
+    }
+    assert_eq!(paced_sink.dropped_buffers(), 2);
+    drop(paced_sink);
+
+    recognizer.streaming_recognize().await.unwrap();
+    let requests = &mock.streaming_requests()[0];
+    // streaming config + two newest chunks
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[1].streaming_request,
+        Some(StreamingRequest::Audio(vec![3; 320]))
+    );
+    assert_eq!(
+        requests[2].streaming_request,
+        Some(StreamingRequest::Audio(vec![4; 320]))
+    );
+
+    let error = recognizer
+        .paced_audio_sink(16000, 1, 0.0, 2, PushOverflowPolicy::DropNewest)
+        .unwrap_err();
+    assert!(error.message.contains("Pace"));
+}
@@ -0,0 +1,253 @@
+#![cfg(feature = "testing")]
+//! Tests of v2 synchronous recognition against mock speech server.
+//! cargo test --features testing --test recognize_test -- --show-output
+use google_cognitive_apis::api::grpc::google::cloud::speechtotext::v2::{
+    recognize_request::AudioSource, RecognitionConfig, RecognizeRequest, RecognizeResponse,
+    SpeechRecognitionAlternative, SpeechRecognitionResult,
+};
+use google_cognitive_apis::common::PooledChannel;
+use google_cognitive_apis::errors::ErrorKind;
+use google_cognitive_apis::speechtotext::cache::{CacheStats, LruRecognitionCache};
+use google_cognitive_apis::speechtotext::recognizer_v2::{
+    redact_recognize_request, redact_streaming_request, Recognizer,
+};
+use google_cognitive_apis::testing::MockSpeech;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const RECOGNIZER: &str = "projects/p/locations/global/recognizers/_";
+
+fn result(transcript: &str, language_code: &str) -> SpeechRecognitionResult {
+    SpeechRecognitionResult {
+        alternatives: vec![SpeechRecognitionAlternative {
+            transcript: transcript.to_string(),
+            ..Default::default()
+        }],
+        language_code: language_code.to_string(),
+        ..Default::default()
+    }
+}
+
+async fn synchronous_recognizer(mock: &MockSpeech) -> Recognizer {
+    let channel = mock.serve().await.unwrap();
+    Recognizer::create_synchronous_recognizer_with_channel(channel, "Bearer test".to_string())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_detect_language() {
+    let mock = MockSpeech::new();
+    mock.push_recognize_response(RecognizeResponse {
+        results: vec![
+            result("ja", "en-us"),
+            result("guten tag, wie geht's", "de-de"),
+        ],
+        metadata: None,
+    });
+    mock.push_recognize_response(RecognizeResponse {
+        results: vec![result(" ", "en-us")],
+        metadata: None,
+    });
+    let mut recognizer = synchronous_recognizer(&mock).await;
+
+    let language = recognizer
+        .detect_language(
+            &[0u8; 320],
+            RECOGNIZER.to_string(),
+            &["en-US", "de-DE"],
+            RecognitionConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(language, "de-de");
+    let config = mock.recognize_requests()[0].message.config.clone().unwrap();
+    assert_eq!(config.language_codes, vec!["en-US", "de-DE"]);
+
+    let error = recognizer
+        .detect_language(
+            &[0u8; 320],
+            RECOGNIZER.to_string(),
+            &["en-US", "de-DE"],
+            RecognitionConfig::default(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("NO_SPEECH"));
+}
+
+#[tokio::test]
+async fn test_audio_source_validation() {
+    let mock = MockSpeech::new();
+    let mut recognizer = synchronous_recognizer(&mock).await;
+
+    for audio_source in [
+        None,
+        Some(AudioSource::Content(vec![])),
+        Some(AudioSource::Uri(" ".into())),
+    ] {
+        let error = recognizer
+            .recognize(RecognizeRequest {
+                recognizer: RECOGNIZER.to_string(),
+                audio_source,
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.code.as_deref(), Some("AUDIO_SOURCE_CONFLICT"));
+    }
+    // request is not sent to Google
+    assert!(mock.recognize_requests().is_empty());
+
+    recognizer
+        .recognize(RecognizeRequest {
+            recognizer: RECOGNIZER.to_string(),
+            audio_source: Some(AudioSource::Uri("gs://bucket/audio.wav".into())),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_recognize_with_headers() {
+    let mock = MockSpeech::new();
+    let mut recognizer = synchronous_recognizer(&mock).await;
+    let request = || RecognizeRequest {
+        recognizer: RECOGNIZER.to_string(),
+        audio_source: Some(AudioSource::Content(vec![1, 2, 3])),
+        ..Default::default()
+    };
+
+    for (name, value) in [("x-trace id", "trace-1"), ("x-trace-id", "trace\n1")] {
+        let headers = HashMap::from([(name.to_string(), value.to_string())]);
+        let error = recognizer
+            .recognize_with_headers(request(), headers)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+    }
+    // same validation of audio source as recognize
+    let error = recognizer
+        .recognize_with_headers(
+            RecognizeRequest {
+                audio_source: None,
+                ..request()
+            },
+            HashMap::new(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code.as_deref(), Some("AUDIO_SOURCE_CONFLICT"));
+    // invalid requests are not sent to Google
+    assert!(mock.recognize_requests().is_empty());
+
+    let headers = HashMap::from([("x-trace-id".to_string(), "trace-1".to_string())]);
+    recognizer
+        .recognize_with_headers(request(), headers)
+        .await
+        .unwrap();
+    let received = mock.recognize_requests();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].metadata.get("x-trace-id").unwrap(), "trace-1");
+    assert_eq!(
+        received[0].metadata.get("authorization").unwrap(),
+        "Bearer test"
+    );
+}
+
+#[tokio::test]
+async fn test_pooled_channel() {
+    let mock = MockSpeech::new();
+    let addr = mock.serve_addr().await.unwrap();
+    let endpoint = tonic::transport::Endpoint::from_shared(format!("http://{}", addr)).unwrap();
+    let pool = PooledChannel::from_endpoint(endpoint, 3);
+    assert_eq!(pool.pool_size(), 3);
+
+    let mut calls = vec![];
+    for _ in 0..6 {
+        let channel = pool.channel();
+        calls.push(tokio::spawn(async move {
+            let mut recognizer = Recognizer::create_synchronous_recognizer_with_channel(
+                channel,
+                "Bearer test".into(),
+            )
+            .await
+            .unwrap();
+            recognizer
+                .recognize(RecognizeRequest {
+                    recognizer: RECOGNIZER.to_string(),
+                    audio_source: Some(AudioSource::Content(vec![0u8; 320])),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }));
+    }
+    for call in calls {
+        call.await.unwrap();
+    }
+    assert_eq!(mock.recognize_requests().len(), 6);
+}
+
+#[test]
+fn test_redact_requests() {
+    let request = RecognizeRequest {
+        recognizer: RECOGNIZER.to_string(),
+        audio_source: Some(AudioSource::Content(vec![7u8; 4096])),
+        ..Default::default()
+    };
+    let redacted = redact_recognize_request(&request);
+    assert!(redacted.contains("Content(<4096 bytes>)"));
+    assert!(redacted.contains(RECOGNIZER));
+    assert!(!redacted.contains("7, 7"));
+
+    let uri = RecognizeRequest {
+        audio_source: Some(AudioSource::Uri("gs://bucket/a.wav".to_string())),
+        ..Default::default()
+    };
+    assert!(redact_recognize_request(&uri).contains("gs://bucket/a.wav"));
+
+    let audio = Recognizer::streaming_request_from_bytes(vec![7u8; 320], String::new());
+    let redacted = redact_streaming_request(&audio);
+    assert!(redacted.contains("Audio(<320 bytes>)"));
+    assert!(!redacted.contains("7, 7"));
+}
+
+#[tokio::test]
+async fn test_recognize_bytes_cache() {
+    let mock = MockSpeech::new();
+    mock.push_recognize_response(RecognizeResponse {
+        results: vec![result("hello", "en-us")],
+        metadata: None,
+    });
+    let mut recognizer = synchronous_recognizer(&mock).await;
+    recognizer.set_recognition_cache(Some(Arc::new(LruRecognitionCache::new(10))));
+
+    for _ in 0..2 {
+        let response = recognizer
+            .recognize_bytes(
+                vec![1u8; 320],
+                RECOGNIZER.to_string(),
+                RecognitionConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.results[0].alternatives[0].transcript, "hello");
+    }
+    assert_eq!(mock.recognize_requests().len(), 1);
+    assert_eq!(recognizer.cache_stats(), CacheStats { hits: 1, misses: 1 });
+
+    // different audio is not answered from cache
+    let response = recognizer
+        .recognize_bytes(
+            vec![2u8; 320],
+            RECOGNIZER.to_string(),
+            RecognitionConfig::default(),
+        )
+        .await
+        .unwrap();
+    assert!(response.results.is_empty());
+    assert_eq!(mock.recognize_requests().len(), 2);
+    assert_eq!(recognizer.cache_stats(), CacheStats { hits: 1, misses: 2 });
+}